@@ -0,0 +1,133 @@
+use runtime::{SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
+use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
+use cell::InstantCell;
+
+/// Process that gets the value of a cell.
+pub struct ReadCell<C>(pub(crate) C);
+
+/// Process that writes some value to a cell.
+pub struct WriteCell<C, A> {
+    pub(crate) cell: C,
+    pub(crate) written: A,
+}
+
+pub trait CanWrite<R, A> {
+    /// Stages the value `written`, to be merged at the end of the instant.
+    fn stage(&self, runtime: &mut R, written: A);
+}
+
+impl<C> Process for ReadCell<C> where C: InstantCell {
+    type Value = C::Value;
+
+    fn describe(&self) -> Description {
+        Description::leaf("read_cell")
+    }
+}
+
+impl<C> ProcessMut for ReadCell<C> where C: InstantCell {}
+
+impl<C, A> Process for WriteCell<C, A> where C: InstantCell, A: 'static {
+    type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::leaf("write_cell")
+    }
+}
+
+impl<C, A> ProcessMut for WriteCell<C, A> where C: InstantCell, A: 'static {}
+
+// Non-parallel
+
+impl<C> ProcessSt for ReadCell<C> where C: InstantCell {
+    fn call<K>(self, runtime: &mut SingleThreadRuntime, next: K)
+        where K: ContinuationSt<Self::Value>
+    {
+        next.call(runtime, self.0.get());
+    }
+}
+
+impl<C> ProcessMutSt for ReadCell<C> where C: InstantCell {
+    fn call_mut<K>(self, runtime: &mut SingleThreadRuntime, next: K)
+        where Self: Sized, K: ContinuationSt<(Self, Self::Value)>
+    {
+        let v = self.0.get();
+        next.call(runtime, (self, v));
+    }
+}
+
+impl<C, A> ProcessSt for WriteCell<C, A>
+    where C: InstantCell + CanWrite<SingleThreadRuntime, A>, A: 'static
+{
+    fn call<K>(self, runtime: &mut SingleThreadRuntime, next: K)
+        where K: ContinuationSt<Self::Value>
+    {
+        self.cell.stage(runtime, self.written);
+        next.call(runtime, ());
+    }
+}
+
+impl<C, A> ProcessMutSt for WriteCell<C, A>
+    where C: InstantCell + CanWrite<SingleThreadRuntime, A>, A: Clone + 'static
+{
+    fn call_mut<K>(self, runtime: &mut SingleThreadRuntime, next: K)
+        where Self: Sized, K: ContinuationSt<(Self, Self::Value)>
+    {
+        self.cell.stage(runtime, self.written.clone());
+        next.call(runtime, (self, ()));
+    }
+}
+
+// Parallel
+
+impl<C> ConstraintOnValue for ReadCell<C> where C: InstantCell, C::Value: Send + Sync {
+    type T = C::Value;
+}
+
+impl<C> ProcessPl for ReadCell<C> where C: InstantCell + Send + Sync, C::Value: Send + Sync {
+    fn call<K>(self, runtime: &mut ParallelRuntime, next: K)
+        where K: ContinuationPl<Self::Value>
+    {
+        next.call(runtime, self.0.get());
+    }
+}
+
+impl<C> ProcessMutPl for ReadCell<C>
+    where C: InstantCell + Send + Sync, C::Value: Send + Sync
+{
+    fn call_mut<K>(self, runtime: &mut ParallelRuntime, next: K)
+        where Self: Sized, K: ContinuationPl<(Self, Self::Value)>
+    {
+        let v = self.0.get();
+        next.call(runtime, (self, v));
+    }
+}
+
+impl<C, A> ConstraintOnValue for WriteCell<C, A> {
+    type T = ();
+}
+
+impl<C, A> ProcessPl for WriteCell<C, A>
+    where C: InstantCell + CanWrite<ParallelRuntime, A> + Send + Sync,
+          A: Send + Sync + 'static
+{
+    fn call<K>(self, runtime: &mut ParallelRuntime, next: K)
+        where K: ContinuationPl<Self::Value>
+    {
+        self.cell.stage(runtime, self.written);
+        next.call(runtime, ());
+    }
+}
+
+impl<C, A> ProcessMutPl for WriteCell<C, A>
+    where C: InstantCell + CanWrite<ParallelRuntime, A> + Send + Sync,
+          A: Clone + Send + Sync + 'static
+{
+    fn call_mut<K>(self, runtime: &mut ParallelRuntime, next: K)
+        where Self: Sized, K: ContinuationPl<(Self, Self::Value)>
+    {
+        self.cell.stage(runtime, self.written.clone());
+        next.call(runtime, (self, ()));
+    }
+}
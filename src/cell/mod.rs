@@ -0,0 +1,37 @@
+//! Shared state with synchronous semantics.
+//!
+//! An `InstantCell` holds a value that every process can read and write. During
+//! an instant all the reads see the value committed at the end of the previous
+//! instant, while the writes are staged and merged into the value by a user
+//! function at the end of the instant. The result of an instant thus doesn't
+//! depend on the order in which the processes are executed, as long as the merge
+//! function doesn't depend on the order of the writes (for the parallel version
+//! this order is arbitrary).
+
+mod access;
+mod single_thread;
+mod parallel;
+pub use self::access::{ReadCell, WriteCell, CanWrite};
+pub use self::single_thread::InstantCellSt;
+pub use self::parallel::InstantCellPl;
+
+/// A cell whose writes only take effect at the end of the instant.
+pub trait InstantCell: Clone + 'static {
+    /// The value stored in the cell.
+    type Value: Clone;
+
+    /// Returns the value committed at the end of the last instant.
+    fn get(&self) -> Self::Value;
+
+    /// Returns a process that gets immediately the value committed at the end
+    /// of the last instant.
+    fn read(&self) -> ReadCell<Self> where Self: Sized {
+        ReadCell(self.clone())
+    }
+
+    /// Returns a process that stages `written` to be merged into the value of
+    /// the cell at the end of the instant.
+    fn write<A>(&self, written: A) -> WriteCell<Self, A> where Self: Sized {
+        WriteCell { cell: self.clone(), written }
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::{Arc, Mutex};
+use std::mem;
+
+use runtime::ParallelRuntime;
+use cell::{InstantCell, CanWrite};
+
+/// The content of a cell.
+struct InstantCellContent<T, A, F> {
+    value: Mutex<T>,
+    staged: Mutex<Vec<A>>,
+    merge: Mutex<F>,
+    commit_scheduled: Mutex<bool>,
+}
+
+/// A parallel instant cell.
+pub struct InstantCellPl<T, A, F>(Arc<InstantCellContent<T, A, F>>);
+
+impl<T, A, F> Clone for InstantCellPl<T, A, F> {
+    fn clone(&self) -> Self {
+        InstantCellPl(self.0.clone())
+    }
+}
+
+impl<T, A, F> InstantCellPl<T, A, F>
+    where T: Clone + Send + Sync + 'static,
+          A: Send + Sync + 'static,
+          F: FnMut(A, &mut T) + Send + 'static
+{
+    /// Creates a new cell holding `initial`. At the end of each instant, every
+    /// value written during the instant is merged into the value of the cell
+    /// by `merge`. The writes can come in any order.
+    pub fn new(initial: T, merge: F) -> Self {
+        InstantCellPl(Arc::new(InstantCellContent {
+            value: Mutex::new(initial),
+            staged: Mutex::new(Vec::new()),
+            merge: Mutex::new(merge),
+            commit_scheduled: Mutex::new(false),
+        }))
+    }
+
+    /// Merges the staged writes into the value.
+    fn commit(&self) {
+        let staged = mem::take(&mut *self.0.staged.lock().unwrap());
+        let mut value = self.0.value.lock().unwrap();
+        let mut merge = self.0.merge.lock().unwrap();
+        for written in staged {
+            (*merge)(written, &mut *value);
+        }
+        *self.0.commit_scheduled.lock().unwrap() = false;
+    }
+}
+
+impl<T, A, F> InstantCell for InstantCellPl<T, A, F>
+    where T: Clone + Send + Sync + 'static,
+          A: Send + Sync + 'static,
+          F: FnMut(A, &mut T) + Send + 'static
+{
+    type Value = T;
+
+    fn get(&self) -> T {
+        self.0.value.lock().unwrap().clone()
+    }
+}
+
+impl<T, A, F> CanWrite<ParallelRuntime, A> for InstantCellPl<T, A, F>
+    where T: Clone + Send + Sync + 'static,
+          A: Send + Sync + 'static,
+          F: FnMut(A, &mut T) + Send + 'static
+{
+    fn stage(&self, runtime: &mut ParallelRuntime, written: A) {
+        self.0.staged.lock().unwrap().push(written);
+        let mut commit_scheduled = self.0.commit_scheduled.lock().unwrap();
+        if !*commit_scheduled {
+            *commit_scheduled = true;
+            drop(commit_scheduled);
+            let cell = self.clone();
+            runtime.on_end_of_instant(Box::new(move |_: &mut ParallelRuntime, ()| {
+                cell.commit();
+            }));
+        }
+    }
+}
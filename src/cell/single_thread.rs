@@ -0,0 +1,74 @@
+use std::rc::Rc;
+use std::cell::{Cell, RefCell};
+use std::mem;
+
+use runtime::SingleThreadRuntime;
+use cell::{InstantCell, CanWrite};
+
+/// The content of a cell.
+struct InstantCellContent<T, A, F> {
+    value: RefCell<T>,
+    staged: RefCell<Vec<A>>,
+    merge: RefCell<F>,
+    commit_scheduled: Cell<bool>,
+}
+
+/// A non-parallel instant cell.
+pub struct InstantCellSt<T, A, F>(Rc<InstantCellContent<T, A, F>>);
+
+impl<T, A, F> Clone for InstantCellSt<T, A, F> {
+    fn clone(&self) -> Self {
+        InstantCellSt(self.0.clone())
+    }
+}
+
+impl<T, A, F> InstantCellSt<T, A, F>
+    where T: Clone + 'static, A: 'static, F: FnMut(A, &mut T) + 'static
+{
+    /// Creates a new cell holding `initial`. At the end of each instant, every
+    /// value written during the instant is merged into the value of the cell
+    /// by `merge`, in the order of the writes.
+    pub fn new(initial: T, merge: F) -> Self {
+        InstantCellSt(Rc::new(InstantCellContent {
+            value: RefCell::new(initial),
+            staged: RefCell::new(Vec::new()),
+            merge: RefCell::new(merge),
+            commit_scheduled: Cell::new(false),
+        }))
+    }
+
+    /// Merges the staged writes into the value.
+    fn commit(&self) {
+        let staged = mem::take(&mut *self.0.staged.borrow_mut());
+        let mut value = self.0.value.borrow_mut();
+        let mut merge = self.0.merge.borrow_mut();
+        for written in staged {
+            (*merge)(written, &mut *value);
+        }
+        self.0.commit_scheduled.set(false);
+    }
+}
+
+impl<T, A, F> InstantCell for InstantCellSt<T, A, F>
+    where T: Clone + 'static, A: 'static, F: FnMut(A, &mut T) + 'static
+{
+    type Value = T;
+
+    fn get(&self) -> T {
+        self.0.value.borrow().clone()
+    }
+}
+
+impl<T, A, F> CanWrite<SingleThreadRuntime, A> for InstantCellSt<T, A, F>
+    where T: Clone + 'static, A: 'static, F: FnMut(A, &mut T) + 'static
+{
+    fn stage(&self, runtime: &mut SingleThreadRuntime, written: A) {
+        self.0.staged.borrow_mut().push(written);
+        if !self.0.commit_scheduled.replace(true) {
+            let cell = self.clone();
+            runtime.on_end_of_instant(Box::new(move |_: &mut SingleThreadRuntime, ()| {
+                cell.commit();
+            }));
+        }
+    }
+}
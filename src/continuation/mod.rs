@@ -9,6 +9,8 @@ mod map;
 pub use self::map::Map;
 mod pause;
 pub use self::pause::Pause;
+mod pause_n;
+pub use self::pause_n::{PauseN, PauseUntil};
 
 use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
 
@@ -35,6 +37,17 @@ pub trait Continuation<R, V>: 'static where R: Runtime {
     fn pause(self) -> Pause<Self> where Self: Sized {
         Pause(self)
     }
+
+    /// Create a new continuation that calls `Self` `n` instants later.
+    fn pause_n(self, n: usize) -> PauseN<Self> where Self: Sized {
+        PauseN { continuation: self, n }
+    }
+
+    /// Create a new continuation that calls `Self` at the given instant, the first
+    /// instant of the execution being 0.
+    fn pause_until(self, instant: usize) -> PauseUntil<Self> where Self: Sized {
+        PauseUntil { continuation: self, instant }
+    }
 }
 
 impl<R, V, F> Continuation<R, V> for F where R: Runtime, F: FnOnce(&mut R, V) + 'static {
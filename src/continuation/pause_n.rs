@@ -0,0 +1,67 @@
+use runtime::{SingleThreadRuntime, ParallelRuntime};
+use continuation::{Continuation, ContinuationSt, ContinuationPl};
+
+/// A continuation that calls another continuation `n` instants later.
+pub struct PauseN<C> { pub(crate) continuation: C, pub(crate) n: usize }
+
+impl<C, V> Continuation<SingleThreadRuntime, V> for PauseN<C>
+    where C: ContinuationSt<V>, V: 'static
+{
+    fn call(self, runtime: &mut SingleThreadRuntime, value: V) {
+        let instant = runtime.current_instant() + self.n;
+        PauseUntil { continuation: self.continuation, instant }.call(runtime, value);
+    }
+    
+    fn call_box(self: Box<Self>, runtime: &mut SingleThreadRuntime, value: V) {
+        (*self).call(runtime, value);
+    }
+}
+
+impl<C, V> Continuation<ParallelRuntime, V> for PauseN<C>
+    where C: ContinuationPl<V>, V: Send + Sync + 'static
+{
+    fn call(self, runtime: &mut ParallelRuntime, value: V) {
+        let instant = runtime.current_instant() + self.n;
+        PauseUntil { continuation: self.continuation, instant }.call(runtime, value);
+    }
+    
+    fn call_box(self: Box<Self>, runtime: &mut ParallelRuntime, value: V) {
+        (*self).call(runtime, value);
+    }
+}
+
+/// A continuation that calls another continuation at a given instant, or
+/// immediately if this instant is already reached.
+pub struct PauseUntil<C> { pub(crate) continuation: C, pub(crate) instant: usize }
+
+impl<C, V> Continuation<SingleThreadRuntime, V> for PauseUntil<C>
+    where C: ContinuationSt<V>, V: 'static
+{
+    fn call(self, runtime: &mut SingleThreadRuntime, value: V) {
+        if self.instant <= runtime.current_instant() {
+            self.continuation.call(runtime, value);
+        } else {
+            runtime.on_instant(self.instant, Box::new(self.continuation.map(|()| value)));
+        }
+    }
+    
+    fn call_box(self: Box<Self>, runtime: &mut SingleThreadRuntime, value: V) {
+        (*self).call(runtime, value);
+    }
+}
+
+impl<C, V> Continuation<ParallelRuntime, V> for PauseUntil<C>
+    where C: ContinuationPl<V>, V: Send + Sync + 'static
+{
+    fn call(self, runtime: &mut ParallelRuntime, value: V) {
+        if self.instant <= runtime.current_instant() {
+            self.continuation.call(runtime, value);
+        } else {
+            runtime.on_instant(self.instant, Box::new(self.continuation.map(|()| value)));
+        }
+    }
+    
+    fn call_box(self: Box<Self>, runtime: &mut ParallelRuntime, value: V) {
+        (*self).call(runtime, value);
+    }
+}
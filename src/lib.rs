@@ -5,6 +5,9 @@
 //! and continuations that are essential for the execution engines, the module
 //! `process` contating different methods for the creation of new
 //! processes, and four kinds of signals in charge of inter-process communication.  
+//! The module `cell` provides shared state whose writes only take effect at the
+//! end of the instant, and the module `testing` some helpers to check that the
+//! results of a program don't depend on the order of execution.
 //!
 //! In most of the time the users only care about modules `process` and `signal`.
 //! However it is not always trivial to build a process due to the necessity of
@@ -28,6 +31,8 @@ pub mod runtime;
 pub mod continuation;
 pub mod process;
 pub mod signal;
+pub mod cell;
+pub mod testing;
 
 pub use process::*;
 pub use signal::*;
@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 /// Chains a computation onto the end of another process.
@@ -10,6 +10,10 @@ impl<P1, P2, F> Process for AndThen<P1, F>
     where P1: Process, P2: Process, F: FnOnce(P1::Value) -> P2 + 'static
 {
     type Value = P2::Value;
+
+    fn describe(&self) -> Description {
+        Description::node("and_then", vec![self.process.describe()])
+    }
 }
 
 impl<P1, P2, F> ProcessMut for AndThen<P1, F>
@@ -0,0 +1,44 @@
+use std::fmt::Write;
+
+/// A node of the tree describing the structure of a process, as returned by
+/// `Process::describe`. Each node is a combinator and its children are the
+/// processes it is built from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Description {
+    pub label: String,
+    pub children: Vec<Description>,
+}
+
+impl Description {
+    /// Creates a node without children.
+    pub fn leaf<L>(label: L) -> Self where L: Into<String> {
+        Description { label: label.into(), children: Vec::new() }
+    }
+
+    /// Creates a node with the given children.
+    pub fn node<L>(label: L, children: Vec<Description>) -> Self where L: Into<String> {
+        Description { label: label.into(), children }
+    }
+
+    /// Renders the tree as a Graphviz DOT graph.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph process {\n");
+        self.write_dot(&mut dot, &mut 0);
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes the node and its children, numbered from `next_id`, and returns
+    /// the id of the node.
+    fn write_dot(&self, dot: &mut String, next_id: &mut usize) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        let label = self.label.replace('\\', "\\\\").replace('"', "\\\"");
+        writeln!(dot, "    n{} [label=\"{}\"];", id, label).unwrap();
+        for child in &self.children {
+            let child_id = child.write_dot(dot, next_id);
+            writeln!(dot, "    n{} -> n{};", id, child_id).unwrap();
+        }
+        id
+    }
+}
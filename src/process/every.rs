@@ -0,0 +1,63 @@
+use runtime::{SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
+use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
+use process::{Loop, Join, PauseN, Value};
+
+/// Executes a process every `n` instants forever.
+///
+/// Each execution of the process is joined with a wait of `n` instants, so if the
+/// process lasts less than `n` instants it is started again exactly `n` instants
+/// after its previous start, and otherwise it is started again as soon as it terminates.
+pub struct Every<P>(pub(crate) Loop<Join<P, PauseN<Value<()>>>>);
+
+impl<P> Process for Every<P> where P: ProcessMut {
+    type Value = ();
+
+    fn describe(&self) -> Description {
+        let Join(ref process, ref pause) = (self.0).0;
+        Description::node(format!("every({})", pause.n), vec![process.describe()])
+    }
+}
+
+impl<P> ProcessMut for Every<P> where P: ProcessMut {}
+
+// Implements the traits for the single thread version of the library.
+
+impl<P> ProcessSt for Every<P> where P: ProcessMutSt {
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        self.0.call(runtime, next);
+    }
+}
+
+impl<P> ProcessMutSt for Every<P> where P: ProcessMutSt {
+    fn call_mut<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        self.0.call_mut(runtime, next.map(|(p, v)| (Every(p), v)));
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<P> ConstraintOnValue for Every<P> where P: ProcessMut {
+    type T = ();
+}
+
+impl<P> ProcessPl for Every<P> where P: ProcessMutPl {
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        self.0.call(runtime, next);
+    }
+}
+
+impl<P> ProcessMutPl for Every<P> where P: ProcessMutPl {
+    fn call_mut<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        self.0.call_mut(runtime, next.map(|(p, v)| (Every(p), v)));
+    }
+}
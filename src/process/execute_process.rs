@@ -3,13 +3,32 @@ use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
 
 use runtime::{Runtime, SingleThreadRuntime};
-use runtime::{ParallelRuntime, ParallelRuntimeCollection};
+use runtime::{ParallelRuntime, ParallelRuntimeCollection, Subscriber};
+use runtime::{InstantStats, StatsCollector};
 use process::{ProcessSt, ProcessPl};
 
 /// Executes a process in a newly created runtime and return its value (without parallization).
 pub fn execute_process<P>(p: P) -> P::Value where P: ProcessSt
+{
+    execute_in_runtime(p, SingleThreadRuntime::new())
+}
+
+/// Same as `execute_process`, with a subscriber notified of the events of the runtime.
+pub fn execute_process_traced<P>(p: P, subscriber: Arc<Subscriber>) -> P::Value
+    where P: ProcessSt
 {
     let mut runtime = SingleThreadRuntime::new();
+    runtime.set_subscriber(subscriber);
+    execute_in_runtime(p, runtime)
+}
+
+/// Same as `execute_process`, the continuations of each instant being executed in
+/// an order given by `seed` (see `SingleThreadRuntime::shuffled`).
+pub fn execute_process_shuffled<P>(p: P, seed: u64) -> P::Value where P: ProcessSt {
+    execute_in_runtime(p, SingleThreadRuntime::shuffled(seed))
+}
+
+fn execute_in_runtime<P>(p: P, mut runtime: SingleThreadRuntime) -> P::Value where P: ProcessSt {
     let res: Rc<RefCell<Option<P::Value>>> = Rc::new(RefCell::new(None));
     let res2 = res.clone();
     let c = move |_: &mut SingleThreadRuntime, v| *res2.borrow_mut() = Some(v);
@@ -19,6 +38,15 @@ pub fn execute_process<P>(p: P) -> P::Value where P: ProcessSt
     res.take().unwrap()
 }
 
+/// Same as `execute_process`, also returning the statistics of each instant.
+pub fn execute_process_with_stats<P>(p: P) -> (P::Value, Vec<InstantStats>)
+    where P: ProcessSt
+{
+    let collector = Arc::new(StatsCollector::new(1));
+    let v = execute_process_traced(p, collector.clone());
+    (v, collector.stats())
+}
+
 /// Executes a process in newly created runtimes and return its value (with parallization).
 pub fn execute_process_parallel<P>(p: P, num_runtimes: usize) -> P::Value where P: ProcessPl {
     if num_runtimes == 0 {
@@ -54,3 +82,59 @@ pub fn execute_process_parallel_with_main<F, P>(f: F, p: P, num_runtimes: usize)
     let mut res = res.lock().unwrap();
     res.take().unwrap()
 }
+
+/// Same as `execute_process_parallel`, with a subscriber shared by all the runtimes.
+pub fn execute_process_parallel_traced<P>(
+    p: P, num_runtimes: usize, subscriber: Arc<Subscriber>) -> P::Value where P: ProcessPl
+{
+    if num_runtimes == 0 {
+        panic!("There should be at least one runtime!");
+    }
+    let mut runtime_col = ParallelRuntimeCollection::new(num_runtimes);
+    runtime_col.set_subscriber(subscriber);
+    execute_in_collection(p, runtime_col)
+}
+
+/// Same as `execute_process_parallel`, also returning the statistics of each instant.
+pub fn execute_process_parallel_with_stats<P>(p: P, num_runtimes: usize)
+    -> (P::Value, Vec<InstantStats>) where P: ProcessPl
+{
+    let collector = Arc::new(StatsCollector::new(num_runtimes));
+    let v = execute_process_parallel_traced(p, num_runtimes, collector.clone());
+    (v, collector.stats())
+}
+
+/// Same as `execute_process_parallel`, the random choices of the engine being made
+/// from `seed`. The execution still depends on the scheduling of the threads.
+pub fn execute_process_parallel_seeded<P>(p: P, num_runtimes: usize, seed: u64) -> P::Value
+    where P: ProcessPl
+{
+    if num_runtimes == 0 {
+        panic!("There should be at least one runtime!");
+    }
+    execute_in_collection(p, ParallelRuntimeCollection::with_seed(num_runtimes, seed))
+}
+
+/// Same as `execute_process_parallel`, in the deterministic mode of
+/// `ParallelRuntimeCollection`: the result and the values of the signals don't
+/// depend on the scheduling of the threads, within the limits explained there.
+pub fn execute_process_parallel_deterministic<P>(p: P, num_runtimes: usize) -> P::Value
+    where P: ProcessPl
+{
+    if num_runtimes == 0 {
+        panic!("There should be at least one runtime!");
+    }
+    execute_in_collection(p, ParallelRuntimeCollection::deterministic(num_runtimes))
+}
+
+fn execute_in_collection<P>(p: P, mut runtime_col: ParallelRuntimeCollection) -> P::Value
+    where P: ProcessPl
+{
+    let res: Arc<Mutex<Option<P::Value>>> = Arc::new(Mutex::new(None));
+    let res2 = res.clone();
+    let c = move |_: &mut ParallelRuntime, v| *res2.lock().unwrap() = Some(v);
+    runtime_col.register_work(Box::new(|r: &mut ParallelRuntime, _| p.call(r, c)));
+    runtime_col.execute(|| ());
+    let mut res = res.lock().unwrap();
+    res.take().unwrap()
+}
@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 /// Flattens the process when it returns another process to get only the
@@ -9,6 +9,10 @@ pub struct Flatten<P>(pub(crate) P);
 
 impl<P> Process for Flatten<P> where P: Process, P::Value: Process {
     type Value = <P::Value as Process>::Value;
+
+    fn describe(&self) -> Description {
+        Description::node("flatten", vec![self.0.describe()])
+    }
 }
 
 impl<P> ProcessMut for Flatten<P> where P: ProcessMut, P::Value: Process {}
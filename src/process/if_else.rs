@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 /// Selects the process to run according to what the previous process returns.
@@ -14,6 +14,11 @@ impl<P, P1, P2, V> Process for IfElse<P, P1, P2>
     where P: Process<Value=bool>, P1: Process<Value=V>, P2: Process<Value=V>
 {
     type Value = V;
+
+    fn describe(&self) -> Description {
+        Description::node("if_else", vec![
+            self.process.describe(), self.if_branch.describe(), self.else_branch.describe()])
+    }
 }
 
 impl<P, P1, P2, V> ProcessMut for IfElse<P, P1, P2>
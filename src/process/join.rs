@@ -4,13 +4,17 @@ use either::{Either, Left, Right};
 
 use runtime::SingleThreadRuntime;
 use continuation::ContinuationSt;
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 
 /// Parallel composition of two processes.
 pub struct Join<P1, P2>(pub(crate) P1, pub(crate) P2);
 
 impl<P1, P2> Process for Join<P1, P2> where P1: Process, P2: Process {
     type Value = (P1::Value, P2::Value);
+
+    fn describe(&self) -> Description {
+        Description::node("join", vec![self.0.describe(), self.1.describe()])
+    }
 }
 
 impl<P1, P2> ProcessMut for Join<P1, P2> where P1: ProcessMut, P2: ProcessMut {}
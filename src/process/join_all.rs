@@ -3,7 +3,7 @@ use std::cell::RefCell;
 
 use runtime::SingleThreadRuntime;
 use continuation::{Continuation, ContinuationSt};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 
 /// Creates a process that executes a collection of processes in parallel and collects
 /// the results into a destination `Vec<T>` in the same order as they were provided.  
@@ -21,6 +21,10 @@ pub struct JoinAll<P>(pub(crate) Vec<P>);
 
 impl<P> Process for JoinAll<P> where P: Process {
     type Value = Vec<P::Value>;
+
+    fn describe(&self) -> Description {
+        Description::node("join_all", self.0.iter().map(|p| p.describe()).collect())
+    }
 }
 
 impl <P> ProcessMut for JoinAll<P> where P: ProcessMut {}
@@ -71,15 +75,15 @@ impl<P> ProcessMutSt for JoinAll<P> where P: ProcessMutSt {
     }
 }
 
-/// Used by `JoinAll` as a barrier for multiple processes.
-struct JoinPoint<V, C> {
+/// Used by `JoinAll` and `JoinAllEach` as a barrier for multiple processes.
+pub(crate) struct JoinPoint<V, C> {
     counter: usize,
     values: Vec<Option<V>>,
     continuation: Option<C>,
 }
 
 impl<V, C> JoinPoint<V, C> where C: ContinuationSt<Vec<V>> {
-    fn new(num_procs: usize, continuation: C) -> Self {
+    pub(crate) fn new(num_procs: usize, continuation: C) -> Self {
         JoinPoint {
             counter: 0,
             values: (0..num_procs).map(|_| None).collect(),
@@ -87,7 +91,7 @@ impl<V, C> JoinPoint<V, C> where C: ContinuationSt<Vec<V>> {
         }
     }
     
-    fn call_ref(&mut self, runtime: &mut SingleThreadRuntime, (value, p_id): (V, usize)) {
+    pub(crate) fn call_ref(&mut self, runtime: &mut SingleThreadRuntime, (value, p_id): (V, usize)) {
         assert!(self.values[p_id].is_none());
         self.values[p_id] = Some(value);
         self.counter += 1;
@@ -0,0 +1,83 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+use runtime::SingleThreadRuntime;
+use continuation::ContinuationSt;
+use process::{Process, ProcessSt, Description};
+use process::join_all::JoinPoint;
+use signal::ValuedSignal;
+use signal::valued_signal::EmitValue;
+
+/// Creates a process that executes a collection of processes in parallel and, as soon
+/// as one of them terminates, calls `handler` on its value and executes the returned
+/// process in the same instant.  
+/// The results of these handler processes are collected into a `Vec` in the same order
+/// as the original processes were provided, and the whole process terminates when all
+/// the handlers are done. This allows to work on early results (for example by emitting
+/// them on a signal) without waiting for the slowest process.
+pub fn join_all_each<I, F, P>(i: I, handler: F) -> JoinAllEach<I::Item, F>
+    where I: IntoIterator, I::Item: Process, F: FnMut(<I::Item as Process>::Value) -> P
+{
+    JoinAllEach { processes: i.into_iter().collect(), handler: Mutex::new(handler) }
+}
+
+/// Same as `join_all_each`, the value of each process being emitted on `signal` as
+/// soon as it terminates.
+pub fn join_all_emit<I, S, V>(i: I, signal: &S)
+    -> JoinAllEach<I::Item, impl FnMut(V) -> EmitValue<S, V>>
+    where I: IntoIterator, I::Item: Process<Value=V>, S: ValuedSignal
+{
+    let signal = signal.clone();
+    join_all_each(i, move |v| signal.emit(v))
+}
+
+/// A process which takes a list of processes and handles their values one by one
+/// in the order in which they are completed.
+pub struct JoinAllEach<P, F> {
+    pub(crate) processes: Vec<P>,
+    /// In a `Mutex` so that the process can be shared between threads as long as
+    /// the handler can be sent to another thread.
+    pub(crate) handler: Mutex<F>,
+}
+
+impl<P, Q, F> Process for JoinAllEach<P, F>
+    where P: Process, Q: Process, F: FnMut(P::Value) -> Q + 'static
+{
+    type Value = Vec<Q::Value>;
+
+    fn describe(&self) -> Description {
+        Description::node(
+            "join_all_each", self.processes.iter().map(|p| p.describe()).collect())
+    }
+}
+
+// Implements the traits for the single thread version of the library.
+
+impl<P, Q, F> ProcessSt for JoinAllEach<P, F>
+    where P: ProcessSt, Q: ProcessSt, F: FnMut(P::Value) -> Q + 'static
+{
+    fn call<C>(mut self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        if self.processes.is_empty() {
+            next.call(runtime, Vec::new());
+        } else {
+            let num_procs = self.processes.len();
+            let joint_point = Rc::new(RefCell::new(JoinPoint::new(num_procs, next)));
+            let handler = Rc::new(RefCell::new(self.handler.into_inner().unwrap()));
+            while let Some(p) = self.processes.pop() {
+                let p_id = self.processes.len();
+                let joint_point = joint_point.clone();
+                let handler = handler.clone();
+                p.call(runtime, move |r: &mut SingleThreadRuntime, v| {
+                    // The borrow of the handler must end before the handler process
+                    // is called since it can call back the handler of another process.
+                    let q = (*handler.borrow_mut())(v);
+                    q.call(r, move |r: &mut SingleThreadRuntime, v|
+                        joint_point.borrow_mut().call_ref(r, (v, p_id)));
+                });
+            }
+        }
+    }
+}
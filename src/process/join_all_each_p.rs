@@ -0,0 +1,47 @@
+use std::sync::{Arc, Mutex};
+
+use runtime::ParallelRuntime;
+use continuation::ContinuationPl;
+use process::{ProcessPl, ConstraintOnValue};
+
+use process::JoinAllEach;
+use process::join_all_p::JoinPoint;
+
+// Implements the traits for the parallel version of the library.
+
+impl<P, Q, F> ConstraintOnValue for JoinAllEach<P, F>
+    where P: ProcessPl, Q: ProcessPl, F: FnMut(P::T) -> Q + 'static
+{
+    type T = Vec<Q::Value>;
+}
+
+impl<P, Q, F> ProcessPl for JoinAllEach<P, F>
+    where P: ProcessPl, Q: ProcessPl, F: FnMut(P::T) -> Q + Send + 'static
+{
+    fn call<C>(mut self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        if self.processes.is_empty() {
+            next.call(runtime, Vec::new());
+        } else {
+            let num_procs = self.processes.len();
+            let joint_point = Arc::new(Mutex::new(JoinPoint::new(num_procs, next)));
+            let handler = Arc::new(self.handler);
+            while let Some(p) = self.processes.pop() {
+                let p_id = self.processes.len();
+                let joint_point = joint_point.clone();
+                let handler = handler.clone();
+                let c = move |r: &mut ParallelRuntime, ()| {
+                    p.call(r, move |r: &mut ParallelRuntime, v| {
+                        // The lock on the handler is released before the handler
+                        // process is called.
+                        let q = (*handler.lock().unwrap())(v);
+                        q.call(r, move |r: &mut ParallelRuntime, v|
+                            joint_point.lock().unwrap().call_ref(r, (v, p_id)));
+                    });
+                };
+                runtime.spawn(Box::new(c));
+            };
+        }
+    }
+}
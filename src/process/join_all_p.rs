@@ -1,6 +1,6 @@
 use std::sync::{Arc, Mutex};
 
-use runtime::ParallelRuntime;
+use runtime::{ParallelRuntime, Arrivals};
 use continuation::{Continuation, ContinuationPl};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
@@ -27,7 +27,7 @@ impl<P> ProcessPl for JoinAll<P> where P: ProcessPl {
                     p.call(r, move |r: &mut ParallelRuntime, v|
                         joint_point.lock().unwrap().call_ref(r, (v, p_id)));
                 };
-                runtime.on_current_instant(Box::new(c));
+                runtime.spawn(Box::new(c));
             };
         }
     }
@@ -52,35 +52,38 @@ impl<P> ProcessMutPl for JoinAll<P> where P: ProcessMutPl {
                     p.call_mut(r, move |r: &mut ParallelRuntime, p_v|
                         joint_point.lock().unwrap().call_ref(r, (p_v, p_id)));
                 };
-                runtime.on_current_instant(Box::new(c));
+                runtime.spawn(Box::new(c));
             };
         }
     }
 }
 
-/// Used by `JoinAll` as a barrier for multiple processes.
-struct JoinPoint<V, C> {
+/// Used by `JoinAll` and `JoinAllEach` as a barrier for multiple processes.
+pub(crate) struct JoinPoint<V, C> {
     counter: usize,
     values: Vec<Option<V>>,
     continuation: Option<C>,
+    arrivals: Arrivals,
 }
 
-impl<V, C> JoinPoint<V, C> where C: ContinuationPl<Vec<V>> {
-    fn new(num_procs: usize, continuation: C) -> Self {
+impl<V, C> JoinPoint<V, C> where C: ContinuationPl<Vec<V>>, V: Send + Sync + 'static {
+    pub(crate) fn new(num_procs: usize, continuation: C) -> Self {
         JoinPoint {
             counter: 0,
             values: (0..num_procs).map(|_| None).collect(),
             continuation: Some(continuation),
+            arrivals: Arrivals::default(),
         }
     }
     
-    fn call_ref(&mut self, runtime: &mut ParallelRuntime, (value, p_id): (V, usize)) {
+    pub(crate) fn call_ref(&mut self, runtime: &mut ParallelRuntime, (value, p_id): (V, usize)) {
         assert!(self.values[p_id].is_none());
+        self.arrivals.arrive(runtime);
         self.values[p_id] = Some(value);
         self.counter += 1;
         if self.counter == self.values.len() {
             let values = self.values.iter_mut().map(|v| v.take().unwrap()).collect();
-            self.continuation.take().unwrap().call(runtime, values);
+            self.arrivals.leave(runtime, self.continuation.take().unwrap(), values);
         }
     }
 }
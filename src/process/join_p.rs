@@ -1,7 +1,7 @@
 use std::sync::{Arc, Mutex};
 use either::{Either, Left, Right};
 
-use runtime::ParallelRuntime;
+use runtime::{ParallelRuntime, Arrivals};
 use continuation::ContinuationPl;
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
@@ -32,8 +32,8 @@ impl<P1, P2> ProcessPl for Join<P1, P2> where P1: ProcessPl, P2: ProcessPl {
                 move |r: &mut ParallelRuntime, v|
                     joint_point2.lock().unwrap().call_ref(r, Right(v)));
         };
-        runtime.on_current_instant(Box::new(c1));
-        runtime.on_current_instant(Box::new(c2));
+        runtime.spawn(Box::new(c1));
+        runtime.spawn(Box::new(c2));
     }
 }
 
@@ -60,8 +60,8 @@ impl<P1, P2> ProcessMutPl for Join<P1, P2> where P1: ProcessMutPl, P2: ProcessMu
                 move |r: &mut ParallelRuntime, p_v|
                     joint_point2.lock().unwrap().call_ref(r, Right(p_v)));
         };
-        runtime.on_current_instant(Box::new(c1));
-        runtime.on_current_instant(Box::new(c2));
+        runtime.spawn(Box::new(c1));
+        runtime.spawn(Box::new(c2));
     }
 }
 
@@ -70,18 +70,23 @@ struct JoinPoint<V1, V2, C> {
     counter: i32,
     values: (Option<V1>, Option<V2>),
     continuation: Option<C>,
+    arrivals: Arrivals,
 }
 
-impl<V1, V2, C> JoinPoint<V1, V2, C> where C: ContinuationPl<(V1, V2)> {
+impl<V1, V2, C> JoinPoint<V1, V2, C>
+    where C: ContinuationPl<(V1, V2)>, V1: Send + Sync + 'static, V2: Send + Sync + 'static
+{
     fn new(continuation: C) -> Self where {
         JoinPoint {
             counter: 0,
             values: (None, None),
             continuation: Some(continuation),
+            arrivals: Arrivals::default(),
         }
     }
 
     fn call_ref(&mut self, runtime: &mut ParallelRuntime, value: Either<V1, V2>) {
+        self.arrivals.arrive(runtime);
         match value {
             Left(value1) => {
                 assert!(self.values.0.is_none());
@@ -96,7 +101,7 @@ impl<V1, V2, C> JoinPoint<V1, V2, C> where C: ContinuationPl<(V1, V2)> {
         }
         if self.counter == 2 {
             let values = (self.values.0.take().unwrap(), self.values.1.take().unwrap());
-            self.continuation.take().unwrap().call(runtime, values);
+            self.arrivals.leave(runtime, self.continuation.take().unwrap(), values);
         }
     }
 }
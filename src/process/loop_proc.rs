@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{Continuation, ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 /// Repeats a process forever.
@@ -8,6 +8,10 @@ pub struct Loop<P>(pub(crate) P);
 
 impl<P> Process for Loop<P> where P: ProcessMut {
     type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::node("loop", vec![self.0.describe()])
+    }
 }
 
 // TODO: This is useless without other control structures.
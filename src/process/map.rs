@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 /// A process that applies a function to the returned value of another process.
@@ -10,6 +10,10 @@ impl<P, F, V> Process for Map<P, F>
     where P: Process, F: FnOnce(P::Value) -> V + 'static
 {
     type Value = V;
+
+    fn describe(&self) -> Description {
+        Description::node("map", vec![self.process.describe()])
+    }
 }
 
 impl<P, F, V> ProcessMut for Map<P, F>
@@ -6,6 +6,8 @@
 //! There is no need to manipulate directly the runtime engines because we only
 //! need to call `execute_process`, `execute_process_parallel`, or
 //! `execute_process_parallel_with_main` at the end to execute the process.
+//! The structure of a process can be inspected with `describe` and rendered as
+//! a Graphviz graph.
 //!
 //! If a process is only defined with things found in this module (in other words,
 //! no signal is used), we can execute it in the two kinds of runtime as long as
@@ -28,10 +30,15 @@ mod execute_process;
 mod process_mut;
 pub use self::execute_process::{execute_process, execute_process_parallel};
 pub use self::execute_process::execute_process_parallel_with_main;
+pub use self::execute_process::execute_process_shuffled;
+pub use self::execute_process::{execute_process_traced, execute_process_parallel_traced};
+pub use self::execute_process::{execute_process_with_stats, execute_process_parallel_with_stats};
+pub use self::execute_process::{execute_process_parallel_seeded, execute_process_parallel_deterministic};
 pub use self::process_mut::{ProcessMut, ProcessMutSt, ProcessMutPl};
 
 mod value;
 mod pause;
+mod pause_n;
 mod map;
 mod flatten;
 mod and_then;
@@ -41,11 +48,16 @@ mod join;
 mod join_p;
 mod join_all;
 mod join_all_p;
+mod join_all_each;
+mod join_all_each_p;
 mod loop_proc;
 mod repeat;
 mod while_proc;
+mod every;
+mod describe;
 pub use self::value::{value_proc, Value};
 pub use self::pause::Pause;
+pub use self::pause_n::{PauseN, PauseUntil};
 pub use self::map::Map;
 pub use self::flatten::Flatten;
 pub use self::and_then::AndThen;
@@ -53,9 +65,12 @@ pub use self::then::Then;
 pub use self::if_else::IfElse;
 pub use self::join::Join;
 pub use self::join_all::{join_all, JoinAll};
+pub use self::join_all_each::{join_all_each, join_all_emit, JoinAllEach};
 pub use self::loop_proc::Loop;
 pub use self::repeat::Repeat;
 pub use self::while_proc::{While, LoopStatus};
+pub use self::every::Every;
+pub use self::describe::Description;
 
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
@@ -66,10 +81,30 @@ pub trait Process: 'static {
     /// The value created by the process.
     type Value;  
 
+    /// Describes the structure of the process as a tree of combinators. The
+    /// processes that are only known during the execution, like the ones created
+    /// by `and_then` or `flatten`, can't be shown.
+    fn describe(&self) -> Description {
+        Description::leaf("process")
+    }
+
     /// Suspends the execution of a process until next instant.
     fn pause(self) -> Pause<Self> where Self: Sized {
         Pause(self)
     }
+
+    /// Suspends the execution of a process for `n` instants. When `n` is zero
+    /// the process is not suspended at all.
+    fn pause_n(self, n: usize) -> PauseN<Self> where Self: Sized {
+        PauseN { process: self, n }
+    }
+
+    /// Suspends the execution of a process until the given instant, the first
+    /// instant of the execution being 0. Long waits are handled by the timers of the
+    /// runtime and cost nothing at the instants in between.
+    fn pause_until(self, instant: usize) -> PauseUntil<Self> where Self: Sized {
+        PauseUntil { process: self, instant }
+    }
     
     /// Applies a function to the value returned by the process before passing it to
     /// its continuation.
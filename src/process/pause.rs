@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{Continuation, ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 /// The process is suspended until next instant.
@@ -8,6 +8,10 @@ pub struct Pause<P>(pub(crate) P);
 
 impl<P> Process for Pause<P> where P: Process {
     type Value = P::Value;
+
+    fn describe(&self) -> Description {
+        Description::node("pause", vec![self.0.describe()])
+    }
 }
 
 impl<P> ProcessMut for Pause<P> where P: ProcessMut {}
@@ -0,0 +1,125 @@
+use runtime::{SingleThreadRuntime, ParallelRuntime};
+use continuation::{Continuation, ContinuationSt, ContinuationPl};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
+use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
+
+/// The process is suspended for a given number of instants.
+pub struct PauseN<P> { pub(crate) process: P, pub(crate) n: usize }
+
+impl<P> Process for PauseN<P> where P: Process {
+    type Value = P::Value;
+
+    fn describe(&self) -> Description {
+        Description::node(format!("pause_n({})", self.n), vec![self.process.describe()])
+    }
+}
+
+impl<P> ProcessMut for PauseN<P> where P: ProcessMut {}
+
+// Implements the traits for the single thread version of the library.
+
+impl<P> ProcessSt for PauseN<P> where P: ProcessSt {
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        self.process.call(runtime, next.pause_n(self.n));
+    }
+}
+
+impl<P> ProcessMutSt for PauseN<P> where P: ProcessMutSt {
+    fn call_mut<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        let n = self.n;
+        self.process.call_mut(
+            runtime,
+            next.pause_n(n).map(move |(process, v): (P, P::Value)| (process.pause_n(n), v)));
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<P> ConstraintOnValue for PauseN<P> where P: ProcessPl {
+    type T = P::Value;
+}
+
+impl<P> ProcessPl for PauseN<P> where P: ProcessPl {
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        self.process.call(runtime, next.pause_n(self.n));
+    }
+}
+
+impl<P> ProcessMutPl for PauseN<P> where P: ProcessMutPl {
+    fn call_mut<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        let n = self.n;
+        self.process.call_mut(
+            runtime,
+            next.pause_n(n).map(move |(process, v): (P, P::Value)| (process.pause_n(n), v)));
+    }
+}
+
+/// The process is suspended until a given instant, the first instant of the
+/// execution being 0. It is not suspended if this instant is already reached.
+pub struct PauseUntil<P> { pub(crate) process: P, pub(crate) instant: usize }
+
+impl<P> Process for PauseUntil<P> where P: Process {
+    type Value = P::Value;
+
+    fn describe(&self) -> Description {
+        Description::node(format!("pause_until({})", self.instant), vec![self.process.describe()])
+    }
+}
+
+impl<P> ProcessMut for PauseUntil<P> where P: ProcessMut {}
+
+// Implements the traits for the single thread version of the library.
+
+impl<P> ProcessSt for PauseUntil<P> where P: ProcessSt {
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        self.process.call(runtime, next.pause_until(self.instant));
+    }
+}
+
+impl<P> ProcessMutSt for PauseUntil<P> where P: ProcessMutSt {
+    fn call_mut<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        let instant = self.instant;
+        self.process.call_mut(
+            runtime,
+            next.pause_until(instant)
+                .map(move |(process, v): (P, P::Value)| (process.pause_until(instant), v)));
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<P> ConstraintOnValue for PauseUntil<P> where P: ProcessPl {
+    type T = P::Value;
+}
+
+impl<P> ProcessPl for PauseUntil<P> where P: ProcessPl {
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        self.process.call(runtime, next.pause_until(self.instant));
+    }
+}
+
+impl<P> ProcessMutPl for PauseUntil<P> where P: ProcessMutPl {
+    fn call_mut<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        let instant = self.instant;
+        self.process.call_mut(
+            runtime,
+            next.pause_until(instant)
+                .map(move |(process, v): (P, P::Value)| (process.pause_until(instant), v)));
+    }
+}
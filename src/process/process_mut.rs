@@ -1,10 +1,11 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessSt, ProcessPl};
+use process::{Process, ProcessSt, ProcessPl, value_proc};
 
 use process::loop_proc::Loop;
 use process::repeat::Repeat;
 use process::while_proc::{While, LoopStatus};
+use process::every::Every;
 
 /// A process that can be executed multiple times, modifying its environement each time.
 pub trait ProcessMut: Process {
@@ -28,6 +29,15 @@ pub trait ProcessMut: Process {
     {
         While(self)
     }
+
+    /// Executes the process every `n` instants forever. If one execution lasts `n`
+    /// instants or more, the next one starts as soon as it terminates.
+    fn every(self, n: usize) -> Every<Self> where Self: Sized {
+        if n == 0 {
+            panic!("The process cannot be executed every 0 instant.");
+        }
+        Every(self.join(value_proc(()).pause_n(n)).loop_proc())
+    }
 }
 
 /// A repeatable reactive process to be executed in a single thread.
@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{Continuation, ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 /// Repeats a process several times and collects all the produced values
@@ -9,6 +9,10 @@ pub struct Repeat<P> { pub(crate) process: P, pub(crate) times: usize }
 
 impl<P> Process for Repeat<P> where P: ProcessMut {
     type Value = Vec<P::Value>;
+
+    fn describe(&self) -> Description {
+        Description::node(format!("repeat({})", self.times), vec![self.process.describe()])
+    }
 }
 
 impl<P> ProcessMut for Repeat<P> where P: ProcessMut {}
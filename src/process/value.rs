@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 /// Create a new process that returns the value `v` immediately.
@@ -13,6 +13,10 @@ pub struct Value<V>(V);
 
 impl<V> Process for Value<V> where V: 'static {
     type Value = V;
+
+    fn describe(&self) -> Description {
+        Description::leaf("value")
+    }
 }
 
 impl<V> ProcessMut for Value<V> where V: Copy + 'static {}
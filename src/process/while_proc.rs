@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{Continuation, ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 /// Indicates if a loop is terminated or not.
@@ -13,6 +13,10 @@ pub struct While<P>(pub(crate) P);
 
 impl<P, V> Process for While<P> where P: ProcessMut<Value=LoopStatus<V>> {
     type Value = V;
+
+    fn describe(&self) -> Description {
+        Description::node("while", vec![self.0.describe()])
+    }
 }
 
 impl<P, V> ProcessMut for While<P> where P: ProcessMut<Value=LoopStatus<V>> {}
@@ -0,0 +1,154 @@
+use std::io::{self, Write};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use std::collections::HashMap;
+
+use runtime::{Subscriber, TraceEvent, BarrierKind};
+
+/// A subscriber recording a timeline of the execution, which can be written in
+/// the trace-event JSON format of Chrome (`about:tracing`) and Perfetto.
+///
+/// Each runtime is shown as a thread with spans for the continuations it
+/// executes, its steals and its waits at the barriers. The beginning of each
+/// instant is shown as a global marker. The runtimes record their events apart,
+/// and the events are only merged by `write_json`.
+pub struct ChromeTracer {
+    start: Instant,
+    threads: RwLock<Vec<Mutex<ThreadTrace>>>,
+}
+
+/// The events of a single runtime.
+#[derive(Default)]
+struct ThreadTrace {
+    events: Vec<ChromeEvent>,
+    /// The start of the spans not closed yet. Continuations can be nested in the
+    /// deterministic mode, where the branches of the joins are executed at once.
+    open_spans: HashMap<Span, Vec<Duration>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Span {
+    Continuation,
+    Steal,
+    Barrier,
+}
+
+struct ChromeEvent {
+    name: &'static str,
+    thread: usize,
+    ts: Duration,
+    /// `None` for the instant markers.
+    dur: Option<Duration>,
+    arg: Option<(&'static str, usize)>,
+}
+
+impl ChromeTracer {
+    /// Creates a tracer whose timeline starts now.
+    pub fn new() -> Self {
+        ChromeTracer {
+            start: Instant::now(),
+            threads: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Calls `f` on the events of the runtime `thread`.
+    fn with_thread<F>(&self, thread: usize, f: F) where F: FnOnce(&mut ThreadTrace) {
+        if self.threads.read().unwrap().len() <= thread {
+            let mut threads = self.threads.write().unwrap();
+            while threads.len() <= thread {
+                threads.push(Mutex::new(ThreadTrace::default()));
+            }
+        }
+        let threads = self.threads.read().unwrap();
+        let mut trace = threads[thread].lock().unwrap();
+        f(&mut trace);
+    }
+
+    /// Writes the recorded timeline as a JSON trace-event file.
+    pub fn write_json<W>(&self, mut out: W) -> io::Result<()> where W: Write {
+        let threads = self.threads.read().unwrap();
+        let traces: Vec<_> = threads.iter().map(|t| t.lock().unwrap()).collect();
+        let mut events: Vec<_> = traces.iter().flat_map(|t| t.events.iter()).collect();
+        events.sort_by_key(|e| e.ts);
+        let micros = |d: Duration| d.as_secs() as f64 * 1e6 + d.subsec_nanos() as f64 / 1e3;
+        writeln!(out, "{{\"traceEvents\":[")?;
+        for (i, e) in events.iter().enumerate() {
+            write!(out, "{{\"name\":\"{}\",\"pid\":0,\"tid\":{},\"ts\":{:.3}",
+                   e.name, e.thread, micros(e.ts))?;
+            match e.dur {
+                Some(dur) => write!(out, ",\"ph\":\"X\",\"dur\":{:.3}", micros(dur))?,
+                None => write!(out, ",\"ph\":\"i\",\"s\":\"g\"")?,
+            }
+            if let Some((key, value)) = e.arg {
+                write!(out, ",\"args\":{{\"{}\":{}}}", key, value)?;
+            }
+            let sep = if i + 1 < events.len() { "," } else { "" };
+            writeln!(out, "}}{}", sep)?;
+        }
+        writeln!(out, "]}}")
+    }
+}
+
+impl Default for ChromeTracer {
+    fn default() -> Self {
+        ChromeTracer::new()
+    }
+}
+
+impl ThreadTrace {
+    fn open(&mut self, span: Span, now: Duration) {
+        self.open_spans.entry(span).or_default().push(now);
+    }
+
+    /// Closes the last span of this kind if there is one open, recording it with the
+    /// given name.
+    fn close(&mut self, thread: usize, span: Span, now: Duration,
+             name: &'static str, arg: Option<(&'static str, usize)>)
+    {
+        if let Some(ts) = self.open_spans.get_mut(&span).and_then(|starts| starts.pop()) {
+            self.events.push(ChromeEvent { name, thread, ts, dur: Some(now - ts), arg });
+        }
+    }
+}
+
+impl Subscriber for ChromeTracer {
+    fn event(&self, thread: usize, instant: usize, event: TraceEvent) {
+        let now = self.start.elapsed();
+        self.with_thread(thread, |trace| match event {
+            // All the runtimes start the instant, one marker is enough.
+            TraceEvent::InstantStart if thread == 0 => {
+                trace.events.push(ChromeEvent {
+                    name: "instant",
+                    thread,
+                    ts: now,
+                    dur: None,
+                    arg: Some(("instant", instant)),
+                });
+            },
+            TraceEvent::ContinuationStart =>
+                trace.open(Span::Continuation, now),
+            TraceEvent::ContinuationExecuted =>
+                trace.close(thread, Span::Continuation, now, "continuation", None),
+            TraceEvent::StealAttempt =>
+                trace.open(Span::Steal, now),
+            TraceEvent::StealSuccess { from } =>
+                trace.close(thread, Span::Steal, now, "steal", Some(("from", from))),
+            TraceEvent::BarrierWait(_) => {
+                // A steal attempt that doesn't succeed ends with the barrier.
+                trace.close(thread, Span::Steal, now, "failed steal", None);
+                trace.open(Span::Barrier, now);
+            },
+            TraceEvent::BarrierPassed(kind) => {
+                let name = match kind {
+                    BarrierKind::Works => "works barrier",
+                    BarrierKind::EndOfInstantWorks => "end of instant barrier",
+                    BarrierKind::PresenceTests => "presence tests barrier",
+                    BarrierKind::SignalsReset => "reset barrier",
+                    BarrierKind::WorkSplit => "work split barrier",
+                };
+                trace.close(thread, Span::Barrier, now, name, Some(("instant", instant)));
+            },
+            _ => (),
+        });
+    }
+}
@@ -7,17 +7,51 @@
 //! no more continuation is left in the runtime(s) and no signal is awaiting for
 //! emisssion.
 //!
+//! Continuations that must only be executed in some distant instant are kept
+//! as timers, and when nothing but timers is pending the runtimes directly jump
+//! to the instant of the earliest timer instead of executing empty instants.
+//! This is only done by `Runtime::execute`: a runtime driven by `Runtime::instant`
+//! advances by exactly one instant at each call.
+//! The runtimes also keep some functions to be executed at the beginning of each
+//! instant, which are used to sustain the emission of signals.
+//!
 //! A `SingleThreadRuntime` is itself the whole execution engine and is runned on
 //! the main thread. In contrast, a `ParallelRuntime` is spawned on a new thread
 //! and is only one part of the whole parallel engine which is given by the struct
 //! `ParallelRuntimeCollection`.
+//!
+//! A `Subscriber` can be installed on the runtimes to be notified of what they
+//! are doing (instants, continuations, steals, barriers, signals). When none is
+//! installed the runtimes just skip the notifications. The `StatsCollector` is
+//! such a subscriber, gathering some numbers about each instant that can then be
+//! exported as CSV, and the `ChromeTracer` records a timeline of the execution
+//! to be viewed in Chrome or Perfetto.
+
+use rand::{SeedableRng, XorShiftRng};
 
 mod single_thread_runtime;
 pub use self::single_thread_runtime::SingleThreadRuntime;
 mod parallel_runtime;
 pub use self::parallel_runtime::ParallelRuntime;
+pub(crate) use self::parallel_runtime::Arrivals;
 mod parallel_runtime_collection;
 pub use self::parallel_runtime_collection::ParallelRuntimeCollection;
+mod trace;
+pub use self::trace::{TraceEvent, BarrierKind, Subscriber, PrintSubscriber};
+pub(crate) use self::trace::default_subscriber;
+mod stats;
+pub use self::stats::{InstantStats, StatsCollector, write_csv};
+mod chrome_trace;
+pub use self::chrome_trace::ChromeTracer;
+
+/// Creates the random number generator number `stream` of the runtimes seeded with
+/// `seed`: the runtime `i` of a collection uses the stream `i`, the collection
+/// itself the stream following the last runtime, and a `SingleThreadRuntime` the
+/// stream 0.
+pub(crate) fn seeded_rng(seed: u64, stream: usize) -> XorShiftRng {
+    // The seed of a `XorShiftRng` must not be all zeros.
+    XorShiftRng::from_seed([seed as u32, (seed >> 32) as u32, stream as u32, 0x9e37_79b9])
+}
 
 /// Must be implemented by all concrete runtime types.
 pub trait Runtime {
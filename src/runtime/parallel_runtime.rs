@@ -1,11 +1,13 @@
 use std::sync::{Arc, Mutex, Barrier, Condvar};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::thread;
+use std::mem;
+use std::collections::BTreeMap;
 use crossbeam::sync::chase_lev;
 use rand::{Rng, XorShiftRng};
 use ordermap::OrderSet;
 
-use runtime::Runtime;
+use runtime::{Runtime, Subscriber, TraceEvent, BarrierKind};
 use continuation::ContinuationPl;
 use signal::signal_runtime::SignalRuntimeRefBasePl;
 
@@ -20,6 +22,16 @@ use signal::signal_runtime::SignalRuntimeRefBasePl;
 /// only at the end of instant (there are notably the `await` constructions
 /// of multi-producer signals). A conditional variable is then used to know
 /// if the whole engine should terminate or not.
+///
+/// Continuations that must be executed in a more distant future are kept by
+/// each runtime as timers. When nothing but timers is pending in the whole
+/// engine, the runtimes agree on the earliest timer and skip the empty instants.
+///
+/// In the deterministic mode nothing is stolen. The works of the next instant
+/// and the timers are rather given to a `WorkSplit` at the end of each instant,
+/// and split again between the runtimes. The continuations woken up by another
+/// runtime are deferred to rounds, where each runtime executes the ones it gets
+/// in a fixed order.
 pub struct ParallelRuntime {
     pub(crate) id: usize,
     pub(crate) num_threads_total: usize,
@@ -27,6 +39,8 @@ pub struct ParallelRuntime {
     pub(crate) stealers: Vec<chase_lev::Stealer<Box<ContinuationPl<()>>>>,
     pub(crate) barrier: Arc<Barrier>,
     pub(crate) rng: XorShiftRng,
+    /// Only in the deterministic mode of `ParallelRuntimeCollection`.
+    pub(crate) split: Option<Arc<WorkSplit>>,
     pub(crate) working_pool: Arc<Mutex<OrderSet<usize>>>,
     pub(crate) whether_to_continue: Arc<(Mutex<RuntimeStatus>, Condvar)>,
     pub(crate) next_instant_works: Vec<Box<ContinuationPl<()>>>,
@@ -34,9 +48,162 @@ pub struct ParallelRuntime {
     pub(crate) eoi_working_pool: Arc<Mutex<OrderSet<usize>>>,
     pub(crate) emitted_signals: Vec<Box<SignalRuntimeRefBasePl>>,
     pub(crate) await_counter: Arc<AtomicUsize>,
+    pub(crate) cancelled_awaits: Arc<AtomicUsize>,
     pub(crate) test_presence_signals: Vec<Box<SignalRuntimeRefBasePl>>,
-    #[cfg(feature = "debug")]
+    pub(crate) timers: Timers,
+    pub(crate) earliest_timer: Arc<AtomicUsize>,
+    pub(crate) sustained: Vec<Sustained>,
+    pub(crate) watchers: Vec<Box<FnMut(usize) + Send>>,
     pub(crate) instant: usize,
+    /// The number of rounds of deferred continuations executed by the engine.
+    pub(crate) round: usize,
+    /// The number of events which got a key on this runtime.
+    pub(crate) seq: usize,
+    /// Whether the empty instants can be skipped, which is only the case in `execute`.
+    pub(crate) fast_forward: bool,
+    pub(crate) subscriber: Option<Arc<Subscriber>>,
+}
+
+/// The place of an event in a deterministic engine: the round during which it
+/// happened, the runtime and the place in the works of this runtime. The deferred
+/// continuations are executed in the order of their keys.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub(crate) struct EventKey {
+    round: usize,
+    runtime: usize,
+    seq: usize,
+}
+
+/// The continuations to execute at later instants, by instant.
+type Timers = BTreeMap<usize, Vec<Box<ContinuationPl<()>>>>;
+
+/// A function executed at the beginning of each instant as long as it returns `true`.
+type Sustained = Box<FnMut(&mut ParallelRuntime) -> bool + Send>;
+
+/// A continuation deferred to the next round.
+type Deferred = (EventKey, Box<ContinuationPl<()>>);
+
+/// Records where the branches of a join arrive, so that in the deterministic mode
+/// the join is left at a place which doesn't depend on the scheduling.
+#[derive(Default)]
+pub(crate) struct Arrivals {
+    last: Option<EventKey>,
+    several_runtimes: bool,
+}
+
+impl Arrivals {
+    /// Records the arrival of a branch on `runtime`.
+    pub(crate) fn arrive(&mut self, runtime: &mut ParallelRuntime) {
+        if runtime.defers_wakeups() {
+            let key = runtime.event_key();
+            if let Some(last) = self.last {
+                self.several_runtimes |= last.runtime != key.runtime;
+            }
+            self.last = self.last.max(Some(key));
+        }
+    }
+
+    /// Calls `c` once all the branches arrived. It is called at once if they all
+    /// arrived on the same runtime, otherwise it is deferred to the runtime of the
+    /// last arrival in the order of the keys.
+    pub(crate) fn leave<C, V>(&self, runtime: &mut ParallelRuntime, c: C, value: V)
+        where C: ContinuationPl<V>, V: Send + Sync + 'static
+    {
+        match self.last {
+            Some(key) if self.several_runtimes =>
+                runtime.defer(key, Box::new(move |r: &mut ParallelRuntime, ()| c.call(r, value))),
+            _ => c.call(runtime, value),
+        }
+    }
+}
+
+/// The works given by a runtime at the end of an instant in the deterministic mode.
+#[derive(Default)]
+struct Deposit {
+    /// The works registered for the next instant during the instant.
+    works: Vec<Box<ContinuationPl<()>>>,
+    /// The works registered for the next instant by the end of instant works and
+    /// the presence tests.
+    late_works: Vec<Box<ContinuationPl<()>>>,
+    /// The timers registered during the instant.
+    timers: Timers,
+}
+
+/// Splits the works of each instant between the runtimes of a deterministic engine,
+/// so that the part of each runtime doesn't depend on the scheduling of the threads.
+///
+/// The works are put in the order in which the single thread runtime would execute
+/// them if it had executed the parts of the previous instant runtime after runtime,
+/// and this order is cut into contiguous parts, the first runtime taking the works
+/// executed first. The timers are kept here until they are due, and the continuations
+/// deferred during a round are collected here too.
+pub(crate) struct WorkSplit {
+    deposits: Vec<Mutex<Deposit>>,
+    parts: Vec<Mutex<Vec<Box<ContinuationPl<()>>>>>,
+    timers: Mutex<Timers>,
+    deferred: Mutex<Vec<Deferred>>,
+    any_deferred: AtomicBool,
+}
+
+impl WorkSplit {
+    pub(crate) fn new(num_runtimes: usize) -> Self {
+        WorkSplit {
+            deposits: (0..num_runtimes).map(|_| Mutex::new(Deposit::default())).collect(),
+            parts: (0..num_runtimes).map(|_| Mutex::new(Vec::new())).collect(),
+            timers: Mutex::new(BTreeMap::new()),
+            deferred: Mutex::new(Vec::new()),
+            any_deferred: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the instant of the earliest timer kept here.
+    fn earliest_timer(&self) -> Option<usize> {
+        self.timers.lock().unwrap().keys().next().cloned()
+    }
+
+    /// Gives the continuations deferred during a round to the runtimes of their
+    /// keys, the smallest key being executed first.
+    fn split_deferred(&self) {
+        let mut deferred = mem::take(&mut *self.deferred.lock().unwrap());
+        self.any_deferred.store(!deferred.is_empty(), Ordering::SeqCst);
+        deferred.sort_by_key(|&(key, _)| key);
+        for (key, c) in deferred.into_iter().rev() {
+            self.parts[key.runtime].lock().unwrap().push(c);
+        }
+    }
+
+    /// Splits the works deposited by all the runtimes and the timers due at `instant`.
+    fn split(&self, instant: usize) {
+        let mut deposits: Vec<_> = self.deposits.iter()
+            .map(|d| mem::take(&mut *d.lock().unwrap()))
+            .collect();
+        // The single thread runtime executes the end of instant works in the
+        // reverse order of their registration, so the last runtime comes first.
+        let mut works = Vec::new();
+        for deposit in deposits.iter_mut() {
+            works.append(&mut deposit.works);
+        }
+        for deposit in deposits.iter_mut().rev() {
+            works.append(&mut deposit.late_works);
+        }
+        let mut timers = self.timers.lock().unwrap();
+        for deposit in deposits {
+            for (at, mut timer_works) in deposit.timers {
+                timers.entry(at).or_default().append(&mut timer_works);
+            }
+        }
+        let later_timers = timers.split_off(&(instant + 1));
+        for (_, mut timer_works) in mem::replace(&mut *timers, later_timers) {
+            works.append(&mut timer_works);
+        }
+        // `works` is used as a stack, the last work being executed first.
+        let num_runtimes = self.parts.len();
+        let (size, remainder) = (works.len() / num_runtimes, works.len() % num_runtimes);
+        for (i, part) in self.parts.iter().enumerate() {
+            let len = works.len() - size - if i < remainder { 1 } else { 0 };
+            *part.lock().unwrap() = works.split_off(len);
+        }
+    }
 }
 
 /// Used at the end of each instant to determine if there is still work to
@@ -48,28 +215,144 @@ pub(crate) enum RuntimeStatus {
 }
 
 impl Runtime for ParallelRuntime {
+    /// Executes instants until all work is completed. The instants where nothing
+    /// but timers is pending in the whole engine are skipped.
+    fn execute(&mut self) {
+        self.fast_forward = true;
+        while self.instant() {};
+        self.fast_forward = false;
+    }
+
     /// Executes a single instant to completion. Indicates if more work remains to be done.
     fn instant(&mut self) -> bool {
-        #[cfg(feature = "debug")] {
-            println!("Thread {}: instant {}.", self.id, self.instant);
-            self.instant += 1;
-        }
+        self.trace(TraceEvent::InstantStart);
+        self.execute_sustained();
         self.consume_current_works(false);
         self.end_of_instant()
     }
 }
 
 impl ParallelRuntime {
+    /// Notifies the subscriber, if there is one.
+    fn trace(&self, event: TraceEvent) {
+        if let Some(ref subscriber) = self.subscriber {
+            subscriber.event(self.id, self.instant, event);
+        }
+    }
+
+    /// Executes a continuation, telling the subscriber.
+    fn execute_work(&mut self, work: Box<ContinuationPl<()>>) {
+        self.trace(TraceEvent::ContinuationStart);
+        work.call_box(self, ());
+        self.trace(TraceEvent::ContinuationExecuted);
+    }
+
+    /// Waits for the other runtimes at the barrier, telling the subscriber.
+    fn wait_barrier(&self, kind: BarrierKind) {
+        self.trace(TraceEvent::BarrierWait(kind));
+        self.barrier.wait();
+        self.trace(TraceEvent::BarrierPassed(kind));
+    }
+
     /// Registers a continuation to execute on the current instant.
     pub(crate) fn on_current_instant(&mut self, c: Box<ContinuationPl<()>>) {
         self.worker.push(c);
     }
 
+    /// Executes a continuation on the current instant, where it could be stolen by
+    /// another runtime. In the deterministic mode the continuation is rather executed
+    /// at once, as the single thread runtime does for the branches of the joins.
+    pub(crate) fn spawn(&mut self, c: Box<ContinuationPl<()>>) {
+        if self.split.is_some() {
+            self.execute_work(c);
+        } else {
+            self.on_current_instant(c);
+        }
+    }
+
+    /// Whether the runtime belongs to a deterministic engine.
+    pub(crate) fn is_deterministic(&self) -> bool {
+        self.split.is_some()
+    }
+
+    /// Whether the processes woken up by a signal are deferred to the next round,
+    /// which is the case in the deterministic mode with several runtimes.
+    pub(crate) fn defers_wakeups(&self) -> bool {
+        self.is_deterministic() && self.num_threads_total > 1
+    }
+
+    /// Returns the key of the current event in the deterministic mode.
+    fn event_key(&mut self) -> EventKey {
+        self.seq += 1;
+        EventKey { round: self.round, runtime: self.id, seq: self.seq }
+    }
+
+    /// Defers a continuation to the next round, where it is executed by the runtime
+    /// of `key`. Only in the deterministic mode.
+    pub(crate) fn defer(&mut self, key: EventKey, c: Box<ContinuationPl<()>>) {
+        let split = self.split.as_ref().expect("Only a deterministic runtime defers continuations.");
+        split.deferred.lock().unwrap().push((key, c));
+    }
+
+    /// Prepares a continuation to register on a signal. In the deterministic mode
+    /// the continuation is deferred to the runtime registering it when it is woken
+    /// up, since whether the signal is emitted before or after the registration
+    /// depends on the scheduling.
+    pub(crate) fn on_wakeup<C>(&mut self, c: C) -> Box<ContinuationPl<()>>
+        where C: ContinuationPl<()>
+    {
+        if self.defers_wakeups() {
+            let key = self.event_key();
+            Box::new(move |r: &mut ParallelRuntime, ()| r.defer(key, Box::new(c)))
+        } else {
+            Box::new(c)
+        }
+    }
+
     /// Registers a continuation to execute at the next instant.
     pub(crate) fn on_next_instant(&mut self, c: Box<ContinuationPl<()>>) {
         self.next_instant_works.push(c);
     }
     
+    /// Registers a continuation to execute at a given instant. The continuation
+    /// is executed at the current instant if this instant is already reached.
+    pub(crate) fn on_instant(&mut self, instant: usize, c: Box<ContinuationPl<()>>) {
+        if instant <= self.instant {
+            self.on_current_instant(c);
+        } else if instant == self.instant + 1 {
+            self.on_next_instant(c);
+        } else {
+            self.timers.entry(instant).or_default().push(c);
+        }
+    }
+
+    /// Returns the number of the current instant, the first instant being 0.
+    pub(crate) fn current_instant(&self) -> usize {
+        self.instant
+    }
+
+    /// Registers a function to execute at the beginning of each instant, as long
+    /// as it returns `true`.
+    pub(crate) fn sustain(&mut self, f: Sustained) {
+        self.sustained.push(f);
+    }
+
+    /// Executes the sustained functions and drops the ones that are over.
+    fn execute_sustained(&mut self) {
+        for mut f in mem::take(&mut self.sustained) {
+            if f(self) {
+                self.sustained.push(f);
+            }
+        }
+    }
+
+    /// Registers a function called at the end of each instant, once the signals
+    /// of all the runtimes are reset, with the number of the instant. Unlike
+    /// sustained functions, watchers don't keep the runtime alive.
+    pub(crate) fn watch(&mut self, f: Box<FnMut(usize) + Send>) {
+        self.watchers.push(f);
+    }
+
     /// Registers a continuation to execute at the end of the instant. Runtime calls for `c`
     /// behave as if they where executed during the next instant.
     pub(crate) fn on_end_of_instant(&mut self, c: Box<ContinuationPl<()>>) {
@@ -79,17 +362,31 @@ impl ParallelRuntime {
     /// Increases the await counter by 1 when some process awaits a signal.
     pub(crate) fn incr_await_counter(&mut self) {
         self.await_counter.fetch_add(1, Ordering::SeqCst);
+        self.trace(TraceEvent::AwaitRegistered);
     }
 
     /// Decrease the await counter by 1 when some signal is emitted and
     /// a corresponding process is thus executed.
     pub(crate) fn decr_await_counter(&mut self) {
         self.await_counter.fetch_sub(1, Ordering::SeqCst);
+        self.trace(TraceEvent::AwaitWoken);
+    }
+
+    /// Tells that a process doesn't wait anymore for a signal it awaits, although its
+    /// continuation stays registered and is still counted by the await counter.
+    pub(crate) fn cancel_await(&mut self) {
+        self.cancelled_awaits.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Called by the continuation of a cancelled await when the signal is emitted.
+    pub(crate) fn cancelled_await_woken(&mut self) {
+        self.cancelled_awaits.fetch_sub(1, Ordering::SeqCst);
     }
 
     /// Registers a emitted signal for the current instant.
     pub(crate) fn emit_signal(&mut self, s: Box<SignalRuntimeRefBasePl>) {
         self.emitted_signals.push(s);
+        self.trace(TraceEvent::SignalEmitted);
     }
     
     /// Registers a signal for which we need to test its presence on the current instant.
@@ -101,41 +398,61 @@ impl ParallelRuntime {
     fn consume_current_works(&mut self, is_eoi: bool) {
         loop {
             if let Some(work) = self.worker.try_pop() {
-                if cfg!(feature = "debug") {
-                    println!("Thread {}: work.", self.id);
-                }
-                work.call_box(self, ());
+                self.execute_work(work);
             } else if let Some(work) = self.try_steal(is_eoi) {
-                if cfg!(feature = "debug") {
-                    println!("Thread {}: work.", self.id);
-                }
-                work.call_box(self, ());
+                self.execute_work(work);
             } else {
                 break;
             }
         }
+        if let Some(split) = self.split.clone() {
+            self.execute_deferred(&split);
+        }
         if self.id == 0 && is_eoi {
             let (ref lock, _) = *self.whether_to_continue;
             let mut runtime_status = lock.lock().unwrap();
             *runtime_status = RuntimeStatus::Undetermined(0);
+            self.earliest_timer.store(usize::MAX, Ordering::SeqCst);
         }
-        if cfg!(feature = "debug") {
-            println!("Thread {}: sleep.", self.id);
+        self.wait_barrier(
+            if is_eoi { BarrierKind::EndOfInstantWorks } else { BarrierKind::Works });
+    }
+
+    /// Executes the continuations deferred by all the runtimes round after round,
+    /// until none is deferred anymore.
+    fn execute_deferred(&mut self, split: &WorkSplit) {
+        loop {
+            self.wait_barrier(BarrierKind::WorkSplit);
+            if self.id == 0 {
+                split.split_deferred();
+            }
+            self.wait_barrier(BarrierKind::WorkSplit);
+            self.round += 1;
+            if !split.any_deferred.load(Ordering::SeqCst) {
+                return;
+            }
+            for work in mem::take(&mut *split.parts[self.id].lock().unwrap()) {
+                self.on_current_instant(work);
+            }
+            while let Some(work) = self.worker.try_pop() {
+                self.execute_work(work);
+            }
         }
-        self.barrier.wait();
     }
 
     /// Tries to steal work from other workers.
-    /// Returns `None` only when there is no longer anyone who is working.
+    /// Returns `None` only when there is no longer anyone who is working, or at
+    /// once in the deterministic mode.
     fn try_steal(&mut self, is_eoi: bool) -> Option<Box<ContinuationPl<()>>> {
         let wp = if is_eoi { &self.eoi_working_pool } else { &self.working_pool };
         {
             let mut working_pool = wp.lock().unwrap();
             assert!(working_pool.remove(&self.id));
-            if cfg!(feature = "debug") {
-                println!("Thread {}: try to steal.", self.id);
-            }
         }
+        if self.split.is_some() {
+            return None;
+        }
+        self.trace(TraceEvent::StealAttempt);
         loop {
             let working_pool = wp.lock().unwrap();
             if working_pool.is_empty() {
@@ -154,9 +471,8 @@ impl ParallelRuntime {
                 chase_lev::Steal::Data(work) => {
                     let mut working_pool = wp.lock().unwrap();
                     assert!(working_pool.insert(self.id));
-                    if cfg!(feature = "debug") {
-                        println!("Thread {}: steal success.", self.id);
-                    }
+                    drop(working_pool);
+                    self.trace(TraceEvent::StealSuccess { from: to_steal });
                     return Some(work);
                 },
                 _ => {
@@ -171,77 +487,150 @@ impl ParallelRuntime {
     /// One important point is to know if there is still work to be done somewhere
     /// (knowing that the work can come from another runtime).
     fn end_of_instant(&mut self) -> bool {
+        let late_start = self.next_instant_works.len();
         while let Some(work) = self.end_of_instant_works.pop() {
-            self.on_current_instant(work);
+            if self.split.is_some() {
+                // Executed in the same order as by the single thread runtime.
+                self.execute_work(work);
+            } else {
+                self.on_current_instant(work);
+            }
         }
         self.consume_current_works(true);
         while let Some(s) = self.test_presence_signals.pop() {
             s.execute_present_works_box(self);
         }
+        if let Some(split) = self.split.clone() {
+            // The continuations of the presence tests are deferred too.
+            self.execute_deferred(&split);
+        }
+        // The presence tests may look at signals emitted by the other runtimes, so
+        // they must all be done before any signal is reset.
+        self.wait_barrier(BarrierKind::PresenceTests);
         while let Some(s) = self.emitted_signals.pop() {
             s.reset_box();
+            self.trace(TraceEvent::SignalReset);
         }
-        self.barrier.wait();
-        self.deal_with_next_instant_works()
-    }
-
-    /// Moves works from `next_instant_works` to `worker` if there is any
-    /// and decides if the program should be terminate (`true` means shouldn't).
-    fn deal_with_next_instant_works(&mut self) -> bool {
-        if self.next_instant_works.is_empty() {
-            let (ref lock, ref cvar) = *self.whether_to_continue;
-            {
-                let mut runtime_status = lock.lock().unwrap();
-                match *runtime_status {
-                    RuntimeStatus::Undetermined(k)
-                        if k == self.num_threads_total - 1 =>
-                    {
-                        self.reset_working_pool();
-                        *runtime_status = RuntimeStatus::Finished;
-                        cvar.notify_all();
-                        // If this is `true` the program hangs. I think this should
-                        // be the desired behavior when we await some signals that can
-                        // never be emitted.
-                        return self.await_counter.load(Ordering::SeqCst) != 0
-                    },
-                    RuntimeStatus::Undetermined(k) => {
-                        *runtime_status = RuntimeStatus::Undetermined(k+1);
-                    },
-                    RuntimeStatus::WorkRemained => return true,
-                    RuntimeStatus::Finished => assert!(false),
-                };
-            }
-            let mut runtime_status = lock.lock().unwrap();
-            loop {
-                match *runtime_status {
-                    RuntimeStatus::Undetermined(_) => {
-                        runtime_status = cvar.wait(runtime_status).unwrap();
-                    },
-                    RuntimeStatus::WorkRemained => return true,
-                    RuntimeStatus::Finished => {
-                        return self.await_counter.load(Ordering::SeqCst) != 0;
-                    },
-                };
-            }
+        if let Some(instant) = self.earliest_timer() {
+            self.earliest_timer.fetch_min(instant, Ordering::SeqCst);
+        }
+        self.wait_barrier(BarrierKind::SignalsReset);
+        for f in self.watchers.iter_mut() {
+            f(self.instant);
+        }
+        self.trace(TraceEvent::InstantEnd);
+        self.deal_with_next_instant_works(late_start)
+    }
+
+    /// Returns the instant of the earliest timer of the runtime. In the deterministic
+    /// mode the first runtime also accounts for the timers kept by the `WorkSplit`.
+    fn earliest_timer(&self) -> Option<usize> {
+        let own = self.timers.keys().next().cloned();
+        match self.split {
+            Some(ref split) if self.id == 0 => match (own, split.earliest_timer()) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+            _ => own,
+        }
+    }
+
+    /// Moves works from `next_instant_works` and the due timers to `worker` if
+    /// there is any and decides if the program should be terminate (`true` means
+    /// shouldn't). The works registered from `late_start` on were registered after
+    /// the works of the instant.
+    fn deal_with_next_instant_works(&mut self, late_start: usize) -> bool {
+        if self.work_remained() {
+            self.instant += 1;
         } else {
-            {
-                let (ref lock, ref cvar) = *self.whether_to_continue;
-                let mut runtime_status = lock.lock().unwrap();
-                match *runtime_status {
-                    RuntimeStatus::Undetermined(_) => {
-                        self.reset_working_pool();
-                        *runtime_status = RuntimeStatus::WorkRemained;
-                        cvar.notify_all();
-                    },
-                    RuntimeStatus::WorkRemained => (),
-                    RuntimeStatus::Finished => assert!(false),
-                };
+            let earliest_timer = self.earliest_timer.load(Ordering::SeqCst);
+            if earliest_timer == usize::MAX {
+                // If this is `true` the program hangs. I think this should
+                // be the desired behavior when we await some signals that can
+                // never be emitted.
+                return self.await_counter.load(Ordering::SeqCst)
+                    > self.cancelled_awaits.load(Ordering::SeqCst);
             }
-            while let Some(work) = self.next_instant_works.pop() {
+            // Nothing but timers is pending, so we can skip the empty instants.
+            self.instant = if self.fast_forward { earliest_timer } else { self.instant + 1 };
+        }
+        if let Some(split) = self.split.clone() {
+            self.split_works(&split, late_start);
+            return true;
+        }
+        while let Some(work) = self.next_instant_works.pop() {
+            self.on_current_instant(work);
+        }
+        let later_timers = self.timers.split_off(&(self.instant + 1));
+        for (_, works) in mem::replace(&mut self.timers, later_timers) {
+            for work in works {
                 self.on_current_instant(work);
             }
+        }
+        true
+    }
+
+    /// Gives the works of the next instant and the timers to the `WorkSplit`, which
+    /// is split by the first runtime, and takes back the part of this runtime.
+    fn split_works(&mut self, split: &WorkSplit, late_start: usize) {
+        let late_works = self.next_instant_works.split_off(late_start);
+        *split.deposits[self.id].lock().unwrap() = Deposit {
+            works: mem::take(&mut self.next_instant_works),
+            late_works,
+            timers: mem::take(&mut self.timers),
+        };
+        self.wait_barrier(BarrierKind::WorkSplit);
+        if self.id == 0 {
+            split.split(self.instant);
+        }
+        self.wait_barrier(BarrierKind::WorkSplit);
+        for work in mem::take(&mut *split.parts[self.id].lock().unwrap()) {
+            self.on_current_instant(work);
+        }
+    }
+
+    /// Decides together with the other runtimes if there is some work to do
+    /// at the next instant.
+    fn work_remained(&mut self) -> bool {
+        let (ref lock, ref cvar) = *self.whether_to_continue;
+        let has_work = !self.next_instant_works.is_empty() || !self.sustained.is_empty()
+            || self.earliest_timer() == Some(self.instant + 1);
+        if has_work {
+            let mut runtime_status = lock.lock().unwrap();
+            match *runtime_status {
+                RuntimeStatus::Undetermined(_) => {
+                    self.reset_working_pool();
+                    *runtime_status = RuntimeStatus::WorkRemained;
+                    cvar.notify_all();
+                },
+                RuntimeStatus::WorkRemained => (),
+                RuntimeStatus::Finished => assert!(false),
+            };
             return true;
         }
+        let mut runtime_status = lock.lock().unwrap();
+        match *runtime_status {
+            RuntimeStatus::Undetermined(k) if k == self.num_threads_total - 1 => {
+                self.reset_working_pool();
+                *runtime_status = RuntimeStatus::Finished;
+                cvar.notify_all();
+                return false;
+            },
+            RuntimeStatus::Undetermined(k) => {
+                *runtime_status = RuntimeStatus::Undetermined(k+1);
+            },
+            RuntimeStatus::WorkRemained => return true,
+            RuntimeStatus::Finished => assert!(false),
+        };
+        loop {
+            match *runtime_status {
+                RuntimeStatus::Undetermined(_) => {
+                    runtime_status = cvar.wait(runtime_status).unwrap();
+                },
+                RuntimeStatus::WorkRemained => return true,
+                RuntimeStatus::Finished => return false,
+            };
+        }
     }
 
     /// Add all the workers to the empty working pool, preparing for the next instant.
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{VecDeque, BTreeMap};
 use std::sync::{Arc, Mutex, Barrier, Condvar};
 use std::sync::atomic::AtomicUsize;
 
@@ -9,22 +9,69 @@ use std::process;
 
 use crossbeam;
 use crossbeam::sync::chase_lev;
-use rand::{weak_rng, Rng};
+use rand::{weak_rng, Rng, XorShiftRng};
 use ordermap::OrderSet;
 
-use runtime::Runtime;
-use runtime::parallel_runtime::{ParallelRuntime, RuntimeStatus};
+use runtime::{Runtime, Subscriber, default_subscriber, seeded_rng};
+use runtime::parallel_runtime::{ParallelRuntime, RuntimeStatus, WorkSplit};
 use continuation::ContinuationPl;
 
 /// Collect multiple `ParallelRuntime` to form a parallel execution engine.
+///
+/// The runtimes choose at random the runtime from which they steal work, and the
+/// work registered in the engine is given to a random runtime. The random number
+/// generators can be seeded with `with_seed`, but the execution still depends on
+/// the scheduling of the threads. An engine created with `deterministic` doesn't
+/// steal work: at the end of each instant, the works of the next instant are put
+/// in the order in which the single thread runtime would execute them and cut into
+/// one contiguous part for each runtime. During the instant the branches of the
+/// joins are executed at once, as by the single thread runtime, and the multi-producer
+/// signals gather the values emitted by each runtime apart, combining them runtime
+/// after runtime at the end of the instant.
+///
+/// Whether a process awaiting a signal registers before or after the signal is
+/// emitted by another runtime depends on the scheduling, so in this mode the process
+/// is never woken up directly when there are several runtimes. The instant is rather
+/// executed in rounds: the processes woken up during a round, and the joins whose
+/// branches ended on several runtimes, are given back to the runtime which registered
+/// them (the one of the last branch for a join) and executed in the order of
+/// registration at the next round, until nothing is woken up anymore.
+///
+/// The values of the signals and the results of the processes are then the same at
+/// each execution. This is useful to test programs written for the parallel engine
+/// while still executing them in parallel. They are also the same as with the single
+/// thread runtime as long as the processes only communicate through the values of
+/// the signals, read at the end of the instant: a process woken up during the instant
+/// continues later than with the single thread runtime. The presence expressions
+/// are an exception, since they are evaluated as soon as one of their signals is
+/// emitted: the round at which they are found to be true may change.
 pub struct ParallelRuntimeCollection {
     runtimes: Vec<ParallelRuntime>,
+    rng: XorShiftRng,
+    deterministic: bool,
 }
 
 impl ParallelRuntimeCollection {
     /// Creates a new parallel execution engine containing a certain number of
     /// runtimes. Each runtime is runned in a separeted thread.
     pub fn new(num_runtimes: usize) -> Self {
+        Self::with_rngs(num_runtimes, |_| weak_rng(), false)
+    }
+
+    /// Same as `new`, the random choices of the engine being made from `seed`.
+    pub fn with_seed(num_runtimes: usize, seed: u64) -> Self {
+        Self::with_rngs(num_runtimes, |i| seeded_rng(seed, i), false)
+    }
+
+    /// Creates an engine whose executions don't depend on the scheduling of the
+    /// threads, as explained above.
+    pub fn deterministic(num_runtimes: usize) -> Self {
+        Self::with_rngs(num_runtimes, |i| seeded_rng(0, i), true)
+    }
+
+    fn with_rngs<F>(num_runtimes: usize, mut rng: F, deterministic: bool) -> Self
+        where F: FnMut(usize) -> XorShiftRng
+    {
         if num_runtimes == 0 {
             panic!("There should be at least one runtime!");
         }
@@ -38,10 +85,13 @@ impl ParallelRuntimeCollection {
         }
         let barrier = Arc::new(Barrier::new(num_runtimes));
         let await_counter = Arc::new(AtomicUsize::new(0));
+        let cancelled_awaits = Arc::new(AtomicUsize::new(0));
+        let earliest_timer = Arc::new(AtomicUsize::new(usize::MAX));
         let working_pool = Arc::new(
             Mutex::new((0..num_runtimes).collect::<OrderSet<_>>()));
         let eoi_working_pool = Arc::new(
             Mutex::new((0..num_runtimes).collect::<OrderSet<_>>()));
+        let split = if deterministic { Some(Arc::new(WorkSplit::new(num_runtimes))) } else { None };
         let whether_to_continue = Arc::new(
             (Mutex::new(RuntimeStatus::WorkRemained), Condvar::new()));
         for i in 0..num_runtimes {
@@ -53,7 +103,8 @@ impl ParallelRuntimeCollection {
                 worker,
                 stealers,
                 barrier: barrier.clone(),
-                rng: weak_rng(),
+                rng: rng(i),
+                split: split.clone(),
                 working_pool: working_pool.clone(),
                 whether_to_continue: whether_to_continue.clone(),
                 next_instant_works: Vec::new(),
@@ -61,12 +112,20 @@ impl ParallelRuntimeCollection {
                 eoi_working_pool: eoi_working_pool.clone(),
                 emitted_signals: Vec::new(),
                 await_counter: await_counter.clone(),
+                cancelled_awaits: cancelled_awaits.clone(),
                 test_presence_signals: Vec::new(),
-                #[cfg(feature = "debug")]
+                timers: BTreeMap::new(),
+                earliest_timer: earliest_timer.clone(),
+                sustained: Vec::new(),
+                watchers: Vec::new(),
                 instant: 0,
+                round: 0,
+                seq: 0,
+                fast_forward: false,
+                subscriber: default_subscriber(),
             })
         }
-        ParallelRuntimeCollection { runtimes }
+        ParallelRuntimeCollection { runtimes, rng: rng(num_runtimes), deterministic }
     }
 
     /// Executes in parallel all the runtimes contained in the collection, with
@@ -94,9 +153,19 @@ impl ParallelRuntimeCollection {
         });
     }
 
+    /// Installs a subscriber shared by all the runtimes of the collection.
+    pub fn set_subscriber(&mut self, subscriber: Arc<Subscriber>) {
+        for runtime in self.runtimes.iter_mut() {
+            runtime.subscriber = Some(subscriber.clone());
+        }
+    }
+
     /// Adds some work to be executed by the execution engine.
     pub fn register_work(&mut self, c: Box<ContinuationPl<()>>) {
-        let runtime = weak_rng().choose_mut(&mut self.runtimes).unwrap();
-        runtime.on_current_instant(c);
+        if self.deterministic {
+            self.runtimes[0].on_current_instant(c);
+        } else {
+            self.rng.choose_mut(&mut self.runtimes).unwrap().on_current_instant(c);
+        }
     }
 }
@@ -1,36 +1,64 @@
 use std::rc::Rc;
+use std::sync::Arc;
+use std::mem;
+use std::collections::BTreeMap;
+use rand::{Rng, XorShiftRng};
 
-use runtime::Runtime;
+use runtime::{Runtime, Subscriber, TraceEvent, default_subscriber, seeded_rng};
 use continuation::ContinuationSt;
 use signal::signal_runtime::SignalRuntimeRefBaseSt;
 
 /// Runtime for executing reactive continuations in the main thread.
+///
+/// The continuations of an instant are executed in LIFO order. A runtime created
+/// with `shuffled` executes them, and the works of the end of instant, in a random
+/// order determined by a seed instead, which can reveal programs whose results
+/// depend on the order of execution, as it happens with the parallel engine.
 pub struct SingleThreadRuntime {
     current_instant_works: Rc<Vec<Box<ContinuationSt<()>>>>,
     next_instant_works: Rc<Vec<Box<ContinuationSt<()>>>>,
     end_of_instant_works: Vec<Box<ContinuationSt<()>>>,
     emitted_signals: Vec<Box<SignalRuntimeRefBaseSt>>,
     await_counter: usize,
+    cancelled_awaits: usize,
     test_presence_signals: Vec<Box<SignalRuntimeRefBaseSt>>,
-    #[cfg(feature = "debug")]
+    timers: BTreeMap<usize, Vec<Box<ContinuationSt<()>>>>,
+    sustained: Vec<Sustained>,
+    watchers: Vec<Box<FnMut(usize)>>,
     instant: usize,
+    subscriber: Option<Arc<Subscriber>>,
+    rng: Option<XorShiftRng>,
 }
 
+/// A function executed at the beginning of each instant as long as it returns `true`.
+type Sustained = Box<FnMut(&mut SingleThreadRuntime) -> bool>;
+
 impl Runtime for SingleThreadRuntime {
+    /// Executes instants until all work is completed. The instants where nothing
+    /// but timers is pending are skipped.
+    fn execute(&mut self) {
+        while self.instant() {
+            self.skip_empty_instants();
+        }
+    }
+
     /// Executes a single instant to completion. Indicates if more work remains to be done.
     fn instant(&mut self) -> bool {
-        #[cfg(feature = "debug")] {
-            println!("instant {}", self.instant);
-            self.instant += 1;
-        }
-        while let Some(work) = Rc::get_mut(&mut self.current_instant_works).unwrap().pop() {
-            work.call_box(self, ());
+        self.trace(TraceEvent::InstantStart);
+        self.execute_sustained();
+        loop {
+            let works = Rc::get_mut(&mut self.current_instant_works).unwrap();
+            match next_work(works, &mut self.rng) {
+                Some(work) => self.execute_work(work),
+                None => break,
+            }
         }
-        while let Some(work) = self.end_of_instant_works.pop() {
-            work.call_box(self, ());
+        while let Some(work) = next_work(&mut self.end_of_instant_works, &mut self.rng) {
+            self.execute_work(work);
         }
         self.end_of_instant();
-        self.current_instant_works.len() != 0 || self.await_counter > 0
+        self.current_instant_works.len() != 0 || self.await_counter > self.cancelled_awaits
+            || !self.timers.is_empty() || !self.sustained.is_empty()
     }
 }
 
@@ -43,24 +71,90 @@ impl SingleThreadRuntime {
             end_of_instant_works: Vec::new(),
             emitted_signals: Vec::new(),
             await_counter: 0,
+            cancelled_awaits: 0,
             test_presence_signals: Vec::new(),
-            #[cfg(feature = "debug")]
+            timers: BTreeMap::new(),
+            sustained: Vec::new(),
+            watchers: Vec::new(),
             instant: 0,
+            subscriber: default_subscriber(),
+            rng: None,
+        }
+    }
+
+    /// Creates a runtime executing the continuations of each instant in an order
+    /// given by `seed`.
+    pub fn shuffled(seed: u64) -> Self {
+        let mut runtime = SingleThreadRuntime::new();
+        runtime.rng = Some(seeded_rng(seed, 0));
+        runtime
+    }
+
+    /// Installs a subscriber which is notified of the events of the runtime.
+    pub fn set_subscriber(&mut self, subscriber: Arc<Subscriber>) {
+        self.subscriber = Some(subscriber);
+    }
+
+    /// Notifies the subscriber, if there is one.
+    fn trace(&self, event: TraceEvent) {
+        if let Some(ref subscriber) = self.subscriber {
+            subscriber.event(0, self.instant, event);
         }
     }
+
+    /// Executes a continuation, telling the subscriber.
+    fn execute_work(&mut self, work: Box<ContinuationSt<()>>) {
+        self.trace(TraceEvent::ContinuationStart);
+        work.call_box(self, ());
+        self.trace(TraceEvent::ContinuationExecuted);
+    }
     
     /// Checks the presents of signals, resets all the emitted signals and replaces
     /// `self.current_instant_works` by `self.next_instant_works` at the end of
-    /// each instant.
+    /// each instant. The timers that are due are then added to the new instant.
     fn end_of_instant(&mut self) {
         while let Some(s) = self.test_presence_signals.pop() {
             s.execute_present_works_box(self);
         }
         while let Some(s) = self.emitted_signals.pop() {
             s.reset_box();
+            self.trace(TraceEvent::SignalReset);
+        }
+        for f in self.watchers.iter_mut() {
+            f(self.instant);
         }
+        self.trace(TraceEvent::InstantEnd);
         self.current_instant_works = self.next_instant_works.clone();
         self.next_instant_works = Rc::new(Vec::new());
+        self.instant += 1;
+        self.add_due_timers();
+    }
+
+    /// When nothing but timers is pending, jumps to the instant of the earliest
+    /// timer. This is only done by `execute`, so that a runtime driven instant by
+    /// instant always advances by one instant.
+    fn skip_empty_instants(&mut self) {
+        if self.current_instant_works.is_empty() && self.sustained.is_empty() {
+            if let Some(&instant) = self.timers.keys().next() {
+                self.instant = instant;
+                self.add_due_timers();
+            }
+        }
+    }
+
+    /// Moves the timers that are due to the current instant.
+    fn add_due_timers(&mut self) {
+        let later_timers = self.timers.split_off(&(self.instant + 1));
+        for (_, works) in mem::replace(&mut self.timers, later_timers) {
+            for work in works {
+                self.on_current_instant(work);
+            }
+        }
+    }
+
+    /// Returns the number of the current instant, the first instant being 0.
+    pub(crate) fn current_instant(&self) -> usize {
+        self.instant
     }
     
     /// Registers a continuation to execute on the current instant.
@@ -73,6 +167,40 @@ impl SingleThreadRuntime {
         Rc::get_mut(&mut self.next_instant_works).unwrap().push(c);
     }
 
+    /// Registers a continuation to execute at a given instant. The continuation
+    /// is executed at the current instant if this instant is already reached.
+    pub(crate) fn on_instant(&mut self, instant: usize, c: Box<ContinuationSt<()>>) {
+        if instant <= self.instant {
+            self.on_current_instant(c);
+        } else if instant == self.instant + 1 {
+            self.on_next_instant(c);
+        } else {
+            self.timers.entry(instant).or_default().push(c);
+        }
+    }
+
+    /// Registers a function to execute at the beginning of each instant, as long
+    /// as it returns `true`.
+    pub(crate) fn sustain(&mut self, f: Sustained) {
+        self.sustained.push(f);
+    }
+
+    /// Executes the sustained functions and drops the ones that are over.
+    fn execute_sustained(&mut self) {
+        for mut f in mem::take(&mut self.sustained) {
+            if f(self) {
+                self.sustained.push(f);
+            }
+        }
+    }
+
+    /// Registers a function called at the end of each instant, once the signals
+    /// are reset, with the number of the instant. Unlike sustained functions,
+    /// watchers don't keep the runtime alive.
+    pub(crate) fn watch(&mut self, f: Box<FnMut(usize)>) {
+        self.watchers.push(f);
+    }
+
     /// Registers a continuation to execute at the end of the instant. Runtime calls for `c`
     /// behave as if they where executed during the next instant.
     pub(crate) fn on_end_of_instant(&mut self, c: Box<ContinuationSt<()>>) {
@@ -82,17 +210,31 @@ impl SingleThreadRuntime {
     /// Increases the await counter by 1 when some process awaits a signal.
     pub(crate) fn incr_await_counter(&mut self) {
         self.await_counter += 1;
+        self.trace(TraceEvent::AwaitRegistered);
     }
 
     /// Decrease the await counter by 1 when some signal is emitted and
     /// one corresponding process is thus executed.
     pub(crate) fn decr_await_counter(&mut self) {
         self.await_counter -= 1;
+        self.trace(TraceEvent::AwaitWoken);
+    }
+
+    /// Tells that a process doesn't wait anymore for a signal it awaits, although its
+    /// continuation stays registered and is still counted by the await counter.
+    pub(crate) fn cancel_await(&mut self) {
+        self.cancelled_awaits += 1;
+    }
+
+    /// Called by the continuation of a cancelled await when the signal is emitted.
+    pub(crate) fn cancelled_await_woken(&mut self) {
+        self.cancelled_awaits -= 1;
     }
     
     /// Registers a emitted signal for the current instant.
     pub(crate) fn emit_signal(&mut self, s: Box<SignalRuntimeRefBaseSt>) {
         self.emitted_signals.push(s);
+        self.trace(TraceEvent::SignalEmitted);
     }
 
     /// Registers a signal for which we need to test its presence on the current instant.
@@ -100,3 +242,14 @@ impl SingleThreadRuntime {
         self.test_presence_signals.push(s);
     }
 }
+
+/// Removes the next work to execute, the last one or a random one.
+fn next_work<W>(works: &mut Vec<W>, rng: &mut Option<XorShiftRng>) -> Option<W> {
+    match *rng {
+        Some(ref mut rng) if !works.is_empty() => {
+            let index = rng.gen_range(0, works.len());
+            Some(works.swap_remove(index))
+        },
+        _ => works.pop(),
+    }
+}
@@ -0,0 +1,151 @@
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use std::collections::BTreeMap;
+
+use runtime::{Subscriber, TraceEvent, BarrierKind};
+
+/// What happened during one instant of the execution. The barrier times are
+/// summed over all the runtimes, and are always zero for a `SingleThreadRuntime`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InstantStats {
+    pub instant: usize,
+    /// The number of continuations executed by each runtime.
+    pub continuations: Vec<usize>,
+    pub steal_attempts: usize,
+    pub steal_successes: usize,
+    pub works_barrier_wait: Duration,
+    pub eoi_barrier_wait: Duration,
+    /// Includes the barriers splitting the works in the deterministic mode.
+    pub reset_barrier_wait: Duration,
+    pub signals_emitted: usize,
+    /// The number of processes still awaiting some signal at the end of the instant.
+    pub awaits_pending: usize,
+}
+
+/// A subscriber building the `InstantStats` of an execution. Each runtime records
+/// its events in its own slot, so the runtimes don't wait for each other, and the
+/// slots are only combined by `stats`.
+pub struct StatsCollector {
+    slots: Vec<Mutex<RuntimeStats>>,
+}
+
+/// What a single runtime recorded.
+#[derive(Default)]
+struct RuntimeStats {
+    stats: BTreeMap<usize, InstantStats>,
+    awaits: BTreeMap<usize, isize>,
+    barrier_start: Option<Instant>,
+}
+
+impl InstantStats {
+    /// Adds the statistics of another runtime for the same instant.
+    fn add(&mut self, other: &InstantStats) {
+        for (n, m) in self.continuations.iter_mut().zip(&other.continuations) {
+            *n += *m;
+        }
+        self.steal_attempts += other.steal_attempts;
+        self.steal_successes += other.steal_successes;
+        self.works_barrier_wait += other.works_barrier_wait;
+        self.eoi_barrier_wait += other.eoi_barrier_wait;
+        self.reset_barrier_wait += other.reset_barrier_wait;
+        self.signals_emitted += other.signals_emitted;
+    }
+}
+
+impl StatsCollector {
+    /// Creates a collector for an engine with the given number of runtimes.
+    pub fn new(num_runtimes: usize) -> Self {
+        StatsCollector {
+            slots: (0..num_runtimes).map(|_| Mutex::new(RuntimeStats::default())).collect(),
+        }
+    }
+
+    /// Returns the statistics of the instants executed so far, ordered by instant.
+    /// The instants that were skipped because only timers were pending are absent.
+    pub fn stats(&self) -> Vec<InstantStats> {
+        let mut stats: BTreeMap<usize, InstantStats> = BTreeMap::new();
+        let mut awaits: BTreeMap<usize, isize> = BTreeMap::new();
+        for slot in &self.slots {
+            let slot = slot.lock().unwrap();
+            for (&instant, s) in &slot.stats {
+                stats.entry(instant).or_insert_with(|| self.empty_stats(instant)).add(s);
+            }
+            for (&instant, n) in &slot.awaits {
+                *awaits.entry(instant).or_insert(0) += *n;
+            }
+        }
+        let mut pending = 0;
+        stats.into_iter().map(|(instant, mut stats)| {
+            pending += awaits.get(&instant).cloned().unwrap_or(0);
+            stats.awaits_pending = pending as usize;
+            stats
+        }).collect()
+    }
+
+    fn empty_stats(&self, instant: usize) -> InstantStats {
+        InstantStats {
+            instant,
+            continuations: vec![0; self.slots.len()],
+            .. InstantStats::default()
+        }
+    }
+}
+
+impl Subscriber for StatsCollector {
+    fn event(&self, thread: usize, instant: usize, event: TraceEvent) {
+        let slot = self.slots.get(thread).unwrap_or_else(|| panic!(
+            "The StatsCollector was created for {} runtimes but the runtime {} sent an event.",
+            self.slots.len(), thread));
+        let mut slot = slot.lock().unwrap();
+        let slot = &mut *slot;
+        let stats = slot.stats.entry(instant).or_insert_with(|| self.empty_stats(instant));
+        match event {
+            TraceEvent::ContinuationExecuted => stats.continuations[thread] += 1,
+            TraceEvent::StealAttempt => stats.steal_attempts += 1,
+            TraceEvent::StealSuccess { .. } => stats.steal_successes += 1,
+            TraceEvent::SignalEmitted => stats.signals_emitted += 1,
+            TraceEvent::AwaitRegistered =>
+                *slot.awaits.entry(instant).or_insert(0) += 1,
+            TraceEvent::AwaitWoken =>
+                *slot.awaits.entry(instant).or_insert(0) -= 1,
+            TraceEvent::BarrierWait(_) =>
+                slot.barrier_start = Some(Instant::now()),
+            TraceEvent::BarrierPassed(kind) => {
+                let waited = slot.barrier_start.take().unwrap().elapsed();
+                match kind {
+                    BarrierKind::Works => stats.works_barrier_wait += waited,
+                    BarrierKind::EndOfInstantWorks | BarrierKind::PresenceTests =>
+                        stats.eoi_barrier_wait += waited,
+                    BarrierKind::SignalsReset | BarrierKind::WorkSplit =>
+                        stats.reset_barrier_wait += waited,
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+/// Writes the statistics as CSV, with one line per instant. Barrier times are
+/// given in microseconds.
+pub fn write_csv<W>(stats: &[InstantStats], mut out: W) -> io::Result<()> where W: Write {
+    let num_runtimes = stats.first().map_or(0, |s| s.continuations.len());
+    write!(out, "instant")?;
+    for i in 0..num_runtimes {
+        write!(out, ",continuations_{}", i)?;
+    }
+    writeln!(out, ",steal_attempts,steal_successes,works_barrier_us,eoi_barrier_us,\
+                   reset_barrier_us,signals_emitted,awaits_pending")?;
+    let micros = |d: Duration| d.as_secs() * 1_000_000 + d.subsec_nanos() as u64 / 1000;
+    for s in stats {
+        write!(out, "{}", s.instant)?;
+        for n in &s.continuations {
+            write!(out, ",{}", n)?;
+        }
+        writeln!(out, ",{},{},{},{},{},{},{}",
+                 s.steal_attempts, s.steal_successes, micros(s.works_barrier_wait),
+                 micros(s.eoi_barrier_wait), micros(s.reset_barrier_wait),
+                 s.signals_emitted, s.awaits_pending)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+/// The events a runtime reports to its subscriber during the execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// A new instant starts.
+    InstantStart,
+    /// The instant is over, signals have been reset.
+    InstantEnd,
+    /// The runtime starts executing a continuation.
+    ContinuationStart,
+    /// The continuation has been executed.
+    ContinuationExecuted,
+    /// The runtime has no more work and tries to steal some from the others.
+    StealAttempt,
+    /// Some work was stolen from the runtime `from`.
+    StealSuccess { from: usize },
+    /// The runtime starts waiting for the other runtimes at a barrier.
+    BarrierWait(BarrierKind),
+    /// All the runtimes have reached the barrier.
+    BarrierPassed(BarrierKind),
+    /// A signal is emitted for the first time in the instant.
+    SignalEmitted,
+    /// An emitted signal is reset at the end of the instant.
+    SignalReset,
+    /// A process starts awaiting a signal.
+    AwaitRegistered,
+    /// A process awaiting a signal is woken up by its emission.
+    AwaitWoken,
+}
+
+/// The barriers at which parallel runtimes synchronize at the end of each instant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BarrierKind {
+    /// After the works of the instant.
+    Works,
+    /// After the works to be executed at the end of the instant.
+    EndOfInstantWorks,
+    /// After the presence tests of the end of the instant, before the signals
+    /// are reset.
+    PresenceTests,
+    /// After the signals are reset, before deciding what to do next.
+    SignalsReset,
+    /// In the deterministic mode, while the works of the next instant or the
+    /// continuations deferred to the next round are split between the runtimes.
+    WorkSplit,
+}
+
+/// Receives the events of the runtimes it is installed on. With a parallel
+/// engine the same subscriber is shared and called from every thread, `thread`
+/// being the id of the calling runtime (always 0 for a `SingleThreadRuntime`).
+pub trait Subscriber: Send + Sync {
+    /// Called by a runtime for each of its events, on the thread executing it and
+    /// before it goes on, so a slow subscriber slows down the execution.
+    ///
+    /// `thread` is always 0 with a `SingleThreadRuntime`, and the id of the runtime
+    /// in `0..num_threads` with a parallel engine, in the deterministic mode too.
+    /// `instant` is the instant being executed by this runtime: the events of the
+    /// end of an instant, up to `InstantEnd`, still carry its number. Instants may
+    /// be skipped when only timers are pending.
+    ///
+    /// With a parallel engine the method is called from all the threads at the same
+    /// time, without any lock, so the subscriber has to synchronize itself. The
+    /// events of a runtime come in order, but nothing orders the events of different
+    /// runtimes: the last events of an instant from one runtime may come after the
+    /// first ones of the next instant from another.
+    fn event(&self, thread: usize, instant: usize, event: TraceEvent);
+}
+
+/// Prints the events on the standard output. This is what the `debug` feature
+/// installs by default.
+pub struct PrintSubscriber;
+
+impl Subscriber for PrintSubscriber {
+    fn event(&self, thread: usize, instant: usize, event: TraceEvent) {
+        match event {
+            TraceEvent::InstantStart => println!("Thread {}: instant {}.", thread, instant),
+            TraceEvent::ContinuationStart => println!("Thread {}: work.", thread),
+            TraceEvent::StealAttempt => println!("Thread {}: try to steal.", thread),
+            TraceEvent::StealSuccess { from } =>
+                println!("Thread {}: steal success from {}.", thread, from),
+            TraceEvent::BarrierWait(_) => println!("Thread {}: sleep.", thread),
+            _ => (),
+        }
+    }
+}
+
+/// The subscriber of newly created runtimes.
+pub(crate) fn default_subscriber() -> Option<Arc<Subscriber>> {
+    if cfg!(feature = "debug") {
+        Some(Arc::new(PrintSubscriber))
+    } else {
+        None
+    }
+}
@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 use signal::Signal;
@@ -11,6 +11,10 @@ pub struct AwaitImmediate<S>(pub(crate) S);
 
 impl<S> Process for AwaitImmediate<S> where S: Signal {
     type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("await_immediate({})", self.0.label()))
+    }
 }
 
 impl<S> ProcessMut for AwaitImmediate<S> where S: Signal {}
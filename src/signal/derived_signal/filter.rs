@@ -0,0 +1,170 @@
+use std::sync::Arc;
+use std::marker::PhantomData;
+
+use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use signal::Signal;
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants};
+use signal::signal_runtime::{SignalRuntimeRefSt, SignalRuntimeRefPl, AwaitToken};
+use signal::valued_signal::{ValuedSignal, SpSignal, GetValue};
+
+/// A signal which is present only when another single-producer signal is present
+/// with a value satisfying some predicate.
+///
+/// This is restricted to single-producer signals because the value of a
+/// multi-producer signal is only known at the end of the instant, which is too late
+/// to decide if the signal is present. Note also that `pre_present` still refers
+/// to the emissions of the original signal.
+pub struct FilterSignal<S, F> {
+    pub(crate) signal: S,
+    pub(crate) pred: Arc<F>,
+}
+
+impl<S, F> Clone for FilterSignal<S, F> where S: Clone {
+    fn clone(&self) -> Self {
+        FilterSignal { signal: self.signal.clone(), pred: self.pred.clone() }
+    }
+}
+
+impl<S, F> Signal for FilterSignal<S, F>
+    where S: ValuedSignal<SigType=SpSignal>, F: Fn(&S::Stored) -> bool + 'static
+{
+    type RuntimeRef = FilterSignalRuntimeRef<S::RuntimeRef, F, S::Stored>;
+
+    fn runtime(&self) -> Self::RuntimeRef {
+        FilterSignalRuntimeRef {
+            signal_runtime: self.signal.runtime(),
+            pred: self.pred.clone(),
+            marker: PhantomData,
+        }
+    }
+
+    fn label(&self) -> String {
+        format!("filter({})", self.signal.label())
+    }
+}
+
+impl<S, F> ValuedSignal for FilterSignal<S, F>
+    where S: ValuedSignal<SigType=SpSignal>, F: Fn(&S::Stored) -> bool + 'static
+{
+    type Stored = S::Stored;
+    type SigType = SpSignal;
+}
+
+/// Runtime reference of a filtered signal, which refers to the runtime of the
+/// original signal.
+pub struct FilterSignalRuntimeRef<R, F, V> {
+    signal_runtime: R,
+    pred: Arc<F>,
+    marker: PhantomData<fn(&V)>,
+}
+
+impl<R, F, V> Clone for FilterSignalRuntimeRef<R, F, V> where R: Clone {
+    fn clone(&self) -> Self {
+        FilterSignalRuntimeRef {
+            signal_runtime: self.signal_runtime.clone(),
+            pred: self.pred.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R, F, V> FilterSignalRuntimeRef<R, F, V> where R: GetValue<V>, F: Fn(&V) -> bool {
+    /// Tests if the value of the original signal satisfies the predicate.
+    fn is_accepted(&self) -> bool {
+        (self.pred)(&self.signal_runtime.get_value())
+    }
+}
+
+impl<Rt, R, F, V> SignalRuntimeRefBase<Rt> for FilterSignalRuntimeRef<R, F, V>
+    where Rt: Runtime, R: SignalRuntimeRefBase<Rt> + GetValue<V>, F: Fn(&V) -> bool + 'static, V: 'static
+{
+    fn is_emitted(&self) -> bool {
+        self.signal_runtime.is_emitted() && self.is_accepted()
+    }
+
+    fn emissions(&self) -> EmissionInstants {
+        self.signal_runtime.emissions()
+    }
+
+    /// A derived signal is never emitted itself, so there is nothing to reset.
+    fn reset(&mut self) {}
+
+    fn execute_present_works(&mut self, runtime: &mut Rt) {
+        self.signal_runtime.execute_present_works(runtime);
+    }
+}
+
+impl<R, F, V> GetValue<V> for FilterSignalRuntimeRef<R, F, V> where R: GetValue<V> {
+    fn get_value(&self) -> V {
+        self.signal_runtime.get_value()
+    }
+}
+
+// Implements the traits for the single thread version of the library.
+
+impl<R, F, V> SignalRuntimeRefSt for FilterSignalRuntimeRef<R, F, V>
+    where R: SignalRuntimeRefSt + GetValue<V> + Clone, F: Fn(&V) -> bool + 'static, V: 'static
+{
+    /// Calls `c` at the first cycle where the original signal is present with
+    /// a value satisfying the predicate.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationSt<()>
+    {
+        let mut filtered = self.clone();
+        let token2 = token.cloned();
+        self.signal_runtime.on_signal_cancellable(runtime, token, move |r: &mut SingleThreadRuntime, ()| {
+            if filtered.is_accepted() {
+                c.call(r, ());
+            } else {
+                r.on_next_instant(Box::new(move |r: &mut SingleThreadRuntime, ()| {
+                    filtered.on_signal_cancellable(r, token2.as_ref(), c);
+                }));
+            }
+        });
+    }
+
+    /// Calls `c` only if the original signal is present during this cycle.
+    /// It's up to `c` to test the value with `is_emitted`.
+    fn on_signal_present<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+        where C: ContinuationSt<()>
+    {
+        self.signal_runtime.on_signal_present(runtime, c);
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<R, F, V> SignalRuntimeRefPl for FilterSignalRuntimeRef<R, F, V>
+    where R: SignalRuntimeRefPl + GetValue<V> + Clone + Sync,
+          F: Fn(&V) -> bool + Send + Sync + 'static,
+          V: 'static,
+{
+    /// Calls `c` at the first cycle where the original signal is present with
+    /// a value satisfying the predicate.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationPl<()>
+    {
+        let mut filtered = self.clone();
+        let token2 = token.cloned();
+        self.signal_runtime.on_signal_cancellable(runtime, token, move |r: &mut ParallelRuntime, ()| {
+            if filtered.is_accepted() {
+                c.call(r, ());
+            } else {
+                r.on_next_instant(Box::new(move |r: &mut ParallelRuntime, ()| {
+                    filtered.on_signal_cancellable(r, token2.as_ref(), c);
+                }));
+            }
+        });
+    }
+
+    /// Calls `c` only if the original signal is present during this cycle.
+    /// It's up to `c` to test the value with `is_emitted`.
+    fn on_signal_present<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+        where C: ContinuationPl<()>
+    {
+        self.signal_runtime.on_signal_present(runtime, c);
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+use std::marker::PhantomData;
+
+use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use signal::Signal;
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants};
+use signal::signal_runtime::{SignalRuntimeRefSt, SignalRuntimeRefPl, AwaitToken};
+use signal::valued_signal::{ValuedSignal, GetValue};
+
+/// A signal whose value is the value of another signal transformed by a function.
+pub struct MapSignal<S, F, V> {
+    pub(crate) signal: S,
+    pub(crate) f: Arc<F>,
+    pub(crate) marker: PhantomData<fn() -> V>,
+}
+
+impl<S, F, V> Clone for MapSignal<S, F, V> where S: Clone {
+    fn clone(&self) -> Self {
+        MapSignal { signal: self.signal.clone(), f: self.f.clone(), marker: PhantomData }
+    }
+}
+
+impl<S, F, V> Signal for MapSignal<S, F, V>
+    where S: ValuedSignal, F: Fn(S::Stored) -> V + 'static, V: 'static
+{
+    type RuntimeRef = MapSignalRuntimeRef<S::RuntimeRef, F, S::Stored>;
+
+    fn runtime(&self) -> Self::RuntimeRef {
+        MapSignalRuntimeRef {
+            signal_runtime: self.signal.runtime(),
+            f: self.f.clone(),
+            marker: PhantomData,
+        }
+    }
+
+    fn label(&self) -> String {
+        format!("map({})", self.signal.label())
+    }
+}
+
+impl<S, F, V> ValuedSignal for MapSignal<S, F, V>
+    where S: ValuedSignal, F: Fn(S::Stored) -> V + 'static, V: 'static
+{
+    type Stored = V;
+    type SigType = S::SigType;
+}
+
+/// Runtime reference of a mapped signal, which refers to the runtime of the
+/// original signal.
+pub struct MapSignalRuntimeRef<R, F, U> {
+    signal_runtime: R,
+    f: Arc<F>,
+    marker: PhantomData<fn(U)>,
+}
+
+impl<R, F, U> Clone for MapSignalRuntimeRef<R, F, U> where R: Clone {
+    fn clone(&self) -> Self {
+        MapSignalRuntimeRef {
+            signal_runtime: self.signal_runtime.clone(),
+            f: self.f.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Rt, R, F, U> SignalRuntimeRefBase<Rt> for MapSignalRuntimeRef<R, F, U>
+    where Rt: Runtime, R: SignalRuntimeRefBase<Rt>, F: 'static, U: 'static
+{
+    fn is_emitted(&self) -> bool {
+        self.signal_runtime.is_emitted()
+    }
+
+    fn emissions(&self) -> EmissionInstants {
+        self.signal_runtime.emissions()
+    }
+
+    /// A derived signal is never emitted itself, so there is nothing to reset.
+    fn reset(&mut self) {}
+
+    fn execute_present_works(&mut self, runtime: &mut Rt) {
+        self.signal_runtime.execute_present_works(runtime);
+    }
+}
+
+impl<R, F, U, V> GetValue<V> for MapSignalRuntimeRef<R, F, U>
+    where R: GetValue<U>, F: Fn(U) -> V
+{
+    /// Returns the value of the original signal transformed by the function.
+    fn get_value(&self) -> V {
+        (self.f)(self.signal_runtime.get_value())
+    }
+}
+
+// Implements the traits for the single thread version of the library.
+
+impl<R, F, U> SignalRuntimeRefSt for MapSignalRuntimeRef<R, F, U>
+    where R: SignalRuntimeRefSt, F: 'static, U: 'static
+{
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationSt<()>
+    {
+        self.signal_runtime.on_signal_cancellable(runtime, token, c);
+    }
+
+    fn on_signal_present<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+        where C: ContinuationSt<()>
+    {
+        self.signal_runtime.on_signal_present(runtime, c);
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<R, F, U> SignalRuntimeRefPl for MapSignalRuntimeRef<R, F, U>
+    where R: SignalRuntimeRefPl, F: Send + Sync + 'static, U: 'static
+{
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationPl<()>
+    {
+        self.signal_runtime.on_signal_cancellable(runtime, token, c);
+    }
+
+    fn on_signal_present<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+        where C: ContinuationPl<()>
+    {
+        self.signal_runtime.on_signal_present(runtime, c);
+    }
+}
@@ -0,0 +1,193 @@
+use std::rc::Rc;
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+
+use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use signal::Signal;
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants};
+use signal::signal_runtime::{SignalRuntimeRefSt, SignalRuntimeRefPl, AwaitToken};
+
+/// A signal which is present when at least one of two signals is present.
+#[derive(Clone)]
+pub struct MergeSignal<S1, S2>(pub(crate) S1, pub(crate) S2);
+
+impl<S1, S2> Signal for MergeSignal<S1, S2> where S1: Signal, S2: Signal {
+    type RuntimeRef = MergeSignalRuntimeRef<S1::RuntimeRef, S2::RuntimeRef>;
+
+    fn runtime(&self) -> Self::RuntimeRef {
+        MergeSignalRuntimeRef(self.0.runtime(), self.1.runtime())
+    }
+
+    fn label(&self) -> String {
+        format!("merge({}, {})", self.0.label(), self.1.label())
+    }
+}
+
+/// Runtime reference of a merged signal, which refers to the runtimes of the
+/// two original signals.
+#[derive(Clone)]
+pub struct MergeSignalRuntimeRef<R1, R2>(R1, R2);
+
+impl<Rt, R1, R2> SignalRuntimeRefBase<Rt> for MergeSignalRuntimeRef<R1, R2>
+    where Rt: Runtime, R1: SignalRuntimeRefBase<Rt>, R2: SignalRuntimeRefBase<Rt>
+{
+    fn is_emitted(&self) -> bool {
+        self.0.is_emitted() || self.1.is_emitted()
+    }
+
+    fn emissions(&self) -> EmissionInstants {
+        self.0.emissions().merge(&self.1.emissions())
+    }
+
+    /// A derived signal is never emitted itself, so there is nothing to reset.
+    fn reset(&mut self) {}
+
+    fn execute_present_works(&mut self, runtime: &mut Rt) {
+        self.0.execute_present_works(runtime);
+        self.1.execute_present_works(runtime);
+    }
+}
+
+// Implements the traits for the single thread version of the library.
+
+/// Cancels the other awaits of a merged signal when one of the signals is emitted.
+/// Returns `false` if they were already cancelled, the continuation being then
+/// woken up by another signal.
+fn cancel_st(runtime: &mut SingleThreadRuntime, token: &AwaitToken) -> bool {
+    match token.cancel() {
+        Some(registered) => {
+            for _ in 0..registered {
+                runtime.cancel_await();
+            }
+            true
+        },
+        None => false,
+    }
+}
+
+impl<R1, R2> SignalRuntimeRefSt for MergeSignalRuntimeRef<R1, R2>
+    where R1: SignalRuntimeRefSt + Clone, R2: SignalRuntimeRefSt + Clone
+{
+    /// Calls `c` at the first cycle where one of the signals is present.
+    ///
+    /// The continuation awaits both signals with the same token, so the other await
+    /// is dropped when one of them is emitted.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationSt<()>
+    {
+        if self.is_emitted() {
+            if token.is_none_or(|token| cancel_st(runtime, token)) {
+                c.call(runtime, ());
+            }
+        } else {
+            let token = token.cloned().unwrap_or_default();
+            let (token1, token2) = (token.clone(), token.clone());
+            let c1 = Rc::new(Cell::new(Some(c)));
+            let c2 = c1.clone();
+            self.0.on_signal_cancellable(runtime, Some(&token), move |r: &mut SingleThreadRuntime, ()| {
+                if cancel_st(r, &token1) {
+                    c1.take().unwrap().call(r, ());
+                }
+            });
+            self.1.on_signal_cancellable(runtime, Some(&token), move |r: &mut SingleThreadRuntime, ()| {
+                if cancel_st(r, &token2) {
+                    c2.take().unwrap().call(r, ());
+                }
+            });
+        }
+    }
+
+    /// Calls `c` only if one of the signals is present during this cycle.
+    fn on_signal_present<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+        where C: ContinuationSt<()>
+    {
+        let c1 = Rc::new(Cell::new(Some(c)));
+        let c2 = c1.clone();
+        self.0.on_signal_present(runtime, move |r: &mut SingleThreadRuntime, ()| {
+            if let Some(c) = c1.take() {
+                c.call(r, ());
+            }
+        });
+        self.1.on_signal_present(runtime, move |r: &mut SingleThreadRuntime, ()| {
+            if let Some(c) = c2.take() {
+                c.call(r, ());
+            }
+        });
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+/// Cancels the other awaits of a merged signal when one of the signals is emitted.
+/// Returns `false` if they were already cancelled, the continuation being then
+/// woken up by another signal.
+fn cancel_pl(runtime: &mut ParallelRuntime, token: &AwaitToken) -> bool {
+    match token.cancel() {
+        Some(registered) => {
+            for _ in 0..registered {
+                runtime.cancel_await();
+            }
+            true
+        },
+        None => false,
+    }
+}
+
+impl<R1, R2> SignalRuntimeRefPl for MergeSignalRuntimeRef<R1, R2>
+    where R1: SignalRuntimeRefPl + Clone + Sync, R2: SignalRuntimeRefPl + Clone + Sync
+{
+    /// Calls `c` at the first cycle where one of the signals is present.
+    ///
+    /// The continuation awaits both signals with the same token, so the other await
+    /// is dropped when one of them is emitted. When the wakeups are deferred it always
+    /// awaits both, so that it is woken up the same way whatever the scheduling.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationPl<()>
+    {
+        if self.is_emitted() && !runtime.defers_wakeups() {
+            if token.is_none_or(|token| cancel_pl(runtime, token)) {
+                c.call(runtime, ());
+            }
+        } else {
+            let token = token.cloned().unwrap_or_default();
+            let (token1, token2) = (token.clone(), token.clone());
+            let c1 = Arc::new(Mutex::new(Some(c)));
+            let c2 = c1.clone();
+            self.0.on_signal_cancellable(runtime, Some(&token), move |r: &mut ParallelRuntime, ()| {
+                if cancel_pl(r, &token1) {
+                    let c = c1.lock().unwrap().take().unwrap();
+                    c.call(r, ());
+                }
+            });
+            self.1.on_signal_cancellable(runtime, Some(&token), move |r: &mut ParallelRuntime, ()| {
+                if cancel_pl(r, &token2) {
+                    let c = c2.lock().unwrap().take().unwrap();
+                    c.call(r, ());
+                }
+            });
+        }
+    }
+
+    /// Calls `c` only if one of the signals is present during this cycle.
+    fn on_signal_present<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+        where C: ContinuationPl<()>
+    {
+        let c1 = Arc::new(Mutex::new(Some(c)));
+        let c2 = c1.clone();
+        self.0.on_signal_present(runtime, move |r: &mut ParallelRuntime, ()| {
+            let c = c1.lock().unwrap().take();
+            if let Some(c) = c {
+                c.call(r, ());
+            }
+        });
+        self.1.on_signal_present(runtime, move |r: &mut ParallelRuntime, ()| {
+            let c = c2.lock().unwrap().take();
+            if let Some(c) = c {
+                c.call(r, ());
+            }
+        });
+    }
+}
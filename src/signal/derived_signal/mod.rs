@@ -0,0 +1,12 @@
+//! Signals derived from existing signals.
+//!
+//! A derived signal is never emitted directly: its status and its value are
+//! computed from the original signals, so it can be awaited or tested like any
+//! other signal.
+
+mod map;
+mod filter;
+mod merge;
+pub use self::map::{MapSignal, MapSignalRuntimeRef};
+pub use self::filter::{FilterSignal, FilterSignalRuntimeRef};
+pub use self::merge::{MergeSignal, MergeSignalRuntimeRef};
@@ -4,6 +4,15 @@
 //! `MpscSignal` and `SpmcSignal`.
 //! Except for `PureSignal`, all signals are emitted with some value and
 //! store some value which can be communicated.
+//! There is also `RegisterSignal`, a single-producer signal whose value is
+//! kept across instants until it is overwritten, and `SignalArray`, an array of
+//! signals sharing a single runtime that can be awaited all at once.
+//! The presence and the values of signals can be recorded as waveforms with
+//! the module `vcd`, and an execution can be recorded and replayed with the
+//! module `record`.
+//!
+//! Every signal gets a unique id at its creation and can be given a name with
+//! `named`, which are shown by `Debug` and in the panic messages.
 //!
 //! We should notice that the implementaion for `MpscSignal` and `SpmcSignal`
 //! may not be very satisfactory. We can imagine having some sender or
@@ -22,17 +31,48 @@
 //! behavior of the signal is only determined when it's associated with some
 //! particular runtime, but I didn't find a way to do this.
 
+// The methods giving access to the `SignalInfo` of a signal, the same for all the
+// signal types, which keep their runtime in `(self.0).runtime`.
+macro_rules! signal_info_methods {
+    () => {
+        /// Gives a name to the signal, which is shown in panic messages and debug output.
+        /// The name is shared with all the clones of the signal.
+        pub fn named(self, name: &str) -> Self {
+            self.info().set_name(name);
+            self
+        }
+
+        /// Returns the id and the name of the signal.
+        pub fn info(&self) -> &SignalInfo {
+            &(self.0).runtime.info
+        }
+    }
+}
+
 pub(crate) mod signal_runtime;
+mod signal_info;
+pub use self::signal_info::SignalInfo;
 
 mod await_immediate;
 mod present_else;
+mod pre_present;
+mod presence_expr;
+mod sustain;
 pub use self::await_immediate::AwaitImmediate;
 pub use self::present_else::PresentElse;
+pub use self::pre_present::PrePresent;
+pub use self::presence_expr::{PresenceExpr, PresenceExprSt, PresenceExprPl, ExprPresentElse};
+pub use self::presence_expr::{Presence, And, Or, Negation};
+pub use self::sustain::{Sustain, StopCondition, Forever, ForInstants, UntilSignal};
 
 pub mod pure_signal;
 pub mod valued_signal;
+pub mod derived_signal;
+pub mod vcd;
+pub mod record;
 pub use self::pure_signal::PureSignal;
 pub use self::valued_signal::ValuedSignal;
+pub use self::derived_signal::MergeSignal;
 
 pub mod parallel;
 pub use self::parallel::*;
@@ -54,11 +94,22 @@ pub trait Signal: Clone + 'static {
     
     /// Returns a reference to the signal's runtime.
     fn runtime(&self) -> Self::RuntimeRef;
+
+    /// Returns a short label of the signal, used to describe processes.
+    fn label(&self) -> String {
+        "signal".to_owned()
+    }
     
     /// Returns a process that waits for the emission of the signal.
     fn await_immediate(&self) -> AwaitImmediate<Self> where Self: Sized {
         AwaitImmediate(self.clone())
     }
+
+    /// Returns a process that tells immediately whether the signal was present
+    /// at the previous instant.
+    fn pre_present(&self) -> PrePresent<Self> where Self: Sized {
+        PrePresent(self.clone())
+    }
     
     /// Test the status of a signal `s`. If the signal is present, the process `p1`
     /// is executed instantaneously, otherwise `p2` is executed at the following instant.
@@ -71,4 +122,15 @@ pub trait Signal: Clone + 'static {
             else_proc: p2,
         }
     }
+
+    /// Returns the presence of the signal, which can be combined with the presence
+    /// of other signals using `&`, `|` and `!` and then tested with `present_else`.
+    fn presence(&self) -> Presence<Self> where Self: Sized {
+        Presence(self.clone())
+    }
+
+    /// Returns a signal which is present when `self` or `other` is present.
+    fn merge<S>(&self, other: S) -> MergeSignal<Self, S> where Self: Sized, S: Signal {
+        MergeSignal(self.clone(), other)
+    }
 }
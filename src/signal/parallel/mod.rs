@@ -4,7 +4,11 @@ mod pure_signal;
 mod mpmc_signal;
 mod mpsc_signal;
 mod spmc_signal;
+mod register_signal;
+mod signal_array;
 pub use self::pure_signal::PureSignalPl;
 pub use self::mpmc_signal::MpmcSignalPl;
 pub use self::mpsc_signal::MpscSignalPl;
 pub use self::spmc_signal::SpmcSignalPl;
+pub use self::register_signal::RegisterSignalPl;
+pub use self::signal_array::{SignalArrayPl, AwaitAnyImmediatePl};
@@ -1,11 +1,19 @@
-use std::sync::{Arc, Mutex};
+use std::fmt;
+use std::mem;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use crossbeam::sync::TreiberStack;
 
 use runtime::ParallelRuntime;
 use continuation::ContinuationPl;
-use signal::Signal;
-use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefPl};
-use signal::valued_signal::{ValuedSignal, MpSignal, CanEmit, GetValue};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefPl};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
+use signal::valued_signal::{ValuedSignal, MpSignal, CanEmit, GetValue, GetPreValue, GetHistory};
+use signal::valued_signal::{CanHandleEach, EachEmissionsPl, HandlerPl};
+use signal::valued_signal::{Gather, Monoid, FromMonoid, Strategy};
+use signal::valued_signal::{PartialValues, DeferredEmissions};
 
 /// A shared pointer to a signal runtime.
 pub struct MpmcSignalRuntimeRef<B, F> {
@@ -20,30 +28,77 @@ impl<B, F> Clone for MpmcSignalRuntimeRef<B, F> {
 
 /// Runtime for multi-producer, multi-consumer signals.
 struct MpmcSignalRuntime<B, F> {
+    info: SignalInfo,
     emitted: Mutex<bool>,
+    /// The last instant of emission plus one, which lets the following emissions of
+    /// the instant skip the locks taken by the first one.
+    emitted_instant: AtomicUsize,
     default_value: B,
-    gather: Mutex<F>,
+    gather: RwLock<F>,
+    finish: fn(&mut F, &mut B),
+    merge_partial: fn(&mut F, B, &mut B),
+    partials: PartialValues<B>,
+    deferred: DeferredEmissions<B, F>,
     value: Mutex<B>,
     last_value: Mutex<B>,
-    last_value_updated: Mutex<bool>,
-    await_works: TreiberStack<Box<ContinuationPl<()>>>,
+    emissions: Mutex<EmissionInstants>,
+    each: EachEmissionsPl,
+    history: Mutex<VecDeque<(usize, B)>>,
+    history_capacity: usize,
+    await_works: Mutex<AwaitWorks<Box<ContinuationPl<()>>>>,
     present_works: TreiberStack<Box<ContinuationPl<()>>>,
 }
 
 impl<B, F> MpmcSignalRuntime<B, F> where B: Clone {
     /// Returns a new instance of SignalRuntime.
-    fn new<A>(default: B, gather: F) -> Self where F: FnMut(A, &mut B) {
+    fn new<A>(default: B, gather: F, history_capacity: usize) -> Self
+        where F: Gather<A, B>
+    {
         MpmcSignalRuntime {
+            info: SignalInfo::new(),
             emitted: Mutex::new(false),
+            emitted_instant: AtomicUsize::new(0),
             default_value: default.clone(),
-            gather: Mutex::new(gather),
+            gather: RwLock::new(gather),
+            finish: <F as Gather<A, B>>::finish,
+            merge_partial: <F as Gather<A, B>>::merge_partial,
+            partials: PartialValues::new(),
+            deferred: DeferredEmissions::new(),
             value: Mutex::new(default.clone()),
             last_value: Mutex::new(default),
-            last_value_updated: Mutex::new(false),
-            await_works: TreiberStack::new(),
+            emissions: Mutex::new(EmissionInstants::default()),
+            each: EachEmissionsPl::new(),
+            history: Mutex::new(VecDeque::with_capacity(history_capacity)),
+            history_capacity,
+            await_works: Mutex::new(AwaitWorks::new()),
             present_works: TreiberStack::new(),
         }
     }
+
+    /// Lets the gather finish combining the values emitted during the instant.
+    /// The values gathered separately by the runtimes are combined first, as well as
+    /// the emissions kept by the runtimes in the deterministic mode.
+    fn finish_gather(&self) {
+        let mut value = self.value.lock().unwrap();
+        let gather = &mut *self.gather.write().unwrap();
+        for partial in self.partials.take_all() {
+            (self.merge_partial)(gather, partial, &mut value);
+        }
+        self.deferred.gather_all(gather, &mut value);
+        (self.finish)(gather, &mut value);
+    }
+
+    /// Records the value emitted at `instant` in the history if it is enabled,
+    /// dropping the oldest value when the history is full.
+    fn push_history(&self, instant: usize, value: B) {
+        if self.history_capacity > 0 {
+            let mut history = self.history.lock().unwrap();
+            if history.len() == self.history_capacity {
+                history.pop_front();
+            }
+            history.push_back((instant, value));
+        }
+    }
 }
 
 impl<B, F> SignalRuntimeRefBase<ParallelRuntime> for MpmcSignalRuntimeRef<B, F>
@@ -54,14 +109,21 @@ impl<B, F> SignalRuntimeRefBase<ParallelRuntime> for MpmcSignalRuntimeRef<B, F>
         *self.runtime.emitted.lock().unwrap()
     }
 
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.lock().unwrap()
+    }
+
     /// Resets the signal at the beginning of each instant.
     fn reset(&mut self) {
         let mut is_emitted = self.runtime.emitted.lock().unwrap();
         if *is_emitted {
             *is_emitted = false;
             drop(is_emitted);
-            *self.runtime.value.lock().unwrap() = self.runtime.default_value.clone();
-            *self.runtime.last_value_updated.lock().unwrap() = false;
+            self.runtime.finish_gather();
+            let value = mem::replace(
+                &mut *self.runtime.value.lock().unwrap(), self.runtime.default_value.clone());
+            self.runtime.push_history(self.emissions().last().unwrap(), value);
         }
     }
 
@@ -81,18 +143,19 @@ impl<B, F> SignalRuntimeRefPl for MpmcSignalRuntimeRef<B, F>
     where B: Clone + Send + Sync + 'static, F: Send + Sync + 'static
 {
     /// Calls `c` at the first cycle where the signal is present.
-    fn on_signal<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationPl<()>
     {
+        let c = runtime.on_wakeup(c);
         // Important: the mutex must be unlocked after the task is added
         // in the stack if this is the case. Similar for `on_signal_present`.
         let emitted_guard = self.runtime.emitted.lock().unwrap();
         if *emitted_guard {
             drop(emitted_guard);
-            c.call(runtime, ());
+            c.call_box(runtime, ());
         } else {
-            runtime.incr_await_counter();
-            self.runtime.await_works.push(Box::new(c));
+            self.runtime.await_works.lock().unwrap().register(runtime, &self.runtime.info, token, c);
         }
     }
     
@@ -100,12 +163,13 @@ impl<B, F> SignalRuntimeRefPl for MpmcSignalRuntimeRef<B, F>
     fn on_signal_present<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
         where C: ContinuationPl<()>
     {
+        let c = runtime.on_wakeup(c);
         let emitted_guard = self.runtime.emitted.lock().unwrap();
         if *emitted_guard {
             drop(emitted_guard);
-            c.call(runtime, ());
+            c.call_box(runtime, ());
         } else {
-            self.runtime.present_works.push(Box::new(c));
+            self.runtime.present_works.push(c);
         }
     }
 }
@@ -113,31 +177,50 @@ impl<B, F> SignalRuntimeRefPl for MpmcSignalRuntimeRef<B, F>
 impl<A, B, F> CanEmit<ParallelRuntime, A> for MpmcSignalRuntimeRef<B, F>
     where A: Send + Sync + 'static,
           B: Clone + Send + Sync + 'static,
-          F: FnMut(A, &mut B) + Send + Sync + 'static
+          F: Gather<A, B> + Send + Sync + 'static
 {
     fn emit(&mut self, runtime: &mut ParallelRuntime, emitted: A) {
-        *self.runtime.emitted.lock().unwrap() = true;
-        {
-            let mut v = self.runtime.value.lock().unwrap();
-            let gather = &mut *self.runtime.gather.lock().unwrap();
-            gather(emitted, &mut v);
+        let instant = runtime.current_instant();
+        if self.runtime.emitted_instant.swap(instant + 1, Ordering::SeqCst) != instant + 1 {
+            *self.runtime.emitted.lock().unwrap() = true;
+            self.runtime.emissions.lock().unwrap().record(instant);
+            runtime.emit_signal(Box::new(self.clone()));
+            let signal_ref = self.clone();
+            let update_last_value = move |_: &mut ParallelRuntime, ()| {
+                *signal_ref.runtime.last_value.lock().unwrap() = signal_ref.get_value();
+            };
+            runtime.on_end_of_instant(Box::new(update_last_value));
+        }
+        self.runtime.each.record(runtime, &self.runtime.info, &emitted);
+        let emitted = if F::is_associative() {
+            let (id, num_runtimes) = (runtime.id, runtime.num_threads_total);
+            let gather = self.runtime.gather.read().unwrap();
+            self.runtime.partials.with_partial(
+                id, num_runtimes, |partial| gather.gather_partial(partial, emitted))
+        } else {
+            Some(emitted)
+        };
+        if let Some(emitted) = emitted {
+            if runtime.is_deterministic() && !F::is_commutative() {
+                self.runtime.deferred.push(runtime.id, runtime.num_threads_total, emitted);
+            } else {
+                let mut v = self.runtime.value.lock().unwrap();
+                let gather = &mut *self.runtime.gather.write().unwrap();
+                gather.gather(emitted, &mut v);
+            }
         }
-        while let Some(c) = self.runtime.await_works.try_pop() {
-            runtime.decr_await_counter();
-            runtime.on_current_instant(c);
+        while let Some(c) = self.runtime.await_works.lock().unwrap().wake(runtime, &self.runtime.info) {
+            runtime.spawn(c);
         }
         self.execute_present_works(runtime);
-        runtime.emit_signal(Box::new(self.clone()));
-        let signal_ref = self.clone();
-        let update_last_value = move |_: &mut ParallelRuntime, ()| {
-            let mut updated = signal_ref.runtime.last_value_updated.lock().unwrap();
-            if !*updated {
-                *updated = true;
-                drop(updated);
-                *signal_ref.runtime.last_value.lock().unwrap() = signal_ref.get_value();
-            }
-        };
-        runtime.on_end_of_instant(Box::new(update_last_value));
+    }
+}
+
+impl<A, B, F> CanHandleEach<ParallelRuntime, A, HandlerPl<A>> for MpmcSignalRuntimeRef<B, F>
+    where A: Clone + Send + 'static, F: Gather<A, B>
+{
+    fn on_each(&mut self, runtime: &mut ParallelRuntime, handler: Box<HandlerPl<A>>) -> usize {
+        self.runtime.each.add_handler(runtime, &self.runtime.info, handler)
     }
 }
 
@@ -145,22 +228,50 @@ impl<B, F> GetValue<B> for MpmcSignalRuntimeRef<B, F> where B: Clone {
     /// Returns the value of the signal for the current instant.
     /// The returned value is cloned and can thus be used directly.
     fn get_value(&self) -> B {
+        self.runtime.finish_gather();
         self.runtime.value.lock().unwrap().clone()
     }
 }
 
+impl<B, F> GetPreValue for MpmcSignalRuntimeRef<B, F> where B: Clone {
+    type PreValue = B;
+
+    /// Returns the value of the signal at its last emission before the current instant,
+    /// or the default value if the signal has never been emitted.
+    fn get_pre_value(&self) -> B {
+        self.runtime.last_value.lock().unwrap().clone()
+    }
+}
+
+impl<B, F> GetHistory<B> for MpmcSignalRuntimeRef<B, F> where B: Clone {
+    /// Returns the values recorded in the history with the instants of their emission,
+    /// the oldest first.
+    fn get_history(&self) -> Vec<(usize, B)> {
+        self.runtime.history.lock().unwrap().iter().cloned().collect()
+    }
+}
+
 impl<B, F> MpmcSignalRuntimeRef<B, F>
     where B: Clone + Send + Sync + 'static, F: Send + Sync + 'static
 {
     /// Returns a new instance of SignalRuntimeRef.
-    fn new<A>(default: B, gather: F) -> Self where F: FnMut(A, &mut B) {
+    fn new<A>(default: B, gather: F, history_capacity: usize) -> Self
+        where F: Gather<A, B>
+    {
         MpmcSignalRuntimeRef {
-            runtime: Arc::new(MpmcSignalRuntime::new(default, gather)),
+            runtime: Arc::new(MpmcSignalRuntime::new(default, gather, history_capacity)),
         }
     }
 }
 
 /// A parallel multi-producer, multi-consumer signal.
+///
+/// The values emitted during an instant are gathered in the order in which the
+/// runtimes emit them. With an engine created by `ParallelRuntimeCollection::deterministic`
+/// they are rather gathered runtime after runtime, each runtime in the order of its
+/// emissions, when the value is read at the end of the instant. This order doesn't
+/// depend on the scheduling, and it is the order of the single thread runtime under
+/// the conditions given by `ParallelRuntimeCollection`.
 pub struct MpmcSignalPl<B, F>(MpmcSignalRuntimeRef<B, F>);
 
 impl<B, F> Clone for MpmcSignalPl<B, F> {
@@ -177,6 +288,10 @@ impl<B, F> Signal for MpmcSignalPl<B, F>
     fn runtime(&self) -> MpmcSignalRuntimeRef<B, F> {
         self.0.clone()
     }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
 }
 
 impl<B, F> ValuedSignal for MpmcSignalPl<B, F>
@@ -186,12 +301,27 @@ impl<B, F> ValuedSignal for MpmcSignalPl<B, F>
     type SigType = MpSignal;
 }
 
+impl<B, F> fmt::Debug for MpmcSignalPl<B, F> where B: Clone + Send + Sync + 'static, F: Send + Sync + 'static {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "MpmcSignalPl", self.0.is_emitted())
+    }
+}
+
 impl<B, F> MpmcSignalPl<B, F> where B: Clone + Send + Sync + 'static, F: Send + Sync + 'static {
+    signal_info_methods!();
+
     /// Creates a new mpmc signal.
     pub fn new<A>(default: B, gather: F) -> Self
         where A: Send + Sync + 'static, F: FnMut(A, &mut B)
     {
-        MpmcSignalPl(MpmcSignalRuntimeRef::new(default, gather))
+        MpmcSignalPl(MpmcSignalRuntimeRef::new(default, gather, 0))
+    }
+
+    /// Creates a new mpmc signal which keeps the values of its last `capacity` emissions.
+    pub fn with_history<A>(default: B, gather: F, capacity: usize) -> Self
+        where A: Send + Sync + 'static, F: FnMut(A, &mut B)
+    {
+        MpmcSignalPl(MpmcSignalRuntimeRef::new(default, gather, capacity))
     }
     
     /// Returns the last value associated to the signal when it was emitted.
@@ -214,4 +344,22 @@ impl MpmcSignalPl<(), ()> {
         }
         MpmcSignalPl::new(Vec::new(), gather)
     }
+
+    /// Creates a new mpmc signal whose values are combined with a monoid, starting from
+    /// its identity. Each runtime gathers its own emissions without locking the signal
+    /// and the partial results are combined at the end of the instant. The values are
+    /// combined in an arbitrary order.
+    pub fn from_monoid<B>() -> MpmcSignalPl<B, FromMonoid<B>>
+        where B: Monoid + Clone + Send + Sync + 'static
+    {
+        MpmcSignalPl(MpmcSignalRuntimeRef::new::<B>(B::identity(), FromMonoid::new(), 0))
+    }
+
+    /// Creates a new mpmc signal combining its values with one of the ready-made
+    /// strategies of the module `strategies`.
+    pub fn from_strategy<A, B, S>(strategy: S) -> MpmcSignalPl<B, S>
+        where A: Send + Sync + 'static, B: Clone + Send + Sync + 'static, S: Strategy<A, B> + Send + Sync + 'static
+    {
+        MpmcSignalPl(MpmcSignalRuntimeRef::new::<A>(S::initial(), strategy, 0))
+    }
 }
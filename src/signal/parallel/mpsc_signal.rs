@@ -1,11 +1,15 @@
+use std::fmt;
 use std::sync::{Arc, Mutex};
 use crossbeam::sync::TreiberStack;
 
 use runtime::ParallelRuntime;
 use continuation::ContinuationPl;
-use signal::Signal;
-use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefPl};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefPl};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
 use signal::valued_signal::{ValuedSignal, MpSignal, CanEmit, GetValue};
+use signal::valued_signal::{CanHandleEach, EachEmissionsPl, HandlerPl, DeferredEmissions};
+use signal::valued_signal::{Gather, Strategy};
 
 /// A shared pointer to a signal runtime.
 pub struct MpscSignalRuntimeRef<B, D, F> {
@@ -22,28 +26,50 @@ impl<B, D, F> Clone for MpscSignalRuntimeRef<B, D, F> {
 /// Since we're not always able to clone the default value, we need a function
 /// that produces the default value each time when it's called.
 struct MpscSignalRuntime<B, D, F> {
+    info: SignalInfo,
     emitted: Mutex<bool>,
     get_default: D,
     gather: Mutex<F>,
+    finish: fn(&mut F, &mut B),
+    deferred: DeferredEmissions<B, F>,
     value: Mutex<Option<B>>,
-    await_works: TreiberStack<Box<ContinuationPl<()>>>,
+    emissions: Mutex<EmissionInstants>,
+    each: EachEmissionsPl,
+    await_works: Mutex<AwaitWorks<Box<ContinuationPl<()>>>>,
     present_works: TreiberStack<Box<ContinuationPl<()>>>,
 }
 
 impl<B, D, F> MpscSignalRuntime<B, D, F> where D: Fn() -> B {
     /// Returns a new instance of SignalRuntime.
-    fn new<A>(get_default: D, gather: F) -> Self where F: FnMut(A, &mut B) {
+    fn new<A>(get_default: D, gather: F) -> Self where F: Gather<A, B> {
         MpscSignalRuntime {
+            info: SignalInfo::new(),
             emitted: Mutex::new(false),
             value: Mutex::new(Some(get_default())),
             get_default: get_default,
             gather: Mutex::new(gather),
-            await_works: TreiberStack::new(),
+            finish: <F as Gather<A, B>>::finish,
+            deferred: DeferredEmissions::new(),
+            emissions: Mutex::new(EmissionInstants::default()),
+            each: EachEmissionsPl::new(),
+            await_works: Mutex::new(AwaitWorks::new()),
             present_works: TreiberStack::new(),
         }
     }
 }
 
+impl<B, D, F> MpscSignalRuntime<B, D, F> {
+    /// Lets the gather finish combining the values emitted during the instant,
+    /// gathering first the emissions kept by the runtimes in the deterministic mode.
+    fn finish_gather(&self) {
+        let gather = &mut *self.gather.lock().unwrap();
+        if let Some(v) = self.value.lock().unwrap().as_mut() {
+            self.deferred.gather_all(gather, v);
+            (self.finish)(gather, v);
+        }
+    }
+}
+
 impl<B, D, F> SignalRuntimeRefBase<ParallelRuntime> for MpscSignalRuntimeRef<B, D, F>
     where B: 'static, D: Fn() -> B + 'static, F: 'static
 {
@@ -52,12 +78,20 @@ impl<B, D, F> SignalRuntimeRefBase<ParallelRuntime> for MpscSignalRuntimeRef<B,
         *self.runtime.emitted.lock().unwrap()
     }
 
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.lock().unwrap()
+    }
+
     /// Resets the signal at the beginning of each instant.
     fn reset(&mut self) {
         let mut is_emitted = self.runtime.emitted.lock().unwrap();
         if *is_emitted {
             *is_emitted = false;
             drop(is_emitted);
+            // Also clears the values that were not combined because the value
+            // of the signal was not read.
+            self.runtime.finish_gather();
             *self.runtime.value.lock().unwrap() = Some((self.runtime.get_default)());
         }
     }
@@ -81,18 +115,19 @@ impl<B, D, F> SignalRuntimeRefPl for MpscSignalRuntimeRef<B, D, F>
           F: Send + Sync + 'static,
 {
     /// Calls `c` at the first cycle where the signal is present.
-    fn on_signal<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationPl<()>
     {
+        let c = runtime.on_wakeup(c);
         // Important: the mutex must be unlocked after the task is added
         // in the stack if this is the case. Similar for `on_signal_present`.
         let emitted_guard = self.runtime.emitted.lock().unwrap();
         if *emitted_guard {
             drop(emitted_guard);
-            c.call(runtime, ());
+            c.call_box(runtime, ());
         } else {
-            runtime.incr_await_counter();
-            self.runtime.await_works.push(Box::new(c));
+            self.runtime.await_works.lock().unwrap().register(runtime, &self.runtime.info, token, c);
         }
     }
     
@@ -100,46 +135,62 @@ impl<B, D, F> SignalRuntimeRefPl for MpscSignalRuntimeRef<B, D, F>
     fn on_signal_present<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
         where C: ContinuationPl<()>
     {
+        let c = runtime.on_wakeup(c);
         let emitted_guard = self.runtime.emitted.lock().unwrap();
         if *emitted_guard {
             drop(emitted_guard);
-            c.call(runtime, ());
+            c.call_box(runtime, ());
         } else {
-            self.runtime.present_works.push(Box::new(c));
+            self.runtime.present_works.push(c);
         }
     }
 }
 
 impl<A, B, D, F> CanEmit<ParallelRuntime, A> for MpscSignalRuntimeRef<B, D, F>
-    where A: 'static,
+    where A: Send + 'static,
           B: Send + Sync + 'static,
           D: Fn() -> B + Send + Sync + 'static,
-          F: FnMut(A, &mut B) + Send + Sync + 'static,
+          F: Gather<A, B> + Send + Sync + 'static,
 {
     fn emit(&mut self, runtime: &mut ParallelRuntime, emitted: A) {
         *self.runtime.emitted.lock().unwrap() = true;
-        {
+        self.runtime.each.record(runtime, &self.runtime.info, &emitted);
+        if runtime.is_deterministic() && !F::is_commutative() {
+            self.runtime.deferred.push(runtime.id, runtime.num_threads_total, emitted);
+        } else {
             let gather = &mut *self.runtime.gather.lock().unwrap();
             match self.runtime.value.lock().unwrap().as_mut() {
-                Some(v) => gather(emitted, v),
-                None => assert!(false),
+                Some(v) => gather.gather(emitted, v),
+                None => panic!(
+                    "Trying to emit the mpsc {} after its value was taken inside an instant.",
+                    self.runtime.info),
             }
         }
-        while let Some(c) = self.runtime.await_works.try_pop() {
-            runtime.decr_await_counter();
-            runtime.on_current_instant(c);
+        while let Some(c) = self.runtime.await_works.lock().unwrap().wake(runtime, &self.runtime.info) {
+            runtime.spawn(c);
         }
         self.execute_present_works(runtime);
+        self.runtime.emissions.lock().unwrap().record(runtime.current_instant());
         runtime.emit_signal(Box::new(self.clone()));
     }
 }
 
+impl<A, B, D, F> CanHandleEach<ParallelRuntime, A, HandlerPl<A>> for MpscSignalRuntimeRef<B, D, F>
+    where A: Clone + Send + 'static, F: Gather<A, B>
+{
+    fn on_each(&mut self, runtime: &mut ParallelRuntime, handler: Box<HandlerPl<A>>) -> usize {
+        self.runtime.each.add_handler(runtime, &self.runtime.info, handler)
+    }
+}
+
 impl<B, D, F> GetValue<B> for MpscSignalRuntimeRef<B, D, F> {
     /// Returns the value of the signal for the current instant.
     /// This function can only be called once at each instant.
     fn get_value(&self) -> B {
-        self.runtime.value.lock().unwrap().take().expect(
-            "Trying to get the value of a mpsc signal more than once inside an instant.")
+        self.runtime.finish_gather();
+        self.runtime.value.lock().unwrap().take().unwrap_or_else(|| panic!(
+            "Trying to get the value of the mpsc {} more than once inside an instant.",
+            self.runtime.info))
     }
 }
 
@@ -149,7 +200,7 @@ impl<B, D, F> MpscSignalRuntimeRef<B, D, F>
           F: Send + Sync + 'static,
 {
     /// Returns a new instance of SignalRuntimeRef.
-    fn new<A>(get_default: D, gather: F) -> Self where F: FnMut(A, &mut B) {
+    fn new<A>(get_default: D, gather: F) -> Self where F: Gather<A, B> {
         MpscSignalRuntimeRef {
             runtime: Arc::new(MpscSignalRuntime::new(get_default, gather)),
         }
@@ -157,6 +208,9 @@ impl<B, D, F> MpscSignalRuntimeRef<B, D, F>
 }
 
 /// A parallel multi-producer, single-consumer signal.
+///
+/// As for `MpmcSignalPl`, the values are gathered in an order which doesn't depend
+/// on the scheduling only with the deterministic engine.
 pub struct MpscSignalPl<B, D, F>(MpscSignalRuntimeRef<B, D, F>);
 
 impl<B, D, F> Clone for MpscSignalPl<B, D, F> {
@@ -175,6 +229,10 @@ impl<B, D, F> Signal for MpscSignalPl<B, D, F>
     fn runtime(&self) -> MpscSignalRuntimeRef<B, D, F> {
         self.0.clone()
     }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
 }
 
 impl<B, D, F> ValuedSignal for MpscSignalPl<B, D, F>
@@ -186,11 +244,23 @@ impl<B, D, F> ValuedSignal for MpscSignalPl<B, D, F>
     type SigType = MpSignal;
 }
     
+impl<B, D, F> fmt::Debug for MpscSignalPl<B, D, F>
+    where B: Send + Sync + 'static,
+          D: Fn() -> B + Send + Sync + 'static,
+          F: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "MpscSignalPl", self.0.is_emitted())
+    }
+}
+
 impl<B, D, F> MpscSignalPl<B, D, F>
     where B: Send + Sync + 'static,
           D: Fn() -> B + Send + Sync + 'static,
           F: Send + Sync + 'static,
 {
+    signal_info_methods!();
+
     /// Creates a new mpmc signal.
     pub fn new<A>(get_default: D, gather: F) -> Self where A: 'static, F: FnMut(A, &mut B) {
         MpscSignalPl(MpscSignalRuntimeRef::new(get_default, gather))
@@ -208,4 +278,14 @@ impl MpscSignalPl<(), (), ()> {
         }
         MpscSignalPl::new(Vec::new, gather)
     }
+
+    /// Creates a new mpsc signal combining its values with one of the ready-made
+    /// strategies of the module `strategies`. Unlike `MpmcSignalPl`, the signal
+    /// gathers each emission into its value under a lock and never uses the partial
+    /// values of the associative strategies.
+    pub fn from_strategy<A, B, S>(strategy: S) -> MpscSignalPl<B, fn() -> B, S>
+        where A: Send + Sync + 'static, B: Send + Sync + 'static, S: Strategy<A, B> + Send + Sync + 'static
+    {
+        MpscSignalPl(MpscSignalRuntimeRef::new::<A>(S::initial, strategy))
+    }
 }
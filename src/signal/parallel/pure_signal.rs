@@ -1,11 +1,13 @@
 use std::sync::{Arc, Mutex};
+use std::fmt;
 use crossbeam::sync::TreiberStack;
 
 use runtime::ParallelRuntime;
 use continuation::ContinuationPl;
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
-use signal::Signal;
-use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefPl};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefPl};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
 use signal::pure_signal::{PureSignal, Emit, TryEmit};
 
 /// A shared pointer to a signal runtime.
@@ -16,8 +18,10 @@ pub struct PureSignalRuntimeRef {
 
 /// Runtime for pure signals.
 struct PureSignalRuntime {
+    info: SignalInfo,
     emitted: Mutex<bool>,
-    await_works: TreiberStack<Box<ContinuationPl<()>>>,
+    emissions: Mutex<EmissionInstants>,
+    await_works: Mutex<AwaitWorks<Box<ContinuationPl<()>>>>,
     present_works: TreiberStack<Box<ContinuationPl<()>>>,
 }
 
@@ -25,8 +29,10 @@ impl PureSignalRuntime {
     /// Returns a new instance of SignalRuntime.
     fn new() -> Self {
         PureSignalRuntime {
+            info: SignalInfo::new(),
             emitted: Mutex::new(false),
-            await_works: TreiberStack::new(),
+            emissions: Mutex::new(EmissionInstants::default()),
+            await_works: Mutex::new(AwaitWorks::new()),
             present_works: TreiberStack::new(),
         }
     }
@@ -38,6 +44,11 @@ impl SignalRuntimeRefBase<ParallelRuntime> for PureSignalRuntimeRef {
         *self.runtime.emitted.lock().unwrap()
     }
 
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.lock().unwrap()
+    }
+
     /// Resets the signal at the beginning of each instant.
     fn reset(&mut self) {
         *self.runtime.emitted.lock().unwrap() = false;
@@ -58,18 +69,19 @@ impl SignalRuntimeRefBase<ParallelRuntime> for PureSignalRuntimeRef {
 
 impl SignalRuntimeRefPl for PureSignalRuntimeRef {
     /// Calls `c` at the first cycle where the signal is present.
-    fn on_signal<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationPl<()>
     {
+        let c = runtime.on_wakeup(c);
         // Important: the mutex must be unlocked after the task is added
         // in the stack if this is the case. Similar for `on_signal_present`.
         let emitted_guard = self.runtime.emitted.lock().unwrap();
         if *emitted_guard {
             drop(emitted_guard);
-            c.call(runtime, ());
+            c.call_box(runtime, ());
         } else {
-            runtime.incr_await_counter();
-            self.runtime.await_works.push(Box::new(c));
+            self.runtime.await_works.lock().unwrap().register(runtime, &self.runtime.info, token, c);
         }
     }
     
@@ -77,13 +89,14 @@ impl SignalRuntimeRefPl for PureSignalRuntimeRef {
     fn on_signal_present<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
         where C: ContinuationPl<()>
     {
+        let c = runtime.on_wakeup(c);
         let emitted_guard = self.runtime.emitted.lock().unwrap();
         if *emitted_guard {
             // Without explicit unlock we get some deadlock here.
             drop(emitted_guard);
-            c.call(runtime, ());
+            c.call_box(runtime, ());
         } else {
-            self.runtime.present_works.push(Box::new(c));
+            self.runtime.present_works.push(c);
         }
     }
 }
@@ -97,11 +110,11 @@ impl PureSignalRuntimeRef {
     /// Sets the signal as emitted for the current instant.
     fn emit(&mut self, runtime: &mut ParallelRuntime) {
         *self.runtime.emitted.lock().unwrap() = true;
-        while let Some(c) = self.runtime.await_works.try_pop() {
-            runtime.decr_await_counter();
-            runtime.on_current_instant(c);
+        while let Some(c) = self.runtime.await_works.lock().unwrap().wake(runtime, &self.runtime.info) {
+            runtime.spawn(c);
         }
         self.execute_present_works(runtime);
+        self.runtime.emissions.lock().unwrap().record(runtime.current_instant());
         runtime.emit_signal(Box::new(self.clone()));
     }
 
@@ -115,11 +128,11 @@ impl PureSignalRuntimeRef {
             }
             *emitted_guard = true;
         }
-        while let Some(c) = self.runtime.await_works.try_pop() {
-            runtime.decr_await_counter();
-            runtime.on_current_instant(c);
+        while let Some(c) = self.runtime.await_works.lock().unwrap().wake(runtime, &self.runtime.info) {
+            runtime.spawn(c);
         }
         self.execute_present_works(runtime);
+        self.runtime.emissions.lock().unwrap().record(runtime.current_instant());
         runtime.emit_signal(Box::new(self.clone()));
         return true;
     }
@@ -135,6 +148,10 @@ impl Signal for PureSignalPl {
     fn runtime(&self) -> PureSignalRuntimeRef {
         self.0.clone()
     }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
 }
 
 impl PureSignal for PureSignalPl {
@@ -144,6 +161,16 @@ impl PureSignal for PureSignalPl {
     }
 }
 
+impl fmt::Debug for PureSignalPl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "PureSignalPl", self.0.is_emitted())
+    }
+}
+
+impl PureSignalPl {
+    signal_info_methods!();
+}
+
 /* Emit */
 
 impl ConstraintOnValue for Emit<PureSignalPl> {
@@ -0,0 +1,216 @@
+use std::sync::{Arc, Mutex};
+use std::fmt;
+use crossbeam::sync::TreiberStack;
+
+use runtime::ParallelRuntime;
+use continuation::ContinuationPl;
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefPl};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
+use signal::valued_signal::{ValuedSignal, SpSignal, CanEmit, GetValue, GetPreValue, PreValue};
+
+/// A shared pointer to a signal runtime.
+pub struct RegisterSignalRuntimeRef<V> {
+    runtime: Arc<RegisterSignalRuntime<V>>,
+}
+
+impl<V> Clone for RegisterSignalRuntimeRef<V> {
+    fn clone(&self) -> Self { 
+        RegisterSignalRuntimeRef { runtime: self.runtime.clone() }
+    }
+}
+
+/// Runtime for register signals.
+struct RegisterSignalRuntime<V> {
+    info: SignalInfo,
+    value: Mutex<Option<V>>,
+    stored: Mutex<V>,
+    emissions: Mutex<EmissionInstants>,
+    await_works: Mutex<AwaitWorks<Box<ContinuationPl<()>>>>,
+    present_works: TreiberStack<Box<ContinuationPl<()>>>,
+}
+
+impl<V> RegisterSignalRuntime<V> where V: Clone {
+    /// Returns a new instance of SignalRuntime.
+    fn new(initial: V) -> Self {
+        RegisterSignalRuntime {
+            info: SignalInfo::new(),
+            value: Mutex::new(None),
+            stored: Mutex::new(initial),
+            emissions: Mutex::new(EmissionInstants::default()),
+            await_works: Mutex::new(AwaitWorks::new()),
+            present_works: TreiberStack::new(),
+        }
+    }
+}
+
+impl<V> SignalRuntimeRefBase<ParallelRuntime> for RegisterSignalRuntimeRef<V>
+    where V: Clone + Send + Sync + 'static
+{
+    /// Returns a bool to indicate if the signal was emitted or not on the current instant.
+    fn is_emitted(&self) -> bool {
+        self.runtime.value.lock().unwrap().is_some()
+    }
+
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.lock().unwrap()
+    }
+
+    /// Resets the signal at the beginning of each instant. The value emitted during
+    /// the instant is kept in the register.
+    fn reset(&mut self) {
+        let value = self.runtime.value.lock().unwrap().take();
+        if let Some(value) = value {
+            *self.runtime.stored.lock().unwrap() = value;
+        }
+    }
+
+    /// Exececutes all the continuations found in the vector `self.present_works`.
+    fn execute_present_works(&mut self, runtime: &mut ParallelRuntime) {
+        while let Some(c) = self.runtime.present_works.try_pop() {
+            c.call_box(runtime, ());
+        }
+    }
+}
+
+
+impl<V> SignalRuntimeRefPl for RegisterSignalRuntimeRef<V>
+    where V: Clone + Send + Sync + 'static
+{
+    /// Calls `c` at the first cycle where the signal is present.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationPl<()>
+    {
+        let c = runtime.on_wakeup(c);
+        // The mutex must be unlocked after the task is added in the stack if
+        // this is the case, as for the other signals.
+        let value_guard = self.runtime.value.lock().unwrap();
+        if value_guard.is_some() {
+            drop(value_guard);
+            c.call_box(runtime, ());
+        } else {
+            self.runtime.await_works.lock().unwrap().register(runtime, &self.runtime.info, token, c);
+        }
+    }
+
+    /// Calls `c` only if the signal is present during this cycle.
+    fn on_signal_present<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+        where C: ContinuationPl<()>
+    {
+        let c = runtime.on_wakeup(c);
+        let value_guard = self.runtime.value.lock().unwrap();
+        if value_guard.is_some() {
+            drop(value_guard);
+            c.call_box(runtime, ());
+        } else {
+            self.runtime.present_works.push(c);
+        }
+    }
+}
+
+impl<V> CanEmit<ParallelRuntime, V> for RegisterSignalRuntimeRef<V>
+    where V: Clone + Send + Sync + 'static
+{
+    fn emit(&mut self, runtime: &mut ParallelRuntime, emitted: V) {
+        {
+            let mut value_guard = self.runtime.value.lock().unwrap();
+            if value_guard.is_some() {
+                panic!("Multiple emissions of the single-producer {} inside an instant.",
+                       self.runtime.info);
+            }
+            *value_guard = Some(emitted);
+        }
+        while let Some(c) = self.runtime.await_works.lock().unwrap().wake(runtime, &self.runtime.info) {
+            runtime.spawn(c);
+        }
+        self.execute_present_works(runtime);
+        self.runtime.emissions.lock().unwrap().record(runtime.current_instant());
+        runtime.emit_signal(Box::new(self.clone()));
+    }
+}
+
+impl<V> GetValue<V> for RegisterSignalRuntimeRef<V> where V: Clone {
+    /// Returns the value of the signal for the current instant.
+    /// The returned value is cloned and can thus be used directly.
+    fn get_value(&self) -> V {
+        self.runtime.value.lock().unwrap().clone().unwrap_or_else(
+            || panic!("Trying to get the value of the {} which is not emitted.", self.runtime.info))
+    }
+}
+
+impl<V> GetPreValue for RegisterSignalRuntimeRef<V> where V: Clone {
+    type PreValue = V;
+
+    /// Returns the value kept in the register, that is the value of the signal at
+    /// its last emission before the current instant or the initial value.
+    fn get_pre_value(&self) -> V {
+        self.runtime.stored.lock().unwrap().clone()
+    }
+}
+
+impl<V> RegisterSignalRuntimeRef<V> where V: Clone + Send + Sync + 'static {
+    /// Returns a new instance of SignalRuntimeRef.
+    fn new(initial: V) -> Self {
+        RegisterSignalRuntimeRef { runtime: Arc::new(RegisterSignalRuntime::new(initial)) }
+    }
+}
+
+/// A parallel register signal.
+///
+/// This is a single-producer signal whose value persists across instants until
+/// it is overwritten by another emission, while its presence is still reset at
+/// each instant. The value of an emission is kept in the register at the end of
+/// the instant, so all the processes reading the register during an instant see
+/// the same value.
+pub struct RegisterSignalPl<V>(RegisterSignalRuntimeRef<V>);
+
+impl<V> Clone for RegisterSignalPl<V> {
+    fn clone(&self) -> Self {
+        RegisterSignalPl(self.0.clone())
+    }
+}
+
+impl<V> Signal for RegisterSignalPl<V> where V: Clone + Send + Sync + 'static {
+    type RuntimeRef = RegisterSignalRuntimeRef<V>;
+    
+    fn runtime(&self) -> RegisterSignalRuntimeRef<V> {
+        self.0.clone()
+    }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
+}
+
+impl<V> ValuedSignal for RegisterSignalPl<V> where V: Clone + Send + Sync + 'static {
+    type Stored = V;
+    type SigType = SpSignal;
+}
+
+impl<V> fmt::Debug for RegisterSignalPl<V> where V: Clone + Send + Sync + 'static {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "RegisterSignalPl", self.0.is_emitted())
+    }
+}
+
+impl<V> RegisterSignalPl<V> where V: Clone + Send + Sync + 'static {
+    signal_info_methods!();
+
+    /// Creates a new register signal holding `initial` until its first emission.
+    pub fn new(initial: V) -> Self {
+        RegisterSignalPl(RegisterSignalRuntimeRef::new(initial))
+    }
+
+    /// Returns a process that gets immediately the value kept in the register.
+    /// This is the same as `pre_value`.
+    pub fn read(&self) -> PreValue<Self> {
+        self.pre_value()
+    }
+
+    /// Returns the value kept in the register.
+    pub fn value(&self) -> V {
+        self.0.get_pre_value()
+    }
+}
@@ -0,0 +1,274 @@
+use std::sync::{Arc, Mutex};
+use std::fmt;
+use crossbeam::sync::TreiberStack;
+
+use runtime::ParallelRuntime;
+use continuation::ContinuationPl;
+use process::{Process, ProcessMut, ProcessPl, ProcessMutPl, ConstraintOnValue, Description};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefPl};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
+use signal::valued_signal::{ValuedSignal, MpSignal, CanEmit, GetValue, EmitValue, AwaitValue};
+
+/// A shared pointer to a signal runtime.
+pub struct SignalArrayRuntimeRef<V> {
+    runtime: Arc<SignalArrayRuntime<V>>,
+}
+
+impl<V> Clone for SignalArrayRuntimeRef<V> {
+    fn clone(&self) -> Self { 
+        SignalArrayRuntimeRef { runtime: self.runtime.clone() }
+    }
+}
+
+/// Runtime shared by all the signals of an array.
+struct SignalArrayRuntime<V> {
+    info: SignalInfo,
+    /// The number of signals, which is also the length of `values`.
+    len: usize,
+    values: Mutex<Vec<Option<V>>>,
+    present: Mutex<Vec<usize>>,
+    emissions: Mutex<EmissionInstants>,
+    await_works: Mutex<AwaitWorks<Box<ContinuationPl<()>>>>,
+    present_works: TreiberStack<Box<ContinuationPl<()>>>,
+}
+
+impl<V> SignalArrayRuntime<V> {
+    /// Returns a new instance of SignalRuntime.
+    fn new(n: usize) -> Self {
+        SignalArrayRuntime {
+            info: SignalInfo::new(),
+            len: n,
+            values: Mutex::new((0..n).map(|_| None).collect()),
+            present: Mutex::new(Vec::new()),
+            emissions: Mutex::new(EmissionInstants::default()),
+            await_works: Mutex::new(AwaitWorks::new()),
+            present_works: TreiberStack::new(),
+        }
+    }
+}
+
+impl<V> SignalRuntimeRefBase<ParallelRuntime> for SignalArrayRuntimeRef<V>
+    where V: Clone + Send + Sync + 'static
+{
+    /// Returns a bool to indicate if some signal of the array was emitted or not
+    /// on the current instant.
+    fn is_emitted(&self) -> bool {
+        !self.runtime.present.lock().unwrap().is_empty()
+    }
+
+    /// Returns the last instants at which some signal of the array was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.lock().unwrap()
+    }
+
+    /// Resets the signals that were emitted during the instant.
+    fn reset(&mut self) {
+        let mut values = self.runtime.values.lock().unwrap();
+        for i in self.runtime.present.lock().unwrap().drain(..) {
+            values[i] = None;
+        }
+    }
+
+    /// Exececutes all the continuations found in the vector `self.present_works`.
+    fn execute_present_works(&mut self, runtime: &mut ParallelRuntime) {
+        while let Some(c) = self.runtime.present_works.try_pop() {
+            c.call_box(runtime, ());
+        }
+    }
+}
+
+
+impl<V> SignalRuntimeRefPl for SignalArrayRuntimeRef<V> where V: Clone + Send + Sync + 'static {
+    /// Calls `c` at the first cycle where some signal of the array is present.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationPl<()>
+    {
+        let c = runtime.on_wakeup(c);
+        // The mutex must be unlocked after the task is added in the stack if
+        // this is the case, as for the other signals.
+        let present_guard = self.runtime.present.lock().unwrap();
+        if !present_guard.is_empty() {
+            drop(present_guard);
+            c.call_box(runtime, ());
+        } else {
+            self.runtime.await_works.lock().unwrap().register(runtime, &self.runtime.info, token, c);
+        }
+    }
+
+    /// Calls `c` only if some signal of the array is present during this cycle.
+    fn on_signal_present<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+        where C: ContinuationPl<()>
+    {
+        let c = runtime.on_wakeup(c);
+        let present_guard = self.runtime.present.lock().unwrap();
+        if !present_guard.is_empty() {
+            drop(present_guard);
+            c.call_box(runtime, ());
+        } else {
+            self.runtime.present_works.push(c);
+        }
+    }
+}
+
+impl<V> CanEmit<ParallelRuntime, (usize, V)> for SignalArrayRuntimeRef<V>
+    where V: Clone + Send + Sync + 'static
+{
+    fn emit(&mut self, runtime: &mut ParallelRuntime, (i, emitted): (usize, V)) {
+        let first_emission = {
+            let mut values = self.runtime.values.lock().unwrap();
+            assert!(i < self.runtime.len, "Emission at index {} out of the bounds of the array {}.",
+                    i, self.runtime.info);
+            if values[i].is_some() {
+                panic!("Multiple emissions at index {} of the array {} inside an instant.",
+                       i, self.runtime.info);
+            }
+            values[i] = Some(emitted);
+            let mut present = self.runtime.present.lock().unwrap();
+            present.push(i);
+            present.len() == 1
+        };
+        while let Some(c) = self.runtime.await_works.lock().unwrap().wake(runtime, &self.runtime.info) {
+            runtime.spawn(c);
+        }
+        self.execute_present_works(runtime);
+        self.runtime.emissions.lock().unwrap().record(runtime.current_instant());
+        if first_emission {
+            runtime.emit_signal(Box::new(self.clone()));
+        }
+    }
+}
+
+impl<V> GetValue<Vec<(usize, V)>> for SignalArrayRuntimeRef<V> where V: Clone {
+    /// Returns the indices of the signals emitted during the current instant with their
+    /// values, in increasing order of the indices.
+    fn get_value(&self) -> Vec<(usize, V)> {
+        let values = self.runtime.values.lock().unwrap();
+        let mut present = self.runtime.present.lock().unwrap().clone();
+        present.sort();
+        present.into_iter().map(|i| (i, values[i].clone().unwrap())).collect()
+    }
+}
+
+impl<V> SignalArrayRuntimeRef<V> {
+    /// Returns a new instance of SignalRuntimeRef.
+    fn new(n: usize) -> Self {
+        SignalArrayRuntimeRef { runtime: Arc::new(SignalArrayRuntime::new(n)) }
+    }
+}
+
+/// A parallel array of single-producer signals sharing a single runtime.
+///
+/// The array is considered as a multi-producer signal which is present when one
+/// of its signals is, and whose value is the list of the present signals with
+/// their values.
+pub struct SignalArrayPl<V>(SignalArrayRuntimeRef<V>);
+
+impl<V> Clone for SignalArrayPl<V> {
+    fn clone(&self) -> Self {
+        SignalArrayPl(self.0.clone())
+    }
+}
+
+impl<V> Signal for SignalArrayPl<V> where V: Clone + Send + Sync + 'static {
+    type RuntimeRef = SignalArrayRuntimeRef<V>;
+    
+    fn runtime(&self) -> SignalArrayRuntimeRef<V> {
+        self.0.clone()
+    }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
+}
+
+impl<V> ValuedSignal for SignalArrayPl<V> where V: Clone + Send + Sync + 'static {
+    type Stored = Vec<(usize, V)>;
+    type SigType = MpSignal;
+}
+
+impl<V> fmt::Debug for SignalArrayPl<V> where V: Clone + Send + Sync + 'static {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "SignalArrayPl", self.0.is_emitted())
+    }
+}
+
+impl<V> SignalArrayPl<V> where V: Clone + Send + Sync + 'static {
+    signal_info_methods!();
+
+    /// Creates a new array of `n` signals.
+    pub fn new(n: usize) -> Self {
+        SignalArrayPl(SignalArrayRuntimeRef::new(n))
+    }
+
+    /// Returns the number of signals in the array.
+    pub fn len(&self) -> usize {
+        self.0.runtime.len
+    }
+
+    /// Returns `true` if the array contains no signal.
+    pub fn is_empty(&self) -> bool {
+        self.0.runtime.len == 0
+    }
+
+    /// Returns a process that emits the signal of index `i` with value `emitted`.
+    pub fn emit_at(&self, i: usize, emitted: V) -> EmitValue<Self, (usize, V)> {
+        self.emit((i, emitted))
+    }
+
+    /// Waits for some signals of the array to be emitted and gets the indices of
+    /// all the signals present at this instant with their values. As for the
+    /// other multi-producer signals, the process terminates at the following instant.
+    pub fn await_any(&self) -> AwaitValue<Self, MpSignal> {
+        self.await()
+    }
+
+    /// Same as `await_any`, but terminates at the instant of the first emission.
+    /// Only the signals emitted before the process is woken up are then returned.
+    pub fn await_any_immediate(&self) -> AwaitAnyImmediatePl<V> {
+        AwaitAnyImmediatePl(self.clone())
+    }
+}
+
+/// Process awaiting some signals of an array to be emitted, which gets the signals
+/// present at the instant they are emitted.
+pub struct AwaitAnyImmediatePl<V>(SignalArrayPl<V>);
+
+impl<V> Process for AwaitAnyImmediatePl<V> where V: Clone + Send + Sync + 'static {
+    type Value = Vec<(usize, V)>;
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("await_any_immediate({})", self.0.label()))
+    }
+}
+
+impl<V> ProcessMut for AwaitAnyImmediatePl<V> where V: Clone + Send + Sync + 'static {}
+
+impl<V> ConstraintOnValue for AwaitAnyImmediatePl<V> where V: Clone + Send + Sync + 'static {
+    type T = Vec<(usize, V)>;
+}
+
+impl<V> ProcessPl for AwaitAnyImmediatePl<V> where V: Clone + Send + Sync + 'static {
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        let signal_runtime = self.0.runtime();
+        self.0.runtime().on_signal(
+            runtime,
+            move |r: &mut ParallelRuntime, ()| next.call(r, signal_runtime.get_value()));
+    }
+}
+
+impl<V> ProcessMutPl for AwaitAnyImmediatePl<V> where V: Clone + Send + Sync + 'static {
+    fn call_mut<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        let signal_runtime = self.0.runtime();
+        let mut signal_runtime2 = self.0.runtime();
+        signal_runtime2.on_signal(
+            runtime,
+            move |r: &mut ParallelRuntime, ()|
+                next.call(r, (self, signal_runtime.get_value())));
+    }
+}
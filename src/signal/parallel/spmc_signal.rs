@@ -1,11 +1,14 @@
+use std::collections::VecDeque;
+use std::fmt;
 use std::sync::{Arc, Mutex};
 use crossbeam::sync::TreiberStack;
 
 use runtime::ParallelRuntime;
 use continuation::ContinuationPl;
-use signal::Signal;
-use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefPl};
-use signal::valued_signal::{ValuedSignal, SpSignal, CanEmit, GetValue, CanTryEmit, TryEmitValue};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefPl};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
+use signal::valued_signal::{ValuedSignal, SpSignal, CanEmit, GetValue, GetPreValue, GetHistory, CanTryEmit, TryEmitValue};
 
 /// A shared pointer to a signal runtime.
 pub struct SpmcSignalRuntimeRef<V> {
@@ -20,24 +23,44 @@ impl<V> Clone for SpmcSignalRuntimeRef<V> {
 
 /// Runtime for multi-producer, multi-consumer signals.
 struct SpmcSignalRuntime<V> {
+    info: SignalInfo,
     value: Mutex<Option<V>>,
     last_value: Mutex<Option<V>>,
     last_value_updated: Mutex<bool>,
-    await_works: TreiberStack<Box<ContinuationPl<()>>>,
+    emissions: Mutex<EmissionInstants>,
+    history: Mutex<VecDeque<(usize, V)>>,
+    history_capacity: usize,
+    await_works: Mutex<AwaitWorks<Box<ContinuationPl<()>>>>,
     present_works: TreiberStack<Box<ContinuationPl<()>>>,
 }
 
 impl<V> SpmcSignalRuntime<V> where V: Clone {
     /// Returns a new instance of SignalRuntime.
-    fn new() -> Self {
+    fn new(history_capacity: usize) -> Self {
         SpmcSignalRuntime {
+            info: SignalInfo::new(),
             value: Mutex::new(None),
             last_value: Mutex::new(None),
             last_value_updated: Mutex::new(false),
-            await_works: TreiberStack::new(),
+            emissions: Mutex::new(EmissionInstants::default()),
+            history: Mutex::new(VecDeque::with_capacity(history_capacity)),
+            history_capacity,
+            await_works: Mutex::new(AwaitWorks::new()),
             present_works: TreiberStack::new(),
         }
     }
+
+    /// Records the value emitted at `instant` in the history if it is enabled,
+    /// dropping the oldest value when the history is full.
+    fn push_history(&self, instant: usize, value: V) {
+        if self.history_capacity > 0 {
+            let mut history = self.history.lock().unwrap();
+            if history.len() == self.history_capacity {
+                history.pop_front();
+            }
+            history.push_back((instant, value));
+        }
+    }
 }
 
 impl<V> SignalRuntimeRefBase<ParallelRuntime> for SpmcSignalRuntimeRef<V>
@@ -48,9 +71,17 @@ impl<V> SignalRuntimeRefBase<ParallelRuntime> for SpmcSignalRuntimeRef<V>
         self.runtime.value.lock().unwrap().is_some()
     }
 
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.lock().unwrap()
+    }
+
     /// Resets the signal at the beginning of each instant.
     fn reset(&mut self) {
-        *self.runtime.value.lock().unwrap() = None;
+        let value = self.runtime.value.lock().unwrap().take();
+        if let Some(value) = value {
+            self.runtime.push_history(self.emissions().last().unwrap(), value);
+        }
         *self.runtime.last_value_updated.lock().unwrap() = false;
     }
 
@@ -70,18 +101,19 @@ impl<V> SignalRuntimeRefPl for SpmcSignalRuntimeRef<V>
     where V: Clone + Send + Sync + 'static
 {
     /// Calls `c` at the first cycle where the signal is present.
-    fn on_signal<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationPl<()>
     {
+        let c = runtime.on_wakeup(c);
         // Important: the mutex must be unlocked after the task is added
         // in the stack if this is the case. Similar for `on_signal_present`.
         let value_guard = self.runtime.value.lock().unwrap();
         if value_guard.is_some() {
             drop(value_guard);
-            c.call(runtime, ());
+            c.call_box(runtime, ());
         } else {
-            runtime.incr_await_counter();
-            self.runtime.await_works.push(Box::new(c));
+            self.runtime.await_works.lock().unwrap().register(runtime, &self.runtime.info, token, c);
         }
     }
     
@@ -89,12 +121,13 @@ impl<V> SignalRuntimeRefPl for SpmcSignalRuntimeRef<V>
     fn on_signal_present<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
         where C: ContinuationPl<()>
     {
+        let c = runtime.on_wakeup(c);
         let value_guard = self.runtime.value.lock().unwrap();
         if value_guard.is_some() {
             drop(value_guard);
-            c.call(runtime, ());
+            c.call_box(runtime, ());
         } else {
-            self.runtime.present_works.push(Box::new(c));
+            self.runtime.present_works.push(c);
         }
     }
 }
@@ -106,15 +139,16 @@ impl<V> CanEmit<ParallelRuntime, V> for SpmcSignalRuntimeRef<V>
         {
             let mut value_guard = self.runtime.value.lock().unwrap();
             if value_guard.is_some() {
-                panic!("Multiple emissions of a single-producer signal inside an instant.");
+                panic!("Multiple emissions of the single-producer {} inside an instant.",
+                       self.runtime.info);
             }
             *value_guard = Some(emitted);
         }
-        while let Some(c) = self.runtime.await_works.try_pop() {
-            runtime.decr_await_counter();
-            runtime.on_current_instant(c);
+        while let Some(c) = self.runtime.await_works.lock().unwrap().wake(runtime, &self.runtime.info) {
+            runtime.spawn(c);
         }
         self.execute_present_works(runtime);
+        self.runtime.emissions.lock().unwrap().record(runtime.current_instant());
         runtime.emit_signal(Box::new(self.clone()));
         let signal_ref = self.clone();
         let update_last_value = move |_: &mut ParallelRuntime, ()| {
@@ -140,11 +174,11 @@ impl<V> CanTryEmit<ParallelRuntime, V> for SpmcSignalRuntimeRef<V>
             }
             *value_guard = Some(emitted);
         }
-        while let Some(c) = self.runtime.await_works.try_pop() {
-            runtime.decr_await_counter();
-            runtime.on_current_instant(c);
+        while let Some(c) = self.runtime.await_works.lock().unwrap().wake(runtime, &self.runtime.info) {
+            runtime.spawn(c);
         }
         self.execute_present_works(runtime);
+        self.runtime.emissions.lock().unwrap().record(runtime.current_instant());
         runtime.emit_signal(Box::new(self.clone()));
         let signal_ref = self.clone();
         let update_last_value = move |_: &mut ParallelRuntime, ()| {
@@ -164,14 +198,32 @@ impl<V> GetValue<V> for SpmcSignalRuntimeRef<V> where V: Clone {
     /// Returns the value of the signal for the current instant.
     /// The returned value is cloned and can thus be used directly.
     fn get_value(&self) -> V {
-        self.runtime.value.lock().unwrap().clone().unwrap()
+        self.runtime.value.lock().unwrap().clone().unwrap_or_else(
+            || panic!("Trying to get the value of the {} which is not emitted.", self.runtime.info))
+    }
+}
+
+impl<V> GetPreValue for SpmcSignalRuntimeRef<V> where V: Clone {
+    type PreValue = Option<V>;
+
+    /// Returns the value of the signal at its last emission before the current instant.
+    fn get_pre_value(&self) -> Option<V> {
+        self.runtime.last_value.lock().unwrap().clone()
+    }
+}
+
+impl<V> GetHistory<V> for SpmcSignalRuntimeRef<V> where V: Clone {
+    /// Returns the values recorded in the history with the instants of their emission,
+    /// the oldest first.
+    fn get_history(&self) -> Vec<(usize, V)> {
+        self.runtime.history.lock().unwrap().iter().cloned().collect()
     }
 }
 
 impl<V> SpmcSignalRuntimeRef<V> where V: Clone + Send + Sync + 'static {
     /// Returns a new instance of SignalRuntimeRef.
-    fn new() -> Self {
-        SpmcSignalRuntimeRef { runtime: Arc::new(SpmcSignalRuntime::new()) }
+    fn new(history_capacity: usize) -> Self {
+        SpmcSignalRuntimeRef { runtime: Arc::new(SpmcSignalRuntime::new(history_capacity)) }
     }
 }
 
@@ -190,6 +242,10 @@ impl<V> Signal for SpmcSignalPl<V> where V: Clone + Send + Sync + 'static {
     fn runtime(&self) -> SpmcSignalRuntimeRef<V> {
         self.0.clone()
     }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
 }
 
 impl<V> ValuedSignal for SpmcSignalPl<V> where V: Clone + Send + Sync + 'static {
@@ -197,10 +253,23 @@ impl<V> ValuedSignal for SpmcSignalPl<V> where V: Clone + Send + Sync + 'static
     type SigType = SpSignal;
 }
 
+impl<V> fmt::Debug for SpmcSignalPl<V> where V: Clone + Send + Sync + 'static {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "SpmcSignalPl", self.0.is_emitted())
+    }
+}
+
 impl<V> SpmcSignalPl<V> where V: Clone + Send + Sync + 'static {
+    signal_info_methods!();
+
     /// Creates a new spmc signal.
     pub fn new() -> Self {
-        SpmcSignalPl(SpmcSignalRuntimeRef::new())
+        SpmcSignalPl(SpmcSignalRuntimeRef::new(0))
+    }
+
+    /// Creates a new spmc signal which keeps the values of its last `capacity` emissions.
+    pub fn with_history(capacity: usize) -> Self {
+        SpmcSignalPl(SpmcSignalRuntimeRef::new(capacity))
     }
 
     /// Returns the last value associated to the signal when it was emitted.
@@ -0,0 +1,72 @@
+use runtime::{SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
+use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
+
+use signal::Signal;
+use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefSt, SignalRuntimeRefPl};
+
+/// Process that tells whether a signal was present at the previous instant.
+pub struct PrePresent<S>(pub(crate) S);
+
+impl<S> Process for PrePresent<S> where S: Signal {
+    type Value = bool;
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("pre_present({})", self.0.label()))
+    }
+}
+
+impl<S> ProcessMut for PrePresent<S> where S: Signal {}
+
+// Implements the traits for the single thread version of the library.
+
+impl<S> ProcessSt for PrePresent<S>
+    where S: Signal, S::RuntimeRef: SignalRuntimeRefSt
+{
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        let present = self.0.runtime().emissions().present_before(runtime.current_instant());
+        next.call(runtime, present);
+    }
+}
+
+impl<S> ProcessMutSt for PrePresent<S>
+    where S: Signal, S::RuntimeRef: SignalRuntimeRefSt
+{
+    fn call_mut<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        let present = self.0.runtime().emissions().present_before(runtime.current_instant());
+        next.call(runtime, (self, present));
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<S> ConstraintOnValue for PrePresent<S> where S: Signal {
+    type T = bool;
+}
+
+impl<S> ProcessPl for PrePresent<S>
+    where S: Signal + Send + Sync, S::RuntimeRef: SignalRuntimeRefPl
+{
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        let present = self.0.runtime().emissions().present_before(runtime.current_instant());
+        next.call(runtime, present);
+    }
+}
+
+impl<S> ProcessMutPl for PrePresent<S>
+    where S: Signal + Send + Sync, S::RuntimeRef: SignalRuntimeRefPl
+{
+    fn call_mut<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        let present = self.0.runtime().emissions().present_before(runtime.current_instant());
+        next.call(runtime, (self, present));
+    }
+}
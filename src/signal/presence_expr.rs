@@ -0,0 +1,568 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+use std::ops::{BitAnd, BitOr, Not};
+
+use runtime::{SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
+use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
+
+use signal::Signal;
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants};
+use signal::signal_runtime::{SignalRuntimeRefSt, SignalRuntimeRefPl};
+
+/// A boolean expression over the presence of signals, built from `Signal::presence`
+/// with the operators `&`, `|` and `!`.
+pub trait PresenceExpr: Clone + 'static {
+    /// Tests the expression. If it is true, the process `p1` is executed
+    /// instantaneously, otherwise `p2` is executed at the following instant.
+    ///
+    /// The expression is considered as true as soon as it's true whatever the
+    /// status of the signals not yet emitted. When this is only known at the end of
+    /// the instant (ex: `!s.presence()`), `p1` is executed at the following instant.
+    fn present_else<P1, P2, V>(&self, p1: P1, p2: P2) -> ExprPresentElse<Self, P1, P2>
+        where P1: Process<Value=V>, P2: Process<Value=V>
+    {
+        ExprPresentElse { expr: self.clone(), present_proc: p1, else_proc: p2 }
+    }
+
+    /// Returns a short label of the expression, used to describe processes.
+    fn label(&self) -> String;
+}
+
+/// The presence of a signal.
+#[derive(Clone)]
+pub struct Presence<S>(pub(crate) S);
+
+/// Conjunction of two expressions.
+#[derive(Clone)]
+pub struct And<E1, E2>(pub(crate) E1, pub(crate) E2);
+
+/// Disjunction of two expressions.
+#[derive(Clone)]
+pub struct Or<E1, E2>(pub(crate) E1, pub(crate) E2);
+
+/// Negation of an expression.
+#[derive(Clone)]
+pub struct Negation<E>(pub(crate) E);
+
+impl<S> PresenceExpr for Presence<S> where S: Signal {
+    fn label(&self) -> String {
+        self.0.label()
+    }
+}
+
+impl<E1, E2> PresenceExpr for And<E1, E2> where E1: PresenceExpr, E2: PresenceExpr {
+    fn label(&self) -> String {
+        format!("({} & {})", self.0.label(), self.1.label())
+    }
+}
+
+impl<E1, E2> PresenceExpr for Or<E1, E2> where E1: PresenceExpr, E2: PresenceExpr {
+    fn label(&self) -> String {
+        format!("({} | {})", self.0.label(), self.1.label())
+    }
+}
+
+impl<E> PresenceExpr for Negation<E> where E: PresenceExpr {
+    fn label(&self) -> String {
+        format!("!{}", self.0.label())
+    }
+}
+
+impl<S, E> BitAnd<E> for Presence<S> {
+    type Output = And<Presence<S>, E>;
+    fn bitand(self, e: E) -> Self::Output { And(self, e) }
+}
+
+impl<S, E> BitOr<E> for Presence<S> {
+    type Output = Or<Presence<S>, E>;
+    fn bitor(self, e: E) -> Self::Output { Or(self, e) }
+}
+
+impl<S> Not for Presence<S> {
+    type Output = Negation<Presence<S>>;
+    fn not(self) -> Self::Output { Negation(self) }
+}
+
+impl<E1, E2, E> BitAnd<E> for And<E1, E2> {
+    type Output = And<And<E1, E2>, E>;
+    fn bitand(self, e: E) -> Self::Output { And(self, e) }
+}
+
+impl<E1, E2, E> BitOr<E> for And<E1, E2> {
+    type Output = Or<And<E1, E2>, E>;
+    fn bitor(self, e: E) -> Self::Output { Or(self, e) }
+}
+
+impl<E1, E2> Not for And<E1, E2> {
+    type Output = Negation<And<E1, E2>>;
+    fn not(self) -> Self::Output { Negation(self) }
+}
+
+impl<E1, E2, E> BitAnd<E> for Or<E1, E2> {
+    type Output = And<Or<E1, E2>, E>;
+    fn bitand(self, e: E) -> Self::Output { And(self, e) }
+}
+
+impl<E1, E2, E> BitOr<E> for Or<E1, E2> {
+    type Output = Or<Or<E1, E2>, E>;
+    fn bitor(self, e: E) -> Self::Output { Or(self, e) }
+}
+
+impl<E1, E2> Not for Or<E1, E2> {
+    type Output = Negation<Or<E1, E2>>;
+    fn not(self) -> Self::Output { Negation(self) }
+}
+
+impl<E1, E> BitAnd<E> for Negation<E1> {
+    type Output = And<Negation<E1>, E>;
+    fn bitand(self, e: E) -> Self::Output { And(self, e) }
+}
+
+impl<E1, E> BitOr<E> for Negation<E1> {
+    type Output = Or<Negation<E1>, E>;
+    fn bitor(self, e: E) -> Self::Output { Or(self, e) }
+}
+
+impl<E> Not for Negation<E> {
+    type Output = E;
+    fn not(self) -> E { self.0 }
+}
+
+/// Three-valued conjunction, `None` meaning that the value is not yet known.
+fn and(v1: Option<bool>, v2: Option<bool>) -> Option<bool> {
+    match (v1, v2) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+/// Three-valued disjunction, `None` meaning that the value is not yet known.
+fn or(v1: Option<bool>, v2: Option<bool>) -> Option<bool> {
+    match (v1, v2) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+/// Process that tests a presence expression and chooses the branch to execute
+/// according to the result.
+pub struct ExprPresentElse<E, P1, P2> {
+    pub(crate) expr: E,
+    pub(crate) present_proc: P1,
+    pub(crate) else_proc: P2,
+}
+
+impl<E, P1, P2, V> Process for ExprPresentElse<E, P1, P2>
+    where E: PresenceExpr, P1: Process<Value=V>, P2: Process<Value=V>
+{
+    type Value = V;
+
+    fn describe(&self) -> Description {
+        Description::node(format!("present_else({})", self.expr.label()),
+                          vec![self.present_proc.describe(), self.else_proc.describe()])
+    }
+}
+
+impl<E, P1, P2, V> ProcessMut for ExprPresentElse<E, P1, P2>
+    where E: PresenceExpr, P1: ProcessMut<Value=V>, P2: ProcessMut<Value=V> {}
+
+// Implements the traits for the single thread version of the library.
+
+pub trait PresenceExprSt: PresenceExpr {
+    /// Evaluates the expression. The signals which are not emitted are considered as
+    /// absent if `complete` is true and as unknown otherwise.
+    fn eval(&self, complete: bool) -> Option<bool>;
+
+    /// Calls `watcher` each time one of the signals of the expression is emitted.
+    /// It's also called when the present works of the signals are executed.
+    fn watch(&self, runtime: &mut SingleThreadRuntime, watcher: &Rc<Fn(&mut SingleThreadRuntime)>);
+
+    /// Executes the present works of all the signals of the expression.
+    fn execute_present_works(&self, runtime: &mut SingleThreadRuntime);
+}
+
+impl<S> PresenceExprSt for Presence<S> where S: Signal, S::RuntimeRef: SignalRuntimeRefSt {
+    fn eval(&self, complete: bool) -> Option<bool> {
+        if self.0.runtime().is_emitted() {
+            Some(true)
+        } else if complete {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    fn watch(&self, runtime: &mut SingleThreadRuntime, watcher: &Rc<Fn(&mut SingleThreadRuntime)>) {
+        let watcher = watcher.clone();
+        self.0.runtime().on_signal_present(runtime, move |r: &mut SingleThreadRuntime, ()| {
+            watcher(r);
+        });
+    }
+
+    fn execute_present_works(&self, runtime: &mut SingleThreadRuntime) {
+        self.0.runtime().execute_present_works(runtime);
+    }
+}
+
+impl<E1, E2> PresenceExprSt for And<E1, E2> where E1: PresenceExprSt, E2: PresenceExprSt {
+    fn eval(&self, complete: bool) -> Option<bool> {
+        and(self.0.eval(complete), self.1.eval(complete))
+    }
+
+    fn watch(&self, runtime: &mut SingleThreadRuntime, watcher: &Rc<Fn(&mut SingleThreadRuntime)>) {
+        self.0.watch(runtime, watcher);
+        self.1.watch(runtime, watcher);
+    }
+
+    fn execute_present_works(&self, runtime: &mut SingleThreadRuntime) {
+        self.0.execute_present_works(runtime);
+        self.1.execute_present_works(runtime);
+    }
+}
+
+impl<E1, E2> PresenceExprSt for Or<E1, E2> where E1: PresenceExprSt, E2: PresenceExprSt {
+    fn eval(&self, complete: bool) -> Option<bool> {
+        or(self.0.eval(complete), self.1.eval(complete))
+    }
+
+    fn watch(&self, runtime: &mut SingleThreadRuntime, watcher: &Rc<Fn(&mut SingleThreadRuntime)>) {
+        self.0.watch(runtime, watcher);
+        self.1.watch(runtime, watcher);
+    }
+
+    fn execute_present_works(&self, runtime: &mut SingleThreadRuntime) {
+        self.0.execute_present_works(runtime);
+        self.1.execute_present_works(runtime);
+    }
+}
+
+impl<E> PresenceExprSt for Negation<E> where E: PresenceExprSt {
+    fn eval(&self, complete: bool) -> Option<bool> {
+        self.0.eval(complete).map(|v| !v)
+    }
+
+    fn watch(&self, runtime: &mut SingleThreadRuntime, watcher: &Rc<Fn(&mut SingleThreadRuntime)>) {
+        self.0.watch(runtime, watcher);
+    }
+
+    fn execute_present_works(&self, runtime: &mut SingleThreadRuntime) {
+        self.0.execute_present_works(runtime);
+    }
+}
+
+/// The entry registered in the runtime to decide the branch to execute at the
+/// end of the instant, if this is not yet done.
+struct ExprTestSt<E, T> {
+    expr: E,
+    branches: Rc<RefCell<Option<T>>>,
+    fire: fn(&mut SingleThreadRuntime, T, bool),
+}
+
+impl<E, T> SignalRuntimeRefBase<SingleThreadRuntime> for ExprTestSt<E, T>
+    where E: PresenceExprSt, T: 'static
+{
+    fn is_emitted(&self) -> bool {
+        self.expr.eval(false) == Some(true)
+    }
+
+    fn emissions(&self) -> EmissionInstants {
+        EmissionInstants::default()
+    }
+
+    fn reset(&mut self) {}
+
+    fn execute_present_works(&mut self, runtime: &mut SingleThreadRuntime) {
+        self.expr.execute_present_works(runtime);
+        let branches = self.branches.borrow_mut().take();
+        if let Some(branches) = branches {
+            let present = self.expr.eval(true) == Some(true);
+            let fire = self.fire;
+            runtime.on_next_instant(Box::new(move |r: &mut SingleThreadRuntime, ()| {
+                fire(r, branches, present);
+            }));
+        }
+    }
+}
+
+/// Executes the present branch as soon as the expression is known to be true,
+/// and registers the expression to decide at the end of the instant otherwise.
+fn test_expr_st<E, T>(
+    expr: E, runtime: &mut SingleThreadRuntime, branches: T,
+    fire: fn(&mut SingleThreadRuntime, T, bool))
+    where E: PresenceExprSt, T: 'static
+{
+    let branches = Rc::new(RefCell::new(Some(branches)));
+    let watcher: Rc<Fn(&mut SingleThreadRuntime)> = {
+        let (expr, branches) = (expr.clone(), branches.clone());
+        Rc::new(move |r: &mut SingleThreadRuntime| {
+            if expr.eval(false) == Some(true) {
+                let branches = branches.borrow_mut().take();
+                if let Some(branches) = branches {
+                    // As for `PresentElse`, the branch is not executed directly
+                    // since many continuations may be bound to a same signal.
+                    r.on_current_instant(Box::new(move |r: &mut SingleThreadRuntime, ()| {
+                        fire(r, branches, true);
+                    }));
+                }
+            }
+        })
+    };
+    expr.watch(runtime, &watcher);
+    runtime.add_test_signal(Box::new(ExprTestSt { expr, branches, fire }));
+}
+
+impl<E, P1, P2, V> ProcessSt for ExprPresentElse<E, P1, P2>
+    where E: PresenceExprSt, P1: ProcessSt<Value=V>, P2: ProcessSt<Value=V>
+{
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        fn fire<P1, P2, V, C>(r: &mut SingleThreadRuntime, (p1, p2, next): (P1, P2, C), present: bool)
+            where P1: ProcessSt<Value=V>, P2: ProcessSt<Value=V>, C: ContinuationSt<V>
+        {
+            if present {
+                p1.call(r, next);
+            } else {
+                p2.call(r, next);
+            }
+        }
+        test_expr_st(self.expr, runtime, (self.present_proc, self.else_proc, next), fire);
+    }
+}
+
+impl<E, P1, P2, V> ProcessMutSt for ExprPresentElse<E, P1, P2>
+    where E: PresenceExprSt, P1: ProcessMutSt<Value=V>, P2: ProcessMutSt<Value=V>
+{
+    fn call_mut<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        fn fire<E, P1, P2, V, C>(
+            r: &mut SingleThreadRuntime, (expr, p1, p2, next): (E, P1, P2, C), present: bool)
+            where E: PresenceExpr,
+                  P1: ProcessMutSt<Value=V>,
+                  P2: ProcessMutSt<Value=V>,
+                  C: ContinuationSt<(ExprPresentElse<E, P1, P2>, V)>
+        {
+            if present {
+                p1.call_mut(r, next.map(move |(p, v)| (expr.present_else(p, p2), v)));
+            } else {
+                p2.call_mut(r, next.map(move |(p, v)| (expr.present_else(p1, p), v)));
+            }
+        }
+        let branches = (self.expr.clone(), self.present_proc, self.else_proc, next);
+        test_expr_st(self.expr, runtime, branches, fire);
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+pub trait PresenceExprPl: PresenceExpr + Send + Sync {
+    /// Evaluates the expression. The signals which are not emitted are considered as
+    /// absent if `complete` is true and as unknown otherwise.
+    fn eval(&self, complete: bool) -> Option<bool>;
+
+    /// Calls `watcher` each time one of the signals of the expression is emitted.
+    /// It's also called when the present works of the signals are executed.
+    fn watch(&self, runtime: &mut ParallelRuntime,
+             watcher: &Arc<Fn(&mut ParallelRuntime) + Send + Sync>);
+
+    /// Executes the present works of all the signals of the expression.
+    fn execute_present_works(&self, runtime: &mut ParallelRuntime);
+}
+
+impl<S> PresenceExprPl for Presence<S>
+    where S: Signal + Send + Sync, S::RuntimeRef: SignalRuntimeRefPl
+{
+    fn eval(&self, complete: bool) -> Option<bool> {
+        // The signals are reset only once the presence tests of all the runtimes are
+        // done, so a signal emitted by another runtime is still seen as emitted.
+        if self.0.runtime().is_emitted() {
+            Some(true)
+        } else if complete {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    fn watch(&self, runtime: &mut ParallelRuntime,
+             watcher: &Arc<Fn(&mut ParallelRuntime) + Send + Sync>)
+    {
+        let watcher = watcher.clone();
+        self.0.runtime().on_signal_present(runtime, move |r: &mut ParallelRuntime, ()| {
+            watcher(r);
+        });
+    }
+
+    fn execute_present_works(&self, runtime: &mut ParallelRuntime) {
+        self.0.runtime().execute_present_works(runtime);
+    }
+}
+
+impl<E1, E2> PresenceExprPl for And<E1, E2> where E1: PresenceExprPl, E2: PresenceExprPl {
+    fn eval(&self, complete: bool) -> Option<bool> {
+        and(self.0.eval(complete), self.1.eval(complete))
+    }
+
+    fn watch(&self, runtime: &mut ParallelRuntime,
+             watcher: &Arc<Fn(&mut ParallelRuntime) + Send + Sync>)
+    {
+        self.0.watch(runtime, watcher);
+        self.1.watch(runtime, watcher);
+    }
+
+    fn execute_present_works(&self, runtime: &mut ParallelRuntime) {
+        self.0.execute_present_works(runtime);
+        self.1.execute_present_works(runtime);
+    }
+}
+
+impl<E1, E2> PresenceExprPl for Or<E1, E2> where E1: PresenceExprPl, E2: PresenceExprPl {
+    fn eval(&self, complete: bool) -> Option<bool> {
+        or(self.0.eval(complete), self.1.eval(complete))
+    }
+
+    fn watch(&self, runtime: &mut ParallelRuntime,
+             watcher: &Arc<Fn(&mut ParallelRuntime) + Send + Sync>)
+    {
+        self.0.watch(runtime, watcher);
+        self.1.watch(runtime, watcher);
+    }
+
+    fn execute_present_works(&self, runtime: &mut ParallelRuntime) {
+        self.0.execute_present_works(runtime);
+        self.1.execute_present_works(runtime);
+    }
+}
+
+impl<E> PresenceExprPl for Negation<E> where E: PresenceExprPl {
+    fn eval(&self, complete: bool) -> Option<bool> {
+        self.0.eval(complete).map(|v| !v)
+    }
+
+    fn watch(&self, runtime: &mut ParallelRuntime,
+             watcher: &Arc<Fn(&mut ParallelRuntime) + Send + Sync>)
+    {
+        self.0.watch(runtime, watcher);
+    }
+
+    fn execute_present_works(&self, runtime: &mut ParallelRuntime) {
+        self.0.execute_present_works(runtime);
+    }
+}
+
+/// The entry registered in the runtime to decide the branch to execute at the
+/// end of the instant, if this is not yet done.
+struct ExprTestPl<E, T> {
+    expr: E,
+    branches: Arc<Mutex<Option<T>>>,
+    fire: fn(&mut ParallelRuntime, T, bool),
+}
+
+impl<E, T> SignalRuntimeRefBase<ParallelRuntime> for ExprTestPl<E, T>
+    where E: PresenceExprPl, T: Send + Sync + 'static
+{
+    fn is_emitted(&self) -> bool {
+        self.expr.eval(false) == Some(true)
+    }
+
+    fn emissions(&self) -> EmissionInstants {
+        EmissionInstants::default()
+    }
+
+    fn reset(&mut self) {}
+
+    fn execute_present_works(&mut self, runtime: &mut ParallelRuntime) {
+        self.expr.execute_present_works(runtime);
+        let branches = self.branches.lock().unwrap().take();
+        if let Some(branches) = branches {
+            let present = self.expr.eval(true) == Some(true);
+            let fire = self.fire;
+            runtime.on_next_instant(Box::new(move |r: &mut ParallelRuntime, ()| {
+                fire(r, branches, present);
+            }));
+        }
+    }
+}
+
+/// Executes the present branch as soon as the expression is known to be true,
+/// and registers the expression to decide at the end of the instant otherwise.
+fn test_expr_pl<E, T>(
+    expr: E, runtime: &mut ParallelRuntime, branches: T,
+    fire: fn(&mut ParallelRuntime, T, bool))
+    where E: PresenceExprPl, T: Send + Sync + 'static
+{
+    let branches = Arc::new(Mutex::new(Some(branches)));
+    let watcher: Arc<Fn(&mut ParallelRuntime) + Send + Sync> = {
+        let (expr, branches) = (expr.clone(), branches.clone());
+        Arc::new(move |r: &mut ParallelRuntime| {
+            if expr.eval(false) == Some(true) {
+                let branches = branches.lock().unwrap().take();
+                if let Some(branches) = branches {
+                    // As for `PresentElse`, the branch is not executed directly
+                    // since many continuations may be bound to a same signal.
+                    r.on_current_instant(Box::new(move |r: &mut ParallelRuntime, ()| {
+                        fire(r, branches, true);
+                    }));
+                }
+            }
+        })
+    };
+    expr.watch(runtime, &watcher);
+    runtime.add_test_signal(Box::new(ExprTestPl { expr, branches, fire }));
+}
+
+impl<E, P1, P2, V> ConstraintOnValue for ExprPresentElse<E, P1, P2>
+    where E: PresenceExpr, P1: Process<Value=V>, P2: Process<Value=V>, V: Send + Sync
+{
+    type T = V;
+}
+
+impl<E, P1, P2, V> ProcessPl for ExprPresentElse<E, P1, P2>
+    where E: PresenceExprPl, P1: ProcessPl<T=V>, P2: ProcessPl<T=V>, V: Send + Sync
+{
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        fn fire<P1, P2, V, C>(r: &mut ParallelRuntime, (p1, p2, next): (P1, P2, C), present: bool)
+            where P1: ProcessPl<T=V>, P2: ProcessPl<T=V>, V: Send + Sync, C: ContinuationPl<V>
+        {
+            if present {
+                p1.call(r, next);
+            } else {
+                p2.call(r, next);
+            }
+        }
+        test_expr_pl(self.expr, runtime, (self.present_proc, self.else_proc, next), fire);
+    }
+}
+
+impl<E, P1, P2, V> ProcessMutPl for ExprPresentElse<E, P1, P2>
+    where E: PresenceExprPl, P1: ProcessMutPl<T=V>, P2: ProcessMutPl<T=V>, V: Send + Sync
+{
+    fn call_mut<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        fn fire<E, P1, P2, V, C>(
+            r: &mut ParallelRuntime, (expr, p1, p2, next): (E, P1, P2, C), present: bool)
+            where E: PresenceExprPl,
+                  P1: ProcessMutPl<T=V>,
+                  P2: ProcessMutPl<T=V>,
+                  V: Send + Sync,
+                  C: ContinuationPl<(ExprPresentElse<E, P1, P2>, V)>
+        {
+            if present {
+                p1.call_mut(r, next.map(move |(p, v)| (expr.present_else(p, p2), v)));
+            } else {
+                p2.call_mut(r, next.map(move |(p, v)| (expr.present_else(p1, p), v)));
+            }
+        }
+        let branches = (self.expr.clone(), self.present_proc, self.else_proc, next);
+        test_expr_pl(self.expr, runtime, branches, fire);
+    }
+}
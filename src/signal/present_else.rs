@@ -1,6 +1,6 @@
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 
 use signal::Signal;
@@ -18,6 +18,11 @@ impl<S, P1, P2, V> Process for PresentElse<S, P1, P2>
     where S: Signal, P1: Process<Value=V>, P2: Process<Value=V>
 {
     type Value = V;
+
+    fn describe(&self) -> Description {
+        Description::node(format!("present_else({})", self.signal.label()),
+                          vec![self.present_proc.describe(), self.else_proc.describe()])
+    }
 }
 
 impl<S, P1, P2, V> ProcessMut for PresentElse<S, P1, P2>
@@ -1,7 +1,7 @@
 //! A reactive signal without value.
 
-use process::{Process, ProcessMut};
-use signal::Signal;
+use process::{Process, ProcessMut, PauseN, Then, Value, value_proc, Description};
+use signal::{Signal, Sustain, Forever};
 
 /// Defines the behavior of a pure signal.
 pub trait PureSignal: Signal {
@@ -13,6 +13,17 @@ pub trait PureSignal: Signal {
         Emit(self.clone())
     }
 
+    /// Returns a process that emits the signal `n` instants after it is called.
+    fn emit_after(&self, n: usize) -> Then<PauseN<Value<()>>, Emit<Self>> where Self: Sized {
+        value_proc(()).pause_n(n).then(self.emit())
+    }
+
+    /// Returns a process that emits the signal at every instant from the instant
+    /// where it is called on.
+    fn sustain(&self) -> Sustain<Emit<Self>, Forever> where Self: Sized {
+        Sustain { process: self.emit(), until: Forever }
+    }
+
     /// Emits the signal if it is not yet emitted and returns a bool indicating
     /// if the emission is successful.
     fn try_emit(&self) -> TryEmit<Self> where Self: Sized {
@@ -25,6 +36,10 @@ pub struct Emit<S>(pub(crate) S);
 
 impl<S> Process for Emit<S> where S: PureSignal {
     type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("emit({})", self.0.label()))
+    }
 }
 
 impl<S> ProcessMut for Emit<S> where S: PureSignal {}
@@ -34,6 +49,10 @@ pub struct TryEmit<S>(pub(crate) S);
 
 impl<S> Process for TryEmit<S> where S: PureSignal {
     type Value = bool;
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("try_emit({})", self.0.label()))
+    }
 }
 
 impl<S> ProcessMut for TryEmit<S> where S: PureSignal {}
@@ -0,0 +1,530 @@
+//! Recording of an execution to reproduce it later.
+//!
+//! A `Recorder` logs the values injected in the program by the host through
+//! `inject` and the values emitted on the signals it `record`s. The log can be
+//! saved as JSON lines, one line per value.
+//!
+//! To replay it, the same program is run with `Recorder::replay`: the injected
+//! values are then read from the log instead of being asked to the host, and
+//! `first_divergence` tells at which instant the values emitted on the recorded
+//! signals start to differ from the ones of the log.
+//!
+//! As in the module `vcd`, the recorded signals are sampled at the end of each
+//! instant with `get_pre_value`, which doesn't change them. The values of the
+//! mpsc signals, which are moved to their consumer, can't be recorded, but their
+//! presence can with `record_presence`.
+
+use std::io::{self, BufRead, Write};
+use std::fmt::Display;
+use std::str::{Chars, FromStr};
+use std::iter::Peekable;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use std::collections::{BTreeMap, HashMap};
+
+use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, ProcessPl, ProcessMutPl};
+use process::{ConstraintOnValue, Description};
+use signal::{Signal, ValuedSignal};
+use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefSt, SignalRuntimeRefPl};
+use signal::valued_signal::{GetPreValue, CanEmit};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryKind {
+    /// A value injected by the host.
+    Input,
+    /// A value emitted on a recorded signal.
+    Emitted,
+}
+
+/// A value of a signal at some instant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub instant: usize,
+    pub kind: EntryKind,
+    pub signal: String,
+    pub value: String,
+}
+
+/// The values injected and emitted during an execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Log {
+    pub entries: Vec<Entry>,
+}
+
+impl Log {
+    /// Writes the log as JSON lines, one object per entry.
+    pub fn write_json_lines<W>(&self, mut out: W) -> io::Result<()> where W: Write {
+        for e in &self.entries {
+            let kind = match e.kind {
+                EntryKind::Input => "input",
+                EntryKind::Emitted => "emitted",
+            };
+            writeln!(out, "{{\"instant\":{},\"kind\":\"{}\",\"signal\":{},\"value\":{}}}",
+                     e.instant, kind, json_string(&e.signal), json_string(&e.value))?;
+        }
+        Ok(())
+    }
+
+    /// Reads a log written by `write_json_lines`. Each line must be a JSON object
+    /// with the fields `instant`, `kind`, `signal` and `value`, and empty lines are
+    /// ignored. An invalid line gives an error of kind `InvalidData`.
+    pub fn read_json_lines<B>(input: B) -> io::Result<Log> where B: BufRead {
+        let mut entries = Vec::new();
+        for (n, line) in input.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match parse_entry(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid log entry at line {}: {}", n + 1, e))),
+            }
+        }
+        Ok(Log { entries })
+    }
+
+    /// Returns the first instant at which the values emitted on the recorded
+    /// signals differ between the two logs.
+    pub fn first_divergence(&self, other: &Log) -> Option<usize> {
+        let expected = self.by_instant(EntryKind::Emitted);
+        let actual = other.by_instant(EntryKind::Emitted);
+        let mut instants: Vec<_> = expected.keys().chain(actual.keys()).cloned().collect();
+        instants.sort();
+        instants.into_iter().find(|i| expected.get(i) != actual.get(i))
+    }
+
+    /// Returns the signals and the values emitted at `instant`, sorted.
+    pub fn emitted_at(&self, instant: usize) -> Vec<(&str, &str)> {
+        self.by_instant(EntryKind::Emitted).remove(&instant).unwrap_or_default()
+    }
+
+    /// The values of the given kind at each instant, sorted so that the order in
+    /// which the runtimes sampled the signals doesn't matter.
+    fn by_instant(&self, kind: EntryKind) -> BTreeMap<usize, Vec<(&str, &str)>> {
+        let mut values = BTreeMap::new();
+        for e in self.entries.iter().filter(|e| e.kind == kind) {
+            values.entry(e.instant).or_insert_with(Vec::new)
+                .push((e.signal.as_str(), e.value.as_str()));
+        }
+        for v in values.values_mut() {
+            v.sort();
+        }
+        values
+    }
+}
+
+/// Keeps the log of an execution, or replays one. It can be cloned and shared
+/// between the runtimes of a parallel engine.
+#[derive(Clone)]
+pub struct Recorder(Arc<Mutex<RecorderContent>>);
+
+struct RecorderContent {
+    log: Log,
+    /// The log being replayed, if any.
+    replayed: Option<Log>,
+    /// The injected values of the replayed log, by instant and signal.
+    inputs: HashMap<(usize, String), Vec<String>>,
+}
+
+impl Recorder {
+    /// Creates a recorder logging a new execution.
+    pub fn new() -> Self {
+        Recorder(Arc::new(Mutex::new(RecorderContent {
+            log: Log::default(),
+            replayed: None,
+            inputs: HashMap::new(),
+        })))
+    }
+
+    /// Creates a recorder replaying the inputs of `log`. The new execution is
+    /// logged too, so that it can be compared with `log`.
+    pub fn replay(log: Log) -> Self {
+        let mut inputs = HashMap::new();
+        for e in log.entries.iter().filter(|e| e.kind == EntryKind::Input) {
+            inputs.entry((e.instant, e.signal.clone())).or_insert_with(Vec::new)
+                .push(e.value.clone());
+        }
+        Recorder(Arc::new(Mutex::new(RecorderContent {
+            log: Log::default(),
+            replayed: Some(log),
+            inputs,
+        })))
+    }
+
+    /// Returns the log of the execution so far.
+    pub fn log(&self) -> Log {
+        self.0.lock().unwrap().log.clone()
+    }
+
+    /// Returns the first instant at which the values emitted on the recorded
+    /// signals differ from the ones of the replayed log, or `None` if they are
+    /// the same or if nothing is replayed.
+    pub fn first_divergence(&self) -> Option<usize> {
+        let content = self.0.lock().unwrap();
+        content.replayed.as_ref().and_then(|log| log.first_divergence(&content.log))
+    }
+
+    /// Returns a process injecting in a valued signal the values given by the host
+    /// for the current instant. `source` is called with the instant each time the
+    /// process is executed; when replaying, it is not called and the values are
+    /// taken from the log instead.
+    pub fn inject<S, A, F>(&self, name: &str, signal: &S, source: F) -> Inject<S, A, F>
+        where S: ValuedSignal, A: Display + FromStr, F: FnMut(usize) -> Vec<A>
+    {
+        Inject {
+            signal: signal.clone(),
+            name: name.to_owned(),
+            source,
+            recorder: self.clone(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a process that starts recording the values emitted on a signal,
+    /// until the end of the execution.
+    pub fn record<S>(&self, name: &str, signal: &S) -> Record<S>
+        where S: Signal, S::RuntimeRef: GetPreValue,
+              <S::RuntimeRef as GetPreValue>::PreValue: Display
+    {
+        fn dump<R>(r: &R) -> String where R: GetPreValue, R::PreValue: Display {
+            r.get_pre_value().to_string()
+        }
+        Record {
+            signal: signal.clone(),
+            name: name.to_owned(),
+            recorder: self.clone(),
+            dump,
+        }
+    }
+
+    /// Same as `record`, only recording the presence of the signal, which can be
+    /// pure or valued. The value of the entries is then `present`.
+    pub fn record_presence<S>(&self, name: &str, signal: &S) -> Record<S> where S: Signal {
+        fn dump<R>(_: &R) -> String {
+            "present".to_owned()
+        }
+        Record {
+            signal: signal.clone(),
+            name: name.to_owned(),
+            recorder: self.clone(),
+            dump,
+        }
+    }
+
+    fn push(&self, instant: usize, kind: EntryKind, signal: &str, value: String) {
+        let entry = Entry { instant, kind, signal: signal.to_owned(), value };
+        self.0.lock().unwrap().log.entries.push(entry);
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Recorder::new()
+    }
+}
+
+/// Process injecting the values of the host in a signal, see `Recorder::inject`.
+pub struct Inject<S, A, F> {
+    signal: S,
+    name: String,
+    source: F,
+    recorder: Recorder,
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<S, A, F> Inject<S, A, F> where A: Display + FromStr, F: FnMut(usize) -> Vec<A> {
+    /// Returns the values to inject at `instant`, and logs them.
+    fn values(&mut self, instant: usize) -> Vec<A> {
+        let replayed: Option<Vec<String>> = {
+            let content = self.recorder.0.lock().unwrap();
+            content.replayed.as_ref().map(|_| {
+                content.inputs.get(&(instant, self.name.clone())).cloned().unwrap_or_default()
+            })
+        };
+        let values = match replayed {
+            Some(values) => values.iter().map(|v| v.parse().unwrap_or_else(|_| {
+                panic!("Cannot parse the value `{}` injected in `{}`.", v, self.name)
+            })).collect(),
+            None => (self.source)(instant),
+        };
+        for v in &values {
+            self.recorder.push(instant, EntryKind::Input, &self.name, v.to_string());
+        }
+        values
+    }
+}
+
+impl<S, A, F> Process for Inject<S, A, F>
+    where S: ValuedSignal, A: 'static, F: FnMut(usize) -> Vec<A> + 'static
+{
+    type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("inject({})", self.signal.label()))
+    }
+}
+
+impl<S, A, F> ProcessMut for Inject<S, A, F>
+    where S: ValuedSignal, A: 'static, F: FnMut(usize) -> Vec<A> + 'static {}
+
+// Non-parallel
+
+impl<S, A, F> ProcessSt for Inject<S, A, F>
+    where S: ValuedSignal, S::RuntimeRef: CanEmit<SingleThreadRuntime, A>,
+          A: Display + FromStr + 'static, F: FnMut(usize) -> Vec<A> + 'static
+{
+    fn call<C>(mut self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        let mut signal_runtime = self.signal.runtime();
+        for v in self.values(runtime.current_instant()) {
+            signal_runtime.emit(runtime, v);
+        }
+        next.call(runtime, ());
+    }
+}
+
+impl<S, A, F> ProcessMutSt for Inject<S, A, F>
+    where S: ValuedSignal, S::RuntimeRef: CanEmit<SingleThreadRuntime, A>,
+          A: Display + FromStr + 'static, F: FnMut(usize) -> Vec<A> + 'static
+{
+    fn call_mut<C>(mut self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        let mut signal_runtime = self.signal.runtime();
+        for v in self.values(runtime.current_instant()) {
+            signal_runtime.emit(runtime, v);
+        }
+        next.call(runtime, (self, ()));
+    }
+}
+
+// Parallel
+
+impl<S, A, F> ConstraintOnValue for Inject<S, A, F> {
+    type T = ();
+}
+
+impl<S, A, F> ProcessPl for Inject<S, A, F>
+    where S: ValuedSignal + Send + Sync, S::RuntimeRef: CanEmit<ParallelRuntime, A>,
+          A: Display + FromStr + 'static,
+          F: FnMut(usize) -> Vec<A> + Send + Sync + 'static
+{
+    fn call<C>(mut self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        let mut signal_runtime = self.signal.runtime();
+        for v in self.values(runtime.current_instant()) {
+            signal_runtime.emit(runtime, v);
+        }
+        next.call(runtime, ());
+    }
+}
+
+impl<S, A, F> ProcessMutPl for Inject<S, A, F>
+    where S: ValuedSignal + Send + Sync, S::RuntimeRef: CanEmit<ParallelRuntime, A>,
+          A: Display + FromStr + 'static,
+          F: FnMut(usize) -> Vec<A> + Send + Sync + 'static
+{
+    fn call_mut<C>(mut self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        let mut signal_runtime = self.signal.runtime();
+        for v in self.values(runtime.current_instant()) {
+            signal_runtime.emit(runtime, v);
+        }
+        next.call(runtime, (self, ()));
+    }
+}
+
+/// Process installing the recording of a signal in the runtime, see
+/// `Recorder::record`.
+pub struct Record<S> where S: Signal {
+    signal: S,
+    name: String,
+    recorder: Recorder,
+    dump: fn(&S::RuntimeRef) -> String,
+}
+
+impl<S> Record<S> where S: Signal {
+    /// Returns the function sampling the signal at the end of each instant.
+    fn sampler<R>(self) -> impl FnMut(usize)
+        where R: Runtime, S::RuntimeRef: SignalRuntimeRefBase<R>
+    {
+        let Record { signal, name, recorder, dump } = self;
+        let runtime_ref = signal.runtime();
+        move |instant| {
+            // Emissions at the next instant may already be recorded in parallel.
+            if runtime_ref.emissions().present_before(instant + 1) {
+                recorder.push(instant, EntryKind::Emitted, &name, dump(&runtime_ref));
+            }
+        }
+    }
+}
+
+impl<S> Process for Record<S> where S: Signal {
+    type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("record({})", self.signal.label()))
+    }
+}
+
+impl<S> ProcessSt for Record<S> where S: Signal, S::RuntimeRef: SignalRuntimeRefSt {
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C) where C: ContinuationSt<()> {
+        runtime.watch(Box::new(self.sampler::<SingleThreadRuntime>()));
+        next.call(runtime, ());
+    }
+}
+
+impl<S> ConstraintOnValue for Record<S> where S: Signal {
+    type T = ();
+}
+
+impl<S> ProcessPl for Record<S>
+    where S: Signal + Send + Sync, S::RuntimeRef: SignalRuntimeRefPl
+{
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C) where C: ContinuationPl<()> {
+        runtime.watch(Box::new(self.sampler::<ParallelRuntime>()));
+        next.call(runtime, ());
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Parses a line of the log, which is a JSON object with the fields written by
+/// `write_json_lines`, in any order. Whitespace is allowed between the tokens.
+fn parse_entry(line: &str) -> Result<Entry, String> {
+    let mut chars = line.chars().peekable();
+    let (mut instant, mut kind, mut signal, mut value) = (None, None, None, None);
+    expect(&mut chars, '{')?;
+    loop {
+        skip_whitespace(&mut chars);
+        let key = parse_string(&mut chars)?;
+        expect(&mut chars, ':')?;
+        skip_whitespace(&mut chars);
+        match key.as_str() {
+            "instant" => {
+                let mut digits = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    digits.push(c);
+                    chars.next();
+                }
+                instant = Some(digits.parse().map_err(|_| "invalid instant".to_owned())?);
+            },
+            "kind" => kind = match parse_string(&mut chars)?.as_str() {
+                "input" => Some(EntryKind::Input),
+                "emitted" => Some(EntryKind::Emitted),
+                k => return Err(format!("unknown kind `{}`", k)),
+            },
+            "signal" => signal = Some(parse_string(&mut chars)?),
+            "value" => value = Some(parse_string(&mut chars)?),
+            k => return Err(format!("unknown field `{}`", k)),
+        }
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some(',') => (),
+            Some('}') => break,
+            _ => return Err("expected `,` or `}`".to_owned()),
+        }
+    }
+    skip_whitespace(&mut chars);
+    if chars.next().is_some() {
+        return Err("unexpected characters after the object".to_owned());
+    }
+    let missing = |field: &str| format!("missing field `{}`", field);
+    Ok(Entry {
+        instant: instant.ok_or_else(|| missing("instant"))?,
+        kind: kind.ok_or_else(|| missing("kind"))?,
+        signal: signal.ok_or_else(|| missing("signal"))?,
+        value: value.ok_or_else(|| missing("value"))?,
+    })
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while let Some(&c) = chars.peek() {
+        if !(c == ' ' || c == '\t' || c == '\n' || c == '\r') {
+            break;
+        }
+        chars.next();
+    }
+}
+
+fn expect(chars: &mut Peekable<Chars>, expected: char) -> Result<(), String> {
+    skip_whitespace(chars);
+    match chars.next() {
+        Some(c) if c == expected => Ok(()),
+        _ => Err(format!("expected `{}`", expected)),
+    }
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    expect(chars, '"')?;
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            None => return Err("unterminated string".to_owned()),
+            Some('"') => return Ok(s),
+            Some('\\') => match chars.next() {
+                Some('"') => s.push('"'),
+                Some('\\') => s.push('\\'),
+                Some('/') => s.push('/'),
+                Some('b') => s.push('\u{8}'),
+                Some('f') => s.push('\u{c}'),
+                Some('n') => s.push('\n'),
+                Some('r') => s.push('\r'),
+                Some('t') => s.push('\t'),
+                Some('u') => {
+                    let mut code = parse_hex4(chars)?;
+                    // A character outside of the basic plane is written as a
+                    // surrogate pair.
+                    if (0xd800..0xdc00).contains(&code) {
+                        if chars.next() != Some('\\') || chars.next() != Some('u') {
+                            return Err("unpaired surrogate".to_owned());
+                        }
+                        let low = parse_hex4(chars)?;
+                        if !(0xdc00..0xe000).contains(&low) {
+                            return Err("unpaired surrogate".to_owned());
+                        }
+                        code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                    }
+                    s.push(::std::char::from_u32(code)
+                        .ok_or_else(|| "unpaired surrogate".to_owned())?);
+                },
+                _ => return Err("invalid escape".to_owned()),
+            },
+            Some(c) if (c as u32) < 0x20 => return Err("control character in string".to_owned()),
+            Some(c) => s.push(c),
+        }
+    }
+}
+
+fn parse_hex4(chars: &mut Peekable<Chars>) -> Result<u32, String> {
+    let code: String = chars.by_ref().take(4).collect();
+    if code.len() != 4 {
+        return Err("invalid unicode escape".to_owned());
+    }
+    u32::from_str_radix(&code, 16).map_err(|_| "invalid unicode escape".to_owned())
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Identifies a signal in panic messages and debug output. Every signal gets a
+/// unique id at its creation and can also be given a name with `named`.
+pub struct SignalInfo {
+    id: usize,
+    name: Mutex<Option<String>>,
+    awaiting: AtomicUsize,
+}
+
+impl SignalInfo {
+    pub(crate) fn new() -> Self {
+        SignalInfo {
+            id: NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            name: Mutex::new(None),
+            awaiting: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn name(&self) -> Option<String> {
+        self.name.lock().unwrap().clone()
+    }
+
+    /// Returns the name of the signal, or its id if it has no name.
+    pub fn label(&self) -> String {
+        self.name().unwrap_or_else(|| format!("#{}", self.id))
+    }
+
+    /// Returns the number of processes awaiting the emission of the signal.
+    pub fn awaiting(&self) -> usize {
+        self.awaiting.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn set_name(&self, name: &str) {
+        *self.name.lock().unwrap() = Some(name.to_owned());
+    }
+
+    pub(crate) fn incr_awaiting(&self) {
+        self.awaiting.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn decr_awaiting(&self) {
+        self.awaiting.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Formats a signal for `Debug`, which all the signal types do the same way.
+    pub(crate) fn debug(&self, f: &mut fmt::Formatter, signal_type: &str, present: bool)
+        -> fmt::Result
+    {
+        f.debug_struct(signal_type)
+            .field("id", &self.id)
+            .field("name", &self.name())
+            .field("present", &present)
+            .field("awaiting", &self.awaiting())
+            .finish()
+    }
+}
+
+impl fmt::Display for SignalInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "signal `{}` (#{})", name, self.id),
+            None => write!(f, "signal #{}", self.id),
+        }
+    }
+}
@@ -1,10 +1,16 @@
+use std::sync::{Arc, Mutex};
+
 use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
+use signal::SignalInfo;
 
 pub trait SignalRuntimeRefBase<R>: 'static where R: Runtime {
     /// Returns a bool to indicate if the signal was emitted or not on the current instant.
     fn is_emitted(&self) -> bool;
 
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants;
+
     /// Resets the signal at the beginning of each instant.
     fn reset(&mut self);
 
@@ -23,6 +29,15 @@ pub trait SignalRuntimeRefBase<R>: 'static where R: Runtime {
 pub trait SignalRuntimeRefSt: SignalRuntimeRefBaseSt {
     /// Calls `c` at the first cycle where the signal is present.
     fn on_signal<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+        where C: ContinuationSt<()>
+    {
+        self.on_signal_cancellable(runtime, None, c);
+    }
+
+    /// Same as `on_signal`, but `c` is dropped instead of being called once `token`
+    /// is cancelled.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationSt<()>;
     
     /// Calls `c` only if the signal is present during this cycle.
@@ -33,6 +48,15 @@ pub trait SignalRuntimeRefSt: SignalRuntimeRefBaseSt {
 pub trait SignalRuntimeRefPl: SignalRuntimeRefBasePl {
     /// Calls `c` at the first cycle where the signal is present.
     fn on_signal<C>(&mut self, runtime: &mut ParallelRuntime, c: C)
+        where C: ContinuationPl<()>
+    {
+        self.on_signal_cancellable(runtime, None, c);
+    }
+
+    /// Same as `on_signal`, but `c` is dropped instead of being called once `token`
+    /// is cancelled.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut ParallelRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationPl<()>;
     
     /// Calls `c` only if the signal is present during this cycle.
@@ -45,3 +69,182 @@ pub trait SignalRuntimeRefBasePl: SignalRuntimeRefBase<ParallelRuntime> + Send {
 
 impl<S> SignalRuntimeRefBaseSt for S where S: SignalRuntimeRefBase<SingleThreadRuntime> {}
 impl<S> SignalRuntimeRefBasePl for S where S: SignalRuntimeRefBase<ParallelRuntime> + Send {}
+
+/// Shared by the awaits of a process on several signals, like the await of a merged
+/// signal. The first one to be woken up cancels the token, and the other awaits are
+/// then dropped by their signals instead of staying registered until they are emitted.
+#[derive(Clone, Default)]
+pub struct AwaitToken(Arc<Mutex<AwaitTokenState>>);
+
+#[derive(Default)]
+struct AwaitTokenState {
+    cancelled: bool,
+    // The number of awaits with this token still registered in a signal.
+    registered: usize,
+}
+
+impl AwaitToken {
+    /// Cancels the token, and returns the number of awaits still registered with it
+    /// if it was not already cancelled.
+    pub(crate) fn cancel(&self) -> Option<usize> {
+        let mut state = self.0.lock().unwrap();
+        if state.cancelled {
+            None
+        } else {
+            state.cancelled = true;
+            Some(state.registered)
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.0.lock().unwrap().cancelled
+    }
+
+    fn register(&self) -> bool {
+        let mut state = self.0.lock().unwrap();
+        if !state.cancelled {
+            state.registered += 1;
+        }
+        !state.cancelled
+    }
+
+    fn unregister(&self) -> bool {
+        let mut state = self.0.lock().unwrap();
+        state.registered -= 1;
+        !state.cancelled
+    }
+}
+
+/// The await counters of a runtime, which are updated by the signals.
+pub(crate) trait AwaitCounters {
+    fn incr_await_counter(&mut self);
+    fn decr_await_counter(&mut self);
+    fn cancelled_await_woken(&mut self);
+}
+
+impl AwaitCounters for SingleThreadRuntime {
+    fn incr_await_counter(&mut self) {
+        SingleThreadRuntime::incr_await_counter(self);
+    }
+
+    fn decr_await_counter(&mut self) {
+        SingleThreadRuntime::decr_await_counter(self);
+    }
+
+    fn cancelled_await_woken(&mut self) {
+        SingleThreadRuntime::cancelled_await_woken(self);
+    }
+}
+
+impl AwaitCounters for ParallelRuntime {
+    fn incr_await_counter(&mut self) {
+        ParallelRuntime::incr_await_counter(self);
+    }
+
+    fn decr_await_counter(&mut self) {
+        ParallelRuntime::decr_await_counter(self);
+    }
+
+    fn cancelled_await_woken(&mut self) {
+        ParallelRuntime::cancelled_await_woken(self);
+    }
+}
+
+/// The continuations awaiting the emission of a signal, with their tokens if they
+/// can be cancelled. The cancelled ones are dropped at the next registration.
+pub(crate) struct AwaitWorks<C>(Vec<(Option<AwaitToken>, C)>);
+
+impl<C> AwaitWorks<C> {
+    pub(crate) fn new() -> Self {
+        AwaitWorks(Vec::new())
+    }
+
+    /// Registers `c` unless its token is already cancelled, and updates the await
+    /// counters.
+    pub(crate) fn register<R>(&mut self, runtime: &mut R, info: &SignalInfo,
+                              token: Option<&AwaitToken>, c: C)
+        where R: AwaitCounters
+    {
+        self.0.retain(|(token, _)| match *token {
+            Some(ref token) if token.is_cancelled() => {
+                token.unregister();
+                runtime.decr_await_counter();
+                runtime.cancelled_await_woken();
+                info.decr_awaiting();
+                false
+            },
+            _ => true,
+        });
+        // An await cancelled before being registered is not counted, so `c` is
+        // simply dropped.
+        if token.is_none_or(AwaitToken::register) {
+            runtime.incr_await_counter();
+            info.incr_awaiting();
+            self.0.push((token.cloned(), c));
+        }
+    }
+
+    /// Returns the next continuation to wake up, dropping the cancelled ones.
+    pub(crate) fn wake<R>(&mut self, runtime: &mut R, info: &SignalInfo) -> Option<C>
+        where R: AwaitCounters
+    {
+        while let Some((token, c)) = self.0.pop() {
+            runtime.decr_await_counter();
+            info.decr_awaiting();
+            if token.is_none_or(|token| token.unregister()) {
+                return Some(c);
+            }
+            runtime.cancelled_await_woken();
+        }
+        None
+    }
+}
+
+/// Keeps track of the last two instants at which a signal was emitted, which is
+/// enough to know the status of the signal at the previous instant even when it
+/// is already emitted at the current one.
+#[derive(Clone, Copy, Default)]
+pub struct EmissionInstants {
+    last: Option<usize>,
+    previous: Option<usize>,
+}
+
+impl EmissionInstants {
+    /// Records an emission at the given instant.
+    pub fn record(&mut self, instant: usize) {
+        if self.last != Some(instant) {
+            self.previous = self.last;
+            self.last = Some(instant);
+        }
+    }
+
+    /// Returns the last instant at which the signal was emitted.
+    pub fn last(&self) -> Option<usize> {
+        self.last
+    }
+
+    /// Returns the last instant strictly before `instant` at which the signal was emitted.
+    pub fn last_before(&self, instant: usize) -> Option<usize> {
+        match self.last {
+            Some(last) if last >= instant => self.previous,
+            last => last,
+        }
+    }
+
+    /// Returns the last instants at which one of the two signals was emitted.
+    pub fn merge(&self, other: &EmissionInstants) -> EmissionInstants {
+        let mut instants: Vec<usize> = [self.previous, self.last, other.previous, other.last]
+            .iter().filter_map(|&i| i).collect();
+        instants.sort();
+        let mut merged = EmissionInstants::default();
+        for instant in instants {
+            merged.record(instant);
+        }
+        merged
+    }
+
+    /// Indicates if the signal was present at the instant before `instant`.
+    pub fn present_before(&self, instant: usize) -> bool {
+        instant > 0 && self.last_before(instant) == Some(instant - 1)
+    }
+}
@@ -4,7 +4,11 @@ mod pure_signal;
 mod mpmc_signal;
 mod mpsc_signal;
 mod spmc_signal;
+mod register_signal;
+mod signal_array;
 pub use self::pure_signal::PureSignalSt;
 pub use self::mpmc_signal::MpmcSignalSt;
 pub use self::mpsc_signal::MpscSignalSt;
 pub use self::spmc_signal::SpmcSignalSt;
+pub use self::register_signal::RegisterSignalSt;
+pub use self::signal_array::{SignalArraySt, AwaitAnyImmediateSt};
@@ -1,11 +1,17 @@
+use std::fmt;
+use std::mem;
+use std::collections::VecDeque;
 use std::rc::Rc;
 use std::cell::RefCell;
 
 use runtime::SingleThreadRuntime;
 use continuation::ContinuationSt;
-use signal::Signal;
-use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefSt};
-use signal::valued_signal::{ValuedSignal, MpSignal, CanEmit, GetValue};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefSt};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
+use signal::valued_signal::{ValuedSignal, MpSignal, CanEmit, GetValue, GetPreValue, GetHistory};
+use signal::valued_signal::{CanHandleEach, EachEmissionsSt, HandlerSt};
+use signal::valued_signal::{Gather, Monoid, FromMonoid, Strategy};
 
 /// A shared pointer to a signal runtime.
 pub struct MpmcSignalRuntimeRef<B, F> {
@@ -20,30 +26,61 @@ impl<B, F> Clone for MpmcSignalRuntimeRef<B, F> {
 
 /// Runtime for multi-producer, multi-consumer signals.
 struct MpmcSignalRuntime<B, F> {
+    info: SignalInfo,
     emitted: RefCell<bool>,
     default_value: B,
     gather: RefCell<F>,
+    finish: fn(&mut F, &mut B),
     value: RefCell<B>,
     last_value: RefCell<B>,
     last_value_updated: RefCell<bool>,
-    await_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
+    emissions: RefCell<EmissionInstants>,
+    each: EachEmissionsSt,
+    history: RefCell<VecDeque<(usize, B)>>,
+    history_capacity: usize,
+    await_works: RefCell<AwaitWorks<Box<ContinuationSt<()>>>>,
     present_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
 }
 
 impl<B, F> MpmcSignalRuntime<B, F> where B: Clone {
     /// Returns a new instance of SignalRuntime.
-    fn new<A>(default: B, gather: F) -> Self where F: FnMut(A, &mut B) {
+    fn new<A>(default: B, gather: F, history_capacity: usize) -> Self
+        where F: Gather<A, B>
+    {
         MpmcSignalRuntime {
+            info: SignalInfo::new(),
             emitted: RefCell::new(false),
             default_value: default.clone(),
             gather: RefCell::new(gather),
+            finish: <F as Gather<A, B>>::finish,
             value: RefCell::new(default.clone()),
             last_value: RefCell::new(default),
             last_value_updated: RefCell::new(false),
-            await_works: RefCell::new(Vec::new()),
+            emissions: RefCell::new(EmissionInstants::default()),
+            each: EachEmissionsSt::new(),
+            history: RefCell::new(VecDeque::with_capacity(history_capacity)),
+            history_capacity,
+            await_works: RefCell::new(AwaitWorks::new()),
             present_works: RefCell::new(Vec::new()),
         }
     }
+
+    /// Lets the gather finish combining the values emitted during the instant.
+    fn finish_gather(&self) {
+        (self.finish)(&mut *self.gather.borrow_mut(), &mut *self.value.borrow_mut());
+    }
+
+    /// Records the value emitted at `instant` in the history if it is enabled,
+    /// dropping the oldest value when the history is full.
+    fn push_history(&self, instant: usize, value: B) {
+        if self.history_capacity > 0 {
+            let mut history = self.history.borrow_mut();
+            if history.len() == self.history_capacity {
+                history.pop_front();
+            }
+            history.push_back((instant, value));
+        }
+    }
 }
 
 impl<B, F> SignalRuntimeRefBase<SingleThreadRuntime> for MpmcSignalRuntimeRef<B, F>
@@ -54,11 +91,19 @@ impl<B, F> SignalRuntimeRefBase<SingleThreadRuntime> for MpmcSignalRuntimeRef<B,
         *self.runtime.emitted.borrow()
     }
 
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.borrow()
+    }
+
     /// Resets the signal at the beginning of each instant.
     fn reset(&mut self) {
         if self.is_emitted() {
             *self.runtime.emitted.borrow_mut() = false;
-            *self.runtime.value.borrow_mut() = self.runtime.default_value.clone();
+            self.runtime.finish_gather();
+            let value = mem::replace(
+                &mut *self.runtime.value.borrow_mut(), self.runtime.default_value.clone());
+            self.runtime.push_history(self.emissions().last().unwrap(), value);
             *self.runtime.last_value_updated.borrow_mut() = false;
         }
     }
@@ -76,14 +121,14 @@ impl<B, F> SignalRuntimeRefSt for MpmcSignalRuntimeRef<B, F>
     where B: Clone + 'static, F: 'static
 {
     /// Calls `c` at the first cycle where the signal is present.
-    fn on_signal<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationSt<()>
     {
         if self.is_emitted() {
             c.call(runtime, ());
         } else {
-            runtime.incr_await_counter();
-            self.runtime.await_works.borrow_mut().push(Box::new(c));
+            self.runtime.await_works.borrow_mut().register(runtime, &self.runtime.info, token, Box::new(c));
         }
     }
     
@@ -100,20 +145,21 @@ impl<B, F> SignalRuntimeRefSt for MpmcSignalRuntimeRef<B, F>
 }
 
 impl<A, B, F> CanEmit<SingleThreadRuntime, A> for MpmcSignalRuntimeRef<B, F>
-    where A: 'static, B: Clone + 'static, F: FnMut(A, &mut B) + 'static
+    where A: 'static, B: Clone + 'static, F: Gather<A, B> + 'static
 {
     fn emit(&mut self, runtime: &mut SingleThreadRuntime, emitted: A) {
         *self.runtime.emitted.borrow_mut() = true;
+        self.runtime.each.record(runtime, &self.runtime.info, &emitted);
         {
             let mut v = self.runtime.value.borrow_mut();
             let gather = &mut *self.runtime.gather.borrow_mut();
-            gather(emitted, &mut v);
+            gather.gather(emitted, &mut v);
         }
-        while let Some(c) = self.runtime.await_works.borrow_mut().pop() {
-            runtime.decr_await_counter();
+        while let Some(c) = self.runtime.await_works.borrow_mut().wake(runtime, &self.runtime.info) {
             c.call_box(runtime, ());
         }
         self.execute_present_works(runtime);
+        self.runtime.emissions.borrow_mut().record(runtime.current_instant());
         runtime.emit_signal(Box::new(self.clone()));
         let signal_ref = self.clone();
         let update_last_value = move |_: &mut SingleThreadRuntime, ()| {
@@ -126,19 +172,48 @@ impl<A, B, F> CanEmit<SingleThreadRuntime, A> for MpmcSignalRuntimeRef<B, F>
     }
 }
 
+impl<A, B, F> CanHandleEach<SingleThreadRuntime, A, HandlerSt<A>> for MpmcSignalRuntimeRef<B, F>
+    where A: Clone + 'static, F: Gather<A, B>
+{
+    fn on_each(&mut self, runtime: &mut SingleThreadRuntime, handler: Box<HandlerSt<A>>) -> usize {
+        self.runtime.each.add_handler(runtime, &self.runtime.info, handler)
+    }
+}
+
 impl<B, F> GetValue<B> for MpmcSignalRuntimeRef<B, F> where B: Clone {
     /// Returns the value of the signal for the current instant.
     /// The returned value is cloned and can thus be used directly.
     fn get_value(&self) -> B {
+        self.runtime.finish_gather();
         self.runtime.value.borrow().clone()
     }
 }
 
+impl<B, F> GetPreValue for MpmcSignalRuntimeRef<B, F> where B: Clone {
+    type PreValue = B;
+
+    /// Returns the value of the signal at its last emission before the current instant,
+    /// or the default value if the signal has never been emitted.
+    fn get_pre_value(&self) -> B {
+        self.runtime.last_value.borrow().clone()
+    }
+}
+
+impl<B, F> GetHistory<B> for MpmcSignalRuntimeRef<B, F> where B: Clone {
+    /// Returns the values recorded in the history with the instants of their emission,
+    /// the oldest first.
+    fn get_history(&self) -> Vec<(usize, B)> {
+        self.runtime.history.borrow().iter().cloned().collect()
+    }
+}
+
 impl<B, F> MpmcSignalRuntimeRef<B, F> where B: Clone + 'static, F: 'static {
     /// Returns a new instance of SignalRuntimeRef.
-    fn new<A>(default: B, gather: F) -> Self where F: FnMut(A, &mut B) {
+    fn new<A>(default: B, gather: F, history_capacity: usize) -> Self
+        where F: Gather<A, B>
+    {
         MpmcSignalRuntimeRef {
-            runtime: Rc::new(MpmcSignalRuntime::new(default, gather)),
+            runtime: Rc::new(MpmcSignalRuntime::new(default, gather, history_capacity)),
         }
     }
 }
@@ -158,6 +233,10 @@ impl<B, F> Signal for MpmcSignalSt<B, F> where B: Clone + 'static, F: 'static {
     fn runtime(&self) -> MpmcSignalRuntimeRef<B, F> {
         self.0.clone()
     }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
 }
 
 impl<B, F> ValuedSignal for MpmcSignalSt<B, F> where B: Clone + 'static, F: 'static {
@@ -165,10 +244,25 @@ impl<B, F> ValuedSignal for MpmcSignalSt<B, F> where B: Clone + 'static, F: 'sta
     type SigType = MpSignal;
 }
 
+impl<B, F> fmt::Debug for MpmcSignalSt<B, F> where B: Clone + 'static, F: 'static {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "MpmcSignalSt", self.0.is_emitted())
+    }
+}
+
 impl<B, F> MpmcSignalSt<B, F> where B: Clone + 'static, F: 'static {
+    signal_info_methods!();
+
     /// Creates a new mpmc signal.
     pub fn new<A>(default: B, gather: F) -> Self where A: 'static, F: FnMut(A, &mut B) {
-        MpmcSignalSt(MpmcSignalRuntimeRef::new(default, gather))
+        MpmcSignalSt(MpmcSignalRuntimeRef::new(default, gather, 0))
+    }
+
+    /// Creates a new mpmc signal which keeps the values of its last `capacity` emissions.
+    pub fn with_history<A>(default: B, gather: F, capacity: usize) -> Self
+        where A: 'static, F: FnMut(A, &mut B)
+    {
+        MpmcSignalSt(MpmcSignalRuntimeRef::new(default, gather, capacity))
     }
 
     /// Returns the last value associated to the signal when it was emitted.
@@ -189,4 +283,18 @@ impl MpmcSignalSt<(), ()> {
         }
         MpmcSignalSt::new(Vec::new(), gather)
     }
+
+    /// Creates a new mpmc signal whose values are combined with a monoid, starting from
+    /// its identity.
+    pub fn from_monoid<B>() -> MpmcSignalSt<B, FromMonoid<B>> where B: Monoid + Clone + 'static {
+        MpmcSignalSt(MpmcSignalRuntimeRef::new::<B>(B::identity(), FromMonoid::new(), 0))
+    }
+
+    /// Creates a new mpmc signal combining its values with one of the ready-made
+    /// strategies of the module `strategies`.
+    pub fn from_strategy<A, B, S>(strategy: S) -> MpmcSignalSt<B, S>
+        where A: 'static, B: Clone + 'static, S: Strategy<A, B> + 'static
+    {
+        MpmcSignalSt(MpmcSignalRuntimeRef::new::<A>(S::initial(), strategy, 0))
+    }
 }
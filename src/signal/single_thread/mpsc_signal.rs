@@ -1,11 +1,15 @@
+use std::fmt;
 use std::rc::Rc;
 use std::cell::RefCell;
 
 use runtime::SingleThreadRuntime;
 use continuation::ContinuationSt;
-use signal::Signal;
-use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefSt};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefSt};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
 use signal::valued_signal::{ValuedSignal, MpSignal, CanEmit, GetValue};
+use signal::valued_signal::{CanHandleEach, EachEmissionsSt, HandlerSt};
+use signal::valued_signal::{Gather, Strategy};
 
 /// A shared pointer to a signal runtime.
 pub struct MpscSignalRuntimeRef<B, D, F> {
@@ -22,28 +26,46 @@ impl<B, D, F> Clone for MpscSignalRuntimeRef<B, D, F> {
 /// Since we're not always able to clone the default value, we need a function
 /// that produces the default value each time when it's called.
 struct MpscSignalRuntime<B, D, F> {
+    info: SignalInfo,
     emitted: RefCell<bool>,
     get_default: D,
     gather: RefCell<F>,
+    finish: fn(&mut F, &mut B),
     value: RefCell<Option<B>>,
-    await_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
+    emissions: RefCell<EmissionInstants>,
+    each: EachEmissionsSt,
+    await_works: RefCell<AwaitWorks<Box<ContinuationSt<()>>>>,
     present_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
 }
 
 impl<B, D, F> MpscSignalRuntime<B, D, F> where D: Fn() -> B {
     /// Returns a new instance of SignalRuntime.
-    fn new<A>(get_default: D, gather: F) -> Self where F: FnMut(A, &mut B) {
+    fn new<A>(get_default: D, gather: F) -> Self where F: Gather<A, B> {
         MpscSignalRuntime {
+            info: SignalInfo::new(),
             emitted: RefCell::new(false),
             value: RefCell::new(Some(get_default())),
             get_default: get_default,
             gather: RefCell::new(gather),
-            await_works: RefCell::new(Vec::new()),
+            finish: <F as Gather<A, B>>::finish,
+            emissions: RefCell::new(EmissionInstants::default()),
+            each: EachEmissionsSt::new(),
+            await_works: RefCell::new(AwaitWorks::new()),
             present_works: RefCell::new(Vec::new()),
         }
     }
 }
 
+impl<B, D, F> MpscSignalRuntime<B, D, F> {
+    /// Lets the gather finish combining the values emitted during the instant.
+    fn finish_gather(&self) {
+        let gather = &mut *self.gather.borrow_mut();
+        if let Some(v) = self.value.borrow_mut().as_mut() {
+            (self.finish)(gather, v);
+        }
+    }
+}
+
 impl<B, D, F> SignalRuntimeRefBase<SingleThreadRuntime> for MpscSignalRuntimeRef<B, D, F>
     where B: 'static, D: Fn() -> B + 'static, F: 'static
 {
@@ -52,10 +74,18 @@ impl<B, D, F> SignalRuntimeRefBase<SingleThreadRuntime> for MpscSignalRuntimeRef
         *self.runtime.emitted.borrow()
     }
 
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.borrow()
+    }
+
     /// Resets the signal at the beginning of each instant.
     fn reset(&mut self) {
         if self.is_emitted() {
             *self.runtime.emitted.borrow_mut() = false;
+            // Also clears the values that were not combined because the value
+            // of the signal was not read.
+            self.runtime.finish_gather();
             *self.runtime.value.borrow_mut() = Some((self.runtime.get_default)());
         }
     }
@@ -73,14 +103,14 @@ impl<B, D, F> SignalRuntimeRefSt for MpscSignalRuntimeRef<B, D, F>
     where B: 'static, D: Fn() -> B + 'static, F: 'static
 {
     /// Calls `c` at the first cycle where the signal is present.
-    fn on_signal<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationSt<()>
     {
         if self.is_emitted() {
             c.call(runtime, ());
         } else {
-            runtime.incr_await_counter();
-            self.runtime.await_works.borrow_mut().push(Box::new(c));
+            self.runtime.await_works.borrow_mut().register(runtime, &self.runtime.info, token, Box::new(c));
         }
     }
     
@@ -100,32 +130,45 @@ impl<A, B, D, F> CanEmit<SingleThreadRuntime, A> for MpscSignalRuntimeRef<B, D,
     where A: 'static,
           B: 'static,
           D: Fn() -> B + 'static,
-          F: FnMut(A, &mut B) + 'static,
+          F: Gather<A, B> + 'static,
 {
     fn emit(&mut self, runtime: &mut SingleThreadRuntime, emitted: A) {
         *self.runtime.emitted.borrow_mut() = true;
+        self.runtime.each.record(runtime, &self.runtime.info, &emitted);
         {
             let gather = &mut *self.runtime.gather.borrow_mut();
             match self.runtime.value.borrow_mut().as_mut() {
-                Some(v) => gather(emitted, v),
-                None => assert!(false),
+                Some(v) => gather.gather(emitted, v),
+                None => panic!(
+                    "Trying to emit the mpsc {} after its value was taken inside an instant.",
+                    self.runtime.info),
             }
         }
-        while let Some(c) = self.runtime.await_works.borrow_mut().pop() {
-            runtime.decr_await_counter();
+        while let Some(c) = self.runtime.await_works.borrow_mut().wake(runtime, &self.runtime.info) {
             c.call_box(runtime, ());
         }
         self.execute_present_works(runtime);
+        self.runtime.emissions.borrow_mut().record(runtime.current_instant());
         runtime.emit_signal(Box::new(self.clone()));
     }
 }
 
+impl<A, B, D, F> CanHandleEach<SingleThreadRuntime, A, HandlerSt<A>> for MpscSignalRuntimeRef<B, D, F>
+    where A: Clone + 'static, F: Gather<A, B>
+{
+    fn on_each(&mut self, runtime: &mut SingleThreadRuntime, handler: Box<HandlerSt<A>>) -> usize {
+        self.runtime.each.add_handler(runtime, &self.runtime.info, handler)
+    }
+}
+
 impl<B, D, F> GetValue<B> for MpscSignalRuntimeRef<B, D, F> {
     /// Returns the value of the signal for the current instant.
     /// This function can only be called once at each instant.
     fn get_value(&self) -> B {
-        self.runtime.value.borrow_mut().take().expect(
-            "Trying to get the value of a mpsc signal more than once inside an instant.")
+        self.runtime.finish_gather();
+        self.runtime.value.borrow_mut().take().unwrap_or_else(|| panic!(
+            "Trying to get the value of the mpsc {} more than once inside an instant.",
+            self.runtime.info))
     }
 }
 
@@ -133,7 +176,7 @@ impl<B, D, F> MpscSignalRuntimeRef<B, D, F>
     where B: 'static, D: Fn() -> B + 'static, F: 'static
 {
     /// Returns a new instance of SignalRuntimeRef.
-    fn new<A>(get_default: D, gather: F) -> Self where F: FnMut(A, &mut B) {
+    fn new<A>(get_default: D, gather: F) -> Self where F: Gather<A, B> {
         MpscSignalRuntimeRef {
             runtime: Rc::new(MpscSignalRuntime::new(get_default, gather)),
         }
@@ -157,6 +200,10 @@ impl<B, D, F> Signal for MpscSignalSt<B, D, F>
     fn runtime(&self) -> MpscSignalRuntimeRef<B, D, F> {
         self.0.clone()
     }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
 }
 
 impl<B, D, F> ValuedSignal for MpscSignalSt<B, D, F>
@@ -166,7 +213,15 @@ impl<B, D, F> ValuedSignal for MpscSignalSt<B, D, F>
     type SigType = MpSignal;
 }
     
+impl<B, D, F> fmt::Debug for MpscSignalSt<B, D, F> where B: 'static, D: Fn() -> B + 'static, F: 'static {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "MpscSignalSt", self.0.is_emitted())
+    }
+}
+
 impl<B, D, F> MpscSignalSt<B, D, F> where B: 'static, D: Fn() -> B + 'static, F: 'static {
+    signal_info_methods!();
+
     /// Creates a new mpmc signal.
     pub fn new<A>(get_default: D, gather: F) -> Self where A: 'static, F: FnMut(A, &mut B) {
         MpscSignalSt(MpscSignalRuntimeRef::new(get_default, gather))
@@ -184,4 +239,12 @@ impl MpscSignalSt<(), (), ()> {
         }
         MpscSignalSt::new(Vec::new, gather)
     }
+
+    /// Creates a new mpsc signal combining its values with one of the ready-made
+    /// strategies of the module `strategies`.
+    pub fn from_strategy<A, B, S>(strategy: S) -> MpscSignalSt<B, fn() -> B, S>
+        where A: 'static, B: 'static, S: Strategy<A, B> + 'static
+    {
+        MpscSignalSt(MpscSignalRuntimeRef::new::<A>(S::initial, strategy))
+    }
 }
@@ -1,13 +1,15 @@
 //! A reactive signal without value.
 
+use std::fmt;
 use std::rc::Rc;
 use std::cell::RefCell;
 
 use runtime::SingleThreadRuntime;
 use continuation::ContinuationSt;
 use process::{ProcessSt, ProcessMutSt};
-use signal::Signal;
-use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefSt};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefSt};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
 use signal::pure_signal::{PureSignal, Emit, TryEmit};
 
 /// A shared pointer to a signal runtime.
@@ -18,8 +20,10 @@ pub struct PureSignalRuntimeRef {
 
 /// Runtime for pure signals.
 struct PureSignalRuntime {
+    info: SignalInfo,
     emitted: RefCell<bool>,
-    await_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
+    emissions: RefCell<EmissionInstants>,
+    await_works: RefCell<AwaitWorks<Box<ContinuationSt<()>>>>,
     present_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
 }
 
@@ -27,8 +31,10 @@ impl PureSignalRuntime {
     /// Returns a new instance of SignalRuntime.
     fn new() -> Self {
         PureSignalRuntime {
+            info: SignalInfo::new(),
             emitted: RefCell::new(false),
-            await_works: RefCell::new(Vec::new()),
+            emissions: RefCell::new(EmissionInstants::default()),
+            await_works: RefCell::new(AwaitWorks::new()),
             present_works: RefCell::new(Vec::new()),
         }
     }
@@ -40,6 +46,11 @@ impl SignalRuntimeRefBase<SingleThreadRuntime> for PureSignalRuntimeRef {
         *self.runtime.emitted.borrow()
     }
 
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.borrow()
+    }
+
     /// Resets the signal at the beginning of each instant.
     fn reset(&mut self) {
         *self.runtime.emitted.borrow_mut() = false;
@@ -56,14 +67,14 @@ impl SignalRuntimeRefBase<SingleThreadRuntime> for PureSignalRuntimeRef {
 
 impl SignalRuntimeRefSt for PureSignalRuntimeRef {
     /// Calls `c` at the first cycle where the signal is present.
-    fn on_signal<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationSt<()>
     {
         if *self.runtime.emitted.borrow() {
             c.call(runtime, ());
         } else {
-            runtime.incr_await_counter();
-            self.runtime.await_works.borrow_mut().push(Box::new(c));
+            self.runtime.await_works.borrow_mut().register(runtime, &self.runtime.info, token, Box::new(c));
         }
     }
     
@@ -88,11 +99,11 @@ impl PureSignalRuntimeRef {
     /// Sets the signal as emitted for the current instant.
     fn emit(&mut self, runtime: &mut SingleThreadRuntime) {
         *self.runtime.emitted.borrow_mut() = true;
-        while let Some(c) = self.runtime.await_works.borrow_mut().pop() {
-            runtime.decr_await_counter();
+        while let Some(c) = self.runtime.await_works.borrow_mut().wake(runtime, &self.runtime.info) {
             c.call_box(runtime, ());
         }
         self.execute_present_works(runtime);
+        self.runtime.emissions.borrow_mut().record(runtime.current_instant());
         runtime.emit_signal(Box::new(self.clone()));
     }
 
@@ -117,6 +128,10 @@ impl Signal for PureSignalSt {
     fn runtime(&self) -> PureSignalRuntimeRef {
         self.0.clone()
     }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
 }
 
 impl PureSignal for PureSignalSt {
@@ -125,6 +140,16 @@ impl PureSignal for PureSignalSt {
     }
 }
 
+impl fmt::Debug for PureSignalSt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "PureSignalSt", self.0.is_emitted())
+    }
+}
+
+impl PureSignalSt {
+    signal_info_methods!();
+}
+
 /* Emit */
 
 impl ProcessSt for Emit<PureSignalSt> {
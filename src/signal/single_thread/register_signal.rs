@@ -0,0 +1,203 @@
+use std::rc::Rc;
+use std::fmt;
+use std::cell::RefCell;
+
+use runtime::SingleThreadRuntime;
+use continuation::ContinuationSt;
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefSt};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
+use signal::valued_signal::{ValuedSignal, SpSignal, CanEmit, GetValue, GetPreValue, PreValue};
+
+/// A shared pointer to a signal runtime.
+pub struct RegisterSignalRuntimeRef<V> {
+    runtime: Rc<RegisterSignalRuntime<V>>,
+}
+
+impl<V> Clone for RegisterSignalRuntimeRef<V> {
+    fn clone(&self) -> Self { 
+        RegisterSignalRuntimeRef { runtime: self.runtime.clone() }
+    }
+}
+
+/// Runtime for register signals.
+struct RegisterSignalRuntime<V> {
+    info: SignalInfo,
+    value: RefCell<Option<V>>,
+    stored: RefCell<V>,
+    emissions: RefCell<EmissionInstants>,
+    await_works: RefCell<AwaitWorks<Box<ContinuationSt<()>>>>,
+    present_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
+}
+
+impl<V> RegisterSignalRuntime<V> where V: Clone {
+    /// Returns a new instance of SignalRuntime.
+    fn new(initial: V) -> Self {
+        RegisterSignalRuntime {
+            info: SignalInfo::new(),
+            value: RefCell::new(None),
+            stored: RefCell::new(initial),
+            emissions: RefCell::new(EmissionInstants::default()),
+            await_works: RefCell::new(AwaitWorks::new()),
+            present_works: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl<V> SignalRuntimeRefBase<SingleThreadRuntime> for RegisterSignalRuntimeRef<V>
+    where V: Clone + 'static
+{
+    /// Returns a bool to indicate if the signal was emitted or not on the current instant.
+    fn is_emitted(&self) -> bool {
+        self.runtime.value.borrow().is_some()
+    }
+
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.borrow()
+    }
+
+    /// Resets the signal at the beginning of each instant. The value emitted during
+    /// the instant is kept in the register.
+    fn reset(&mut self) {
+        let value = self.runtime.value.borrow_mut().take();
+        if let Some(value) = value {
+            *self.runtime.stored.borrow_mut() = value;
+        }
+    }
+
+    /// Exececutes all the continuations found in the vector `self.present_works`.
+    fn execute_present_works(&mut self, runtime: &mut SingleThreadRuntime) {
+        while let Some(c) = self.runtime.present_works.borrow_mut().pop() {
+            c.call_box(runtime, ());
+        }
+    }
+}
+
+
+impl<V> SignalRuntimeRefSt for RegisterSignalRuntimeRef<V> where V: Clone + 'static {
+    /// Calls `c` at the first cycle where the signal is present.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationSt<()>
+    {
+        if self.is_emitted() {
+            c.call(runtime, ());
+        } else {
+            self.runtime.await_works.borrow_mut().register(runtime, &self.runtime.info, token, Box::new(c));
+        }
+    }
+
+    /// Calls `c` only if the signal is present during this cycle.
+    fn on_signal_present<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+        where C: ContinuationSt<()>
+    {
+        if self.is_emitted() {
+            c.call(runtime, ());
+        } else {
+            self.runtime.present_works.borrow_mut().push(Box::new(c));
+        }
+    }
+}
+
+impl<V> CanEmit<SingleThreadRuntime, V> for RegisterSignalRuntimeRef<V>
+    where V: Clone + 'static
+{
+    fn emit(&mut self, runtime: &mut SingleThreadRuntime, emitted: V) {
+        if self.is_emitted() {
+            panic!("Multiple emissions of the single-producer {} inside an instant.",
+                   self.runtime.info);
+        }
+        *self.runtime.value.borrow_mut() = Some(emitted);
+        while let Some(c) = self.runtime.await_works.borrow_mut().wake(runtime, &self.runtime.info) {
+            c.call_box(runtime, ());
+        }
+        self.execute_present_works(runtime);
+        self.runtime.emissions.borrow_mut().record(runtime.current_instant());
+        runtime.emit_signal(Box::new(self.clone()));
+    }
+}
+
+impl<V> GetValue<V> for RegisterSignalRuntimeRef<V> where V: Clone {
+    /// Returns the value of the signal for the current instant.
+    /// The returned value is cloned and can thus be used directly.
+    fn get_value(&self) -> V {
+        self.runtime.value.borrow().clone().unwrap_or_else(
+            || panic!("Trying to get the value of the {} which is not emitted.", self.runtime.info))
+    }
+}
+
+impl<V> GetPreValue for RegisterSignalRuntimeRef<V> where V: Clone {
+    type PreValue = V;
+
+    /// Returns the value kept in the register, that is the value of the signal at
+    /// its last emission before the current instant or the initial value.
+    fn get_pre_value(&self) -> V {
+        self.runtime.stored.borrow().clone()
+    }
+}
+
+impl<V> RegisterSignalRuntimeRef<V> where V: Clone + 'static {
+    /// Returns a new instance of SignalRuntimeRef.
+    fn new(initial: V) -> Self {
+        RegisterSignalRuntimeRef { runtime: Rc::new(RegisterSignalRuntime::new(initial)) }
+    }
+}
+
+/// A non-parallel register signal.
+///
+/// This is a single-producer signal whose value persists across instants until
+/// it is overwritten by another emission, while its presence is still reset at
+/// each instant. The value of an emission is kept in the register at the end of
+/// the instant, so all the processes reading the register during an instant see
+/// the same value.
+pub struct RegisterSignalSt<V>(RegisterSignalRuntimeRef<V>);
+
+impl<V> Clone for RegisterSignalSt<V> {
+    fn clone(&self) -> Self {
+        RegisterSignalSt(self.0.clone())
+    }
+}
+
+impl<V> Signal for RegisterSignalSt<V> where V: Clone + 'static {
+    type RuntimeRef = RegisterSignalRuntimeRef<V>;
+    
+    fn runtime(&self) -> RegisterSignalRuntimeRef<V> {
+        self.0.clone()
+    }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
+}
+
+impl<V> ValuedSignal for RegisterSignalSt<V> where V: Clone + 'static {
+    type Stored = V;
+    type SigType = SpSignal;
+}
+
+impl<V> fmt::Debug for RegisterSignalSt<V> where V: Clone + 'static {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "RegisterSignalSt", self.0.is_emitted())
+    }
+}
+
+impl<V> RegisterSignalSt<V> where V: Clone + 'static {
+    signal_info_methods!();
+
+    /// Creates a new register signal holding `initial` until its first emission.
+    pub fn new(initial: V) -> Self {
+        RegisterSignalSt(RegisterSignalRuntimeRef::new(initial))
+    }
+
+    /// Returns a process that gets immediately the value kept in the register.
+    /// This is the same as `pre_value`.
+    pub fn read(&self) -> PreValue<Self> {
+        self.pre_value()
+    }
+
+    /// Returns the value kept in the register.
+    pub fn value(&self) -> V {
+        self.0.get_pre_value()
+    }
+}
@@ -0,0 +1,261 @@
+use std::rc::Rc;
+use std::fmt;
+use std::cell::RefCell;
+
+use runtime::SingleThreadRuntime;
+use continuation::ContinuationSt;
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefSt};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
+use signal::valued_signal::{ValuedSignal, MpSignal, CanEmit, GetValue, EmitValue, AwaitValue};
+
+/// A shared pointer to a signal runtime.
+pub struct SignalArrayRuntimeRef<V> {
+    runtime: Rc<SignalArrayRuntime<V>>,
+}
+
+impl<V> Clone for SignalArrayRuntimeRef<V> {
+    fn clone(&self) -> Self { 
+        SignalArrayRuntimeRef { runtime: self.runtime.clone() }
+    }
+}
+
+/// Runtime shared by all the signals of an array.
+struct SignalArrayRuntime<V> {
+    info: SignalInfo,
+    /// The number of signals, which is also the length of `values`.
+    len: usize,
+    values: RefCell<Vec<Option<V>>>,
+    present: RefCell<Vec<usize>>,
+    emissions: RefCell<EmissionInstants>,
+    await_works: RefCell<AwaitWorks<Box<ContinuationSt<()>>>>,
+    present_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
+}
+
+impl<V> SignalArrayRuntime<V> {
+    /// Returns a new instance of SignalRuntime.
+    fn new(n: usize) -> Self {
+        SignalArrayRuntime {
+            info: SignalInfo::new(),
+            len: n,
+            values: RefCell::new((0..n).map(|_| None).collect()),
+            present: RefCell::new(Vec::new()),
+            emissions: RefCell::new(EmissionInstants::default()),
+            await_works: RefCell::new(AwaitWorks::new()),
+            present_works: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl<V> SignalRuntimeRefBase<SingleThreadRuntime> for SignalArrayRuntimeRef<V>
+    where V: Clone + 'static
+{
+    /// Returns a bool to indicate if some signal of the array was emitted or not
+    /// on the current instant.
+    fn is_emitted(&self) -> bool {
+        !self.runtime.present.borrow().is_empty()
+    }
+
+    /// Returns the last instants at which some signal of the array was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.borrow()
+    }
+
+    /// Resets the signals that were emitted during the instant.
+    fn reset(&mut self) {
+        let mut values = self.runtime.values.borrow_mut();
+        for i in self.runtime.present.borrow_mut().drain(..) {
+            values[i] = None;
+        }
+    }
+
+    /// Exececutes all the continuations found in the vector `self.present_works`.
+    fn execute_present_works(&mut self, runtime: &mut SingleThreadRuntime) {
+        while let Some(c) = self.runtime.present_works.borrow_mut().pop() {
+            c.call_box(runtime, ());
+        }
+    }
+}
+
+
+impl<V> SignalRuntimeRefSt for SignalArrayRuntimeRef<V> where V: Clone + 'static {
+    /// Calls `c` at the first cycle where some signal of the array is present.
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
+        where C: ContinuationSt<()>
+    {
+        if self.is_emitted() {
+            c.call(runtime, ());
+        } else {
+            self.runtime.await_works.borrow_mut().register(runtime, &self.runtime.info, token, Box::new(c));
+        }
+    }
+
+    /// Calls `c` only if some signal of the array is present during this cycle.
+    fn on_signal_present<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+        where C: ContinuationSt<()>
+    {
+        if self.is_emitted() {
+            c.call(runtime, ());
+        } else {
+            self.runtime.present_works.borrow_mut().push(Box::new(c));
+        }
+    }
+}
+
+impl<V> CanEmit<SingleThreadRuntime, (usize, V)> for SignalArrayRuntimeRef<V>
+    where V: Clone + 'static
+{
+    fn emit(&mut self, runtime: &mut SingleThreadRuntime, (i, emitted): (usize, V)) {
+        {
+            let mut values = self.runtime.values.borrow_mut();
+            assert!(i < self.runtime.len, "Emission at index {} out of the bounds of the array {}.",
+                    i, self.runtime.info);
+            if values[i].is_some() {
+                panic!("Multiple emissions at index {} of the array {} inside an instant.",
+                       i, self.runtime.info);
+            }
+            values[i] = Some(emitted);
+        }
+        self.runtime.present.borrow_mut().push(i);
+        let first_emission = self.runtime.present.borrow().len() == 1;
+        while let Some(c) = self.runtime.await_works.borrow_mut().wake(runtime, &self.runtime.info) {
+            c.call_box(runtime, ());
+        }
+        self.execute_present_works(runtime);
+        self.runtime.emissions.borrow_mut().record(runtime.current_instant());
+        if first_emission {
+            runtime.emit_signal(Box::new(self.clone()));
+        }
+    }
+}
+
+impl<V> GetValue<Vec<(usize, V)>> for SignalArrayRuntimeRef<V> where V: Clone {
+    /// Returns the indices of the signals emitted during the current instant with their
+    /// values, in increasing order of the indices.
+    fn get_value(&self) -> Vec<(usize, V)> {
+        let values = self.runtime.values.borrow();
+        let mut present = self.runtime.present.borrow().clone();
+        present.sort();
+        present.into_iter().map(|i| (i, values[i].clone().unwrap())).collect()
+    }
+}
+
+impl<V> SignalArrayRuntimeRef<V> {
+    /// Returns a new instance of SignalRuntimeRef.
+    fn new(n: usize) -> Self {
+        SignalArrayRuntimeRef { runtime: Rc::new(SignalArrayRuntime::new(n)) }
+    }
+}
+
+/// A non-parallel array of single-producer signals sharing a single runtime.
+///
+/// The array is considered as a multi-producer signal which is present when one
+/// of its signals is, and whose value is the list of the present signals with
+/// their values.
+pub struct SignalArraySt<V>(SignalArrayRuntimeRef<V>);
+
+impl<V> Clone for SignalArraySt<V> {
+    fn clone(&self) -> Self {
+        SignalArraySt(self.0.clone())
+    }
+}
+
+impl<V> Signal for SignalArraySt<V> where V: Clone + 'static {
+    type RuntimeRef = SignalArrayRuntimeRef<V>;
+    
+    fn runtime(&self) -> SignalArrayRuntimeRef<V> {
+        self.0.clone()
+    }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
+}
+
+impl<V> ValuedSignal for SignalArraySt<V> where V: Clone + 'static {
+    type Stored = Vec<(usize, V)>;
+    type SigType = MpSignal;
+}
+
+impl<V> fmt::Debug for SignalArraySt<V> where V: Clone + 'static {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "SignalArraySt", self.0.is_emitted())
+    }
+}
+
+impl<V> SignalArraySt<V> where V: Clone + 'static {
+    signal_info_methods!();
+
+    /// Creates a new array of `n` signals.
+    pub fn new(n: usize) -> Self {
+        SignalArraySt(SignalArrayRuntimeRef::new(n))
+    }
+
+    /// Returns the number of signals in the array.
+    pub fn len(&self) -> usize {
+        self.0.runtime.len
+    }
+
+    /// Returns `true` if the array contains no signal.
+    pub fn is_empty(&self) -> bool {
+        self.0.runtime.len == 0
+    }
+
+    /// Returns a process that emits the signal of index `i` with value `emitted`.
+    pub fn emit_at(&self, i: usize, emitted: V) -> EmitValue<Self, (usize, V)> {
+        self.emit((i, emitted))
+    }
+
+    /// Waits for some signals of the array to be emitted and gets the indices of
+    /// all the signals present at this instant with their values. As for the
+    /// other multi-producer signals, the process terminates at the following instant.
+    pub fn await_any(&self) -> AwaitValue<Self, MpSignal> {
+        self.await()
+    }
+
+    /// Same as `await_any`, but terminates at the instant of the first emission.
+    /// Only the signals emitted before the process is woken up are then returned.
+    pub fn await_any_immediate(&self) -> AwaitAnyImmediateSt<V> {
+        AwaitAnyImmediateSt(self.clone())
+    }
+}
+
+/// Process awaiting some signals of an array to be emitted, which gets the signals
+/// present at the instant they are emitted.
+pub struct AwaitAnyImmediateSt<V>(SignalArraySt<V>);
+
+impl<V> Process for AwaitAnyImmediateSt<V> where V: Clone + 'static {
+    type Value = Vec<(usize, V)>;
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("await_any_immediate({})", self.0.label()))
+    }
+}
+
+impl<V> ProcessMut for AwaitAnyImmediateSt<V> where V: Clone + 'static {}
+
+impl<V> ProcessSt for AwaitAnyImmediateSt<V> where V: Clone + 'static {
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        let signal_runtime = self.0.runtime();
+        self.0.runtime().on_signal(
+            runtime,
+            move |r: &mut SingleThreadRuntime, ()| next.call(r, signal_runtime.get_value()));
+    }
+}
+
+impl<V> ProcessMutSt for AwaitAnyImmediateSt<V> where V: Clone + 'static {
+    fn call_mut<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        let signal_runtime = self.0.runtime();
+        let mut signal_runtime2 = self.0.runtime();
+        signal_runtime2.on_signal(
+            runtime,
+            move |r: &mut SingleThreadRuntime, ()|
+                next.call(r, (self, signal_runtime.get_value())));
+    }
+}
@@ -1,11 +1,14 @@
+use std::collections::VecDeque;
+use std::fmt;
 use std::rc::Rc;
 use std::cell::RefCell;
 
 use runtime::SingleThreadRuntime;
 use continuation::ContinuationSt;
-use signal::Signal;
-use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefSt};
-use signal::valued_signal::{ValuedSignal, SpSignal, CanEmit, GetValue, CanTryEmit, TryEmitValue};
+use signal::{Signal, SignalInfo};
+use signal::signal_runtime::{SignalRuntimeRefBase, EmissionInstants, SignalRuntimeRefSt};
+use signal::signal_runtime::{AwaitToken, AwaitWorks};
+use signal::valued_signal::{ValuedSignal, SpSignal, CanEmit, GetValue, GetPreValue, GetHistory, CanTryEmit, TryEmitValue};
 
 /// A shared pointer to a signal runtime.
 pub struct SpmcSignalRuntimeRef<V> {
@@ -20,24 +23,44 @@ impl<V> Clone for SpmcSignalRuntimeRef<V> {
 
 /// Runtime for single-producer, multi-consumer signals.
 struct SpmcSignalRuntime<V> {
+    info: SignalInfo,
     value: RefCell<Option<V>>,
     last_value: RefCell<Option<V>>,
     last_value_updated: RefCell<bool>,
-    await_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
+    emissions: RefCell<EmissionInstants>,
+    history: RefCell<VecDeque<(usize, V)>>,
+    history_capacity: usize,
+    await_works: RefCell<AwaitWorks<Box<ContinuationSt<()>>>>,
     present_works: RefCell<Vec<Box<ContinuationSt<()>>>>,
 }
 
 impl<V> SpmcSignalRuntime<V> where V: Clone {
     /// Returns a new instance of SignalRuntime.
-    fn new() -> Self {
+    fn new(history_capacity: usize) -> Self {
         SpmcSignalRuntime {
+            info: SignalInfo::new(),
             value: RefCell::new(None),
             last_value: RefCell::new(None),
             last_value_updated: RefCell::new(false),
-            await_works: RefCell::new(Vec::new()),
+            emissions: RefCell::new(EmissionInstants::default()),
+            history: RefCell::new(VecDeque::with_capacity(history_capacity)),
+            history_capacity,
+            await_works: RefCell::new(AwaitWorks::new()),
             present_works: RefCell::new(Vec::new()),
         }
     }
+
+    /// Records the value emitted at `instant` in the history if it is enabled,
+    /// dropping the oldest value when the history is full.
+    fn push_history(&self, instant: usize, value: V) {
+        if self.history_capacity > 0 {
+            let mut history = self.history.borrow_mut();
+            if history.len() == self.history_capacity {
+                history.pop_front();
+            }
+            history.push_back((instant, value));
+        }
+    }
 }
 
 impl<V> SignalRuntimeRefBase<SingleThreadRuntime> for SpmcSignalRuntimeRef<V>
@@ -48,9 +71,17 @@ impl<V> SignalRuntimeRefBase<SingleThreadRuntime> for SpmcSignalRuntimeRef<V>
         self.runtime.value.borrow().is_some()
     }
 
+    /// Returns the last instants at which the signal was emitted.
+    fn emissions(&self) -> EmissionInstants {
+        *self.runtime.emissions.borrow()
+    }
+
     /// Resets the signal at the beginning of each instant.
     fn reset(&mut self) {
-        *self.runtime.value.borrow_mut() = None;
+        let value = self.runtime.value.borrow_mut().take();
+        if let Some(value) = value {
+            self.runtime.push_history(self.emissions().last().unwrap(), value);
+        }
         *self.runtime.last_value_updated.borrow_mut() = false;
     }
 
@@ -65,14 +96,14 @@ impl<V> SignalRuntimeRefBase<SingleThreadRuntime> for SpmcSignalRuntimeRef<V>
 
 impl<V> SignalRuntimeRefSt for SpmcSignalRuntimeRef<V> where V: Clone + 'static {
     /// Calls `c` at the first cycle where the signal is present.
-    fn on_signal<C>(&mut self, runtime: &mut SingleThreadRuntime, c: C)
+    fn on_signal_cancellable<C>(&mut self, runtime: &mut SingleThreadRuntime,
+                                token: Option<&AwaitToken>, c: C)
         where C: ContinuationSt<()>
     {
         if self.is_emitted() {
             c.call(runtime, ());
         } else {
-            runtime.incr_await_counter();
-            self.runtime.await_works.borrow_mut().push(Box::new(c));
+            self.runtime.await_works.borrow_mut().register(runtime, &self.runtime.info, token, Box::new(c));
         }
     }
 
@@ -93,14 +124,15 @@ impl<V> CanEmit<SingleThreadRuntime, V> for SpmcSignalRuntimeRef<V>
 {
     fn emit(&mut self, runtime: &mut SingleThreadRuntime, emitted: V) {
         if self.is_emitted() {
-            panic!("Multiple emissions of a single-producer signal inside an instant.");
+            panic!("Multiple emissions of the single-producer {} inside an instant.",
+                   self.runtime.info);
         }
         *self.runtime.value.borrow_mut() = Some(emitted);
-        while let Some(c) = self.runtime.await_works.borrow_mut().pop() {
-            runtime.decr_await_counter();
+        while let Some(c) = self.runtime.await_works.borrow_mut().wake(runtime, &self.runtime.info) {
             c.call_box(runtime, ());
         }
         self.execute_present_works(runtime);
+        self.runtime.emissions.borrow_mut().record(runtime.current_instant());
         runtime.emit_signal(Box::new(self.clone()));
         let signal_ref = self.clone();
         let update_last_value = move |_: &mut SingleThreadRuntime, ()| {
@@ -130,14 +162,32 @@ impl<V> GetValue<V> for SpmcSignalRuntimeRef<V> where V: Clone {
     /// Returns the value of the signal for the current instant.
     /// The returned value is cloned and can thus be used directly.
     fn get_value(&self) -> V {
-        self.runtime.value.borrow().clone().unwrap()
+        self.runtime.value.borrow().clone().unwrap_or_else(
+            || panic!("Trying to get the value of the {} which is not emitted.", self.runtime.info))
+    }
+}
+
+impl<V> GetPreValue for SpmcSignalRuntimeRef<V> where V: Clone {
+    type PreValue = Option<V>;
+
+    /// Returns the value of the signal at its last emission before the current instant.
+    fn get_pre_value(&self) -> Option<V> {
+        self.runtime.last_value.borrow().clone()
+    }
+}
+
+impl<V> GetHistory<V> for SpmcSignalRuntimeRef<V> where V: Clone {
+    /// Returns the values recorded in the history with the instants of their emission,
+    /// the oldest first.
+    fn get_history(&self) -> Vec<(usize, V)> {
+        self.runtime.history.borrow().iter().cloned().collect()
     }
 }
 
 impl<V> SpmcSignalRuntimeRef<V> where V: Clone + 'static {
     /// Returns a new instance of SignalRuntimeRef.
-    fn new() -> Self {
-        SpmcSignalRuntimeRef { runtime: Rc::new(SpmcSignalRuntime::new()) }
+    fn new(history_capacity: usize) -> Self {
+        SpmcSignalRuntimeRef { runtime: Rc::new(SpmcSignalRuntime::new(history_capacity)) }
     }
 }
 
@@ -156,6 +206,10 @@ impl<V> Signal for SpmcSignalSt<V> where V: Clone + 'static {
     fn runtime(&self) -> SpmcSignalRuntimeRef<V> {
         self.0.clone()
     }
+
+    fn label(&self) -> String {
+        self.info().label()
+    }
 }
 
 impl<V> ValuedSignal for SpmcSignalSt<V> where V: Clone + 'static {
@@ -163,10 +217,23 @@ impl<V> ValuedSignal for SpmcSignalSt<V> where V: Clone + 'static {
     type SigType = SpSignal;
 }
 
+impl<V> fmt::Debug for SpmcSignalSt<V> where V: Clone + 'static {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.info().debug(f, "SpmcSignalSt", self.0.is_emitted())
+    }
+}
+
 impl<V> SpmcSignalSt<V> where V: Clone + 'static {
+    signal_info_methods!();
+
     /// Creates a new spmc signal.
     pub fn new() -> Self {
-        SpmcSignalSt(SpmcSignalRuntimeRef::new())
+        SpmcSignalSt(SpmcSignalRuntimeRef::new(0))
+    }
+
+    /// Creates a new spmc signal which keeps the values of its last `capacity` emissions.
+    pub fn with_history(capacity: usize) -> Self {
+        SpmcSignalSt(SpmcSignalRuntimeRef::new(capacity))
     }
     
     /// Returns the last value associated to the signal when it was emitted.
@@ -0,0 +1,155 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessSt, ProcessMutSt, Description};
+use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
+
+use signal::Signal;
+use signal::signal_runtime::SignalRuntimeRefBase;
+
+/// Process that executes an emission at each instant. The emission is directly
+/// registered in the runtime, which executes it at the beginning of every instant.
+///
+/// By default the process never terminates. It can be stopped after some number of
+/// instants with `for_instants` or when another signal is emitted with `until`.
+pub struct Sustain<P, U> {
+    pub(crate) process: P,
+    pub(crate) until: U,
+}
+
+impl<P> Sustain<P, Forever> {
+    /// Emits the signal during `n` instants. The process terminates at the
+    /// instant following the last emission.
+    pub fn for_instants(self, n: usize) -> Sustain<P, ForInstants> {
+        Sustain { process: self.process, until: ForInstants(n) }
+    }
+
+    /// Emits the signal until `s` is emitted. When `s` is present at some instant,
+    /// the process terminates at the following instant without emitting the signal.
+    pub fn until<S>(self, s: S) -> Sustain<P, UntilSignal<S>> where S: Signal {
+        Sustain { process: self.process, until: UntilSignal(s) }
+    }
+}
+
+impl<P, U> Process for Sustain<P, U> where P: Process<Value=()>, U: 'static {
+    type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::node("sustain", vec![self.process.describe()])
+    }
+}
+
+/// The condition under which a sustained emission stops.
+pub trait StopCondition<R>: 'static where R: Runtime {
+    /// Called before each emission. Returns `true` if the emission must stop.
+    fn is_over(&mut self, runtime: &R) -> bool;
+}
+
+/// The emission never stops.
+pub struct Forever;
+
+impl<R> StopCondition<R> for Forever where R: Runtime {
+    fn is_over(&mut self, _: &R) -> bool {
+        false
+    }
+}
+
+/// The emission stops after a certain number of instants.
+pub struct ForInstants(pub(crate) usize);
+
+impl<R> StopCondition<R> for ForInstants where R: Runtime {
+    fn is_over(&mut self, _: &R) -> bool {
+        if self.0 == 0 {
+            true
+        } else {
+            self.0 -= 1;
+            false
+        }
+    }
+}
+
+/// The emission stops at the instant following the emission of a signal.
+pub struct UntilSignal<S>(pub(crate) S);
+
+impl<S> StopCondition<SingleThreadRuntime> for UntilSignal<S>
+    where S: Signal, S::RuntimeRef: SignalRuntimeRefBase<SingleThreadRuntime>
+{
+    fn is_over(&mut self, runtime: &SingleThreadRuntime) -> bool {
+        self.0.runtime().emissions().present_before(runtime.current_instant())
+    }
+}
+
+impl<S> StopCondition<ParallelRuntime> for UntilSignal<S>
+    where S: Signal, S::RuntimeRef: SignalRuntimeRefBase<ParallelRuntime>
+{
+    fn is_over(&mut self, runtime: &ParallelRuntime) -> bool {
+        self.0.runtime().emissions().present_before(runtime.current_instant())
+    }
+}
+
+// Implements the traits for the single thread version of the library.
+
+impl<P, U> ProcessSt for Sustain<P, U>
+    where P: ProcessMutSt<Value=()>, U: StopCondition<SingleThreadRuntime>
+{
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        let process = Rc::new(RefCell::new(Some(self.process)));
+        let mut until = self.until;
+        let mut next = Some(next);
+        let mut emit = move |r: &mut SingleThreadRuntime| {
+            if until.is_over(r) {
+                next.take().unwrap().call(r, ());
+                return false;
+            }
+            let p = process.borrow_mut().take()
+                .expect("A sustained process must terminate instantaneously.");
+            let process = process.clone();
+            p.call_mut(r, move |_: &mut SingleThreadRuntime, (p, ()): (P, ())| {
+                *process.borrow_mut() = Some(p);
+            });
+            true
+        };
+        if emit(runtime) {
+            runtime.sustain(Box::new(emit));
+        }
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<P, U> ConstraintOnValue for Sustain<P, U> {
+    type T = ();
+}
+
+impl<P, U> ProcessPl for Sustain<P, U>
+    where P: ProcessMutPl<T=()>, U: StopCondition<ParallelRuntime> + Send + Sync
+{
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        let process = Arc::new(Mutex::new(Some(self.process)));
+        let mut until = self.until;
+        let mut next = Some(next);
+        let mut emit = move |r: &mut ParallelRuntime| {
+            if until.is_over(r) {
+                r.on_current_instant(Box::new(next.take().unwrap()));
+                return false;
+            }
+            let p = process.lock().unwrap().take()
+                .expect("A sustained process must terminate instantaneously.");
+            let process = process.clone();
+            p.call_mut(r, move |_: &mut ParallelRuntime, (p, ()): (P, ())| {
+                *process.lock().unwrap() = Some(p);
+            });
+            true
+        };
+        if emit(runtime) {
+            runtime.sustain(Box::new(emit));
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use runtime::{SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 use signal::valued_signal::{ValuedSignal, SignalType, MpSignal, SpSignal};
 use signal::signal_runtime::{SignalRuntimeRefSt, SignalRuntimeRefPl};
@@ -15,6 +15,10 @@ pub struct AwaitValue<S, T>{
 
 impl<S, T> Process for AwaitValue<S, T> where S: ValuedSignal<SigType=T>, T: SignalType {
     type Value = S::Stored;
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("await({})", self.signal.label()))
+    }
 }
 
 impl<S, T> ProcessMut for AwaitValue<S, T> where S: ValuedSignal<SigType=T>, T: SignalType {}
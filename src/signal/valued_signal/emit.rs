@@ -1,6 +1,6 @@
 use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 use signal::signal_runtime::SignalRuntimeRefBase;
 use signal::ValuedSignal;
@@ -13,6 +13,10 @@ pub struct EmitValue<S, A> {
 
 impl<S, A> Process for EmitValue<S, A> where S: ValuedSignal, A: 'static {
     type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("emit({})", self.signal.label()))
+    }
 }
 
 impl<S, A> ProcessMut for EmitValue<S, A> where S: ValuedSignal, A: 'static {}
@@ -0,0 +1,194 @@
+use std::marker::PhantomData;
+use std::sync::{Mutex, RwLock};
+
+/// The way in which the values emitted to a multi-producer signal are combined.
+/// Every closure of type `FnMut(A, &mut B)` is a gather.
+pub trait Gather<A, B> {
+    /// Combines the value `emitted` with the value of the signal.
+    fn gather(&mut self, emitted: A, value: &mut B);
+
+    /// Called before the value of the signal is read at the end of an instant where
+    /// it is emitted. Does nothing by default.
+    fn finish(&mut self, _value: &mut B) {}
+
+    /// Indicates if the parallel runtimes can gather their emissions separately with
+    /// `gather_partial`, the partial results being then combined by `merge_partial`.
+    fn is_associative() -> bool {
+        false
+    }
+
+    /// Indicates if the value of the signal doesn't depend on the order of the
+    /// emissions, and is thus the same for the parallel and non-parallel runtimes.
+    /// The deterministic parallel engine then gathers the emissions at once instead
+    /// of keeping them to gather them in order.
+    fn is_commutative() -> bool {
+        false
+    }
+
+    /// Combines `emitted` with `partial`, the value gathered by one of the parallel
+    /// runtimes during the instant. Only called when the gather is associative.
+    /// Returns `emitted` back if it is not combined, which is the default: it is then
+    /// gathered with the value of the signal, as for the other gathers.
+    fn gather_partial(&self, _partial: &mut Option<B>, emitted: A) -> Option<A> {
+        Some(emitted)
+    }
+
+    /// Combines the value gathered by one of the parallel runtimes with the value of
+    /// the signal.
+    fn merge_partial(&mut self, _partial: B, _value: &mut B) {}
+}
+
+impl<A, B, F> Gather<A, B> for F where F: FnMut(A, &mut B) {
+    fn gather(&mut self, emitted: A, value: &mut B) {
+        self(emitted, value);
+    }
+}
+
+/// A type with an associative operation and an identity element.
+pub trait Monoid {
+    /// Returns the identity element.
+    fn identity() -> Self;
+
+    /// Combines `other` into `self`.
+    fn combine(&mut self, other: Self);
+}
+
+impl<T> Monoid for Vec<T> {
+    fn identity() -> Self {
+        Vec::new()
+    }
+
+    fn combine(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
+impl Monoid for String {
+    fn identity() -> Self {
+        String::new()
+    }
+
+    fn combine(&mut self, other: Self) {
+        self.push_str(&other);
+    }
+}
+
+// The integers are monoids for the addition, unlike the floating point numbers whose
+// addition is not associative.
+macro_rules! additive_monoid {
+    ($($t:ty),*) => {
+        $(
+            impl Monoid for $t {
+                fn identity() -> Self {
+                    0 as $t
+                }
+
+                fn combine(&mut self, other: Self) {
+                    *self += other;
+                }
+            }
+        )*
+    }
+}
+
+additive_monoid!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A gather combining the emitted values with a monoid. Since the operation is
+/// associative, each parallel runtime can gather its emissions separately, and the
+/// partial results are only combined when the value of the signal is read.
+pub struct FromMonoid<B>(PhantomData<fn(B)>);
+
+impl<B> FromMonoid<B> {
+    pub(crate) fn new() -> Self {
+        FromMonoid(PhantomData)
+    }
+}
+
+impl<B> Gather<B, B> for FromMonoid<B> where B: Monoid {
+    fn gather(&mut self, emitted: B, value: &mut B) {
+        value.combine(emitted);
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<B>, emitted: B) -> Option<B> {
+        match *partial {
+            Some(ref mut v) => v.combine(emitted),
+            None => *partial = Some(emitted),
+        }
+        None
+    }
+
+    fn merge_partial(&mut self, partial: B, value: &mut B) {
+        value.combine(partial);
+    }
+}
+
+/// The values gathered separately by each parallel runtime during an instant.
+pub(crate) struct PartialValues<B> {
+    slots: RwLock<Vec<Mutex<Option<B>>>>,
+}
+
+impl<B> PartialValues<B> {
+    pub(crate) fn new() -> Self {
+        PartialValues { slots: RwLock::new(Vec::new()) }
+    }
+
+    /// Calls `f` on the value gathered by the runtime `id` among `num_runtimes`.
+    /// Only this runtime locks the value during the instant, so this doesn't block.
+    pub(crate) fn with_partial<F, T>(&self, id: usize, num_runtimes: usize, f: F) -> T
+        where F: FnOnce(&mut Option<B>) -> T
+    {
+        if self.slots.read().unwrap().len() < num_runtimes {
+            let mut slots = self.slots.write().unwrap();
+            while slots.len() < num_runtimes {
+                slots.push(Mutex::new(None));
+            }
+        }
+        let slots = self.slots.read().unwrap();
+        let mut partial = slots[id].lock().unwrap();
+        f(&mut partial)
+    }
+
+    /// Takes the values gathered by the runtimes, in the order of the runtimes.
+    pub(crate) fn take_all(&self) -> Vec<B> {
+        self.slots.read().unwrap().iter().filter_map(|s| s.lock().unwrap().take()).collect()
+    }
+}
+
+/// An emission waiting to be gathered.
+type DeferredEmission<B, F> = Box<FnOnce(&mut F, &mut B) + Send>;
+
+/// The emissions of an instant kept by each parallel runtime in the deterministic
+/// mode, when the gather isn't associative. They are gathered runtime after runtime,
+/// each runtime in the order of its emissions, when the value of the signal is read.
+pub(crate) struct DeferredEmissions<B, F> {
+    emissions: PartialValues<Vec<DeferredEmission<B, F>>>,
+}
+
+impl<B, F> DeferredEmissions<B, F> {
+    pub(crate) fn new() -> Self {
+        DeferredEmissions { emissions: PartialValues::new() }
+    }
+
+    /// Keeps `emitted` for the runtime `id` among `num_runtimes`.
+    pub(crate) fn push<A>(&self, id: usize, num_runtimes: usize, emitted: A)
+        where A: Send + 'static, F: Gather<A, B>
+    {
+        let gather = move |gather: &mut F, value: &mut B| gather.gather(emitted, value);
+        self.emissions.with_partial(id, num_runtimes, |emissions| {
+            emissions.get_or_insert_with(Vec::new).push(Box::new(gather));
+        });
+    }
+
+    /// Gathers the emissions kept so far.
+    pub(crate) fn gather_all(&self, gather: &mut F, value: &mut B) {
+        for emissions in self.emissions.take_all() {
+            for emission in emissions {
+                emission(gather, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use runtime::{SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
+use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
+use signal::valued_signal::ValuedSignal;
+use signal::signal_runtime::{SignalRuntimeRefSt, SignalRuntimeRefPl};
+
+/// Process that gets the values recorded in the history of a signal.
+pub struct History<S>(pub(crate) S);
+
+pub trait GetHistory<V> {
+    /// Returns the values recorded in the history with the instants of their emission,
+    /// the oldest first.
+    fn get_history(&self) -> Vec<(usize, V)>;
+}
+
+impl<S> Process for History<S> where S: ValuedSignal {
+    type Value = Vec<(usize, S::Stored)>;
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("history({})", self.0.label()))
+    }
+}
+
+impl<S> ProcessMut for History<S> where S: ValuedSignal {}
+
+// Implements the traits for the single thread version of the library.
+
+impl<S> ProcessSt for History<S>
+    where S: ValuedSignal, S::RuntimeRef: GetHistory<S::Stored> + SignalRuntimeRefSt
+{
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        next.call(runtime, self.0.runtime().get_history());
+    }
+}
+
+impl<S> ProcessMutSt for History<S>
+    where S: ValuedSignal, S::RuntimeRef: GetHistory<S::Stored> + SignalRuntimeRefSt
+{
+    fn call_mut<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        let history = self.0.runtime().get_history();
+        next.call(runtime, (self, history));
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<S> ConstraintOnValue for History<S> where S: ValuedSignal, S::Stored: Send + Sync {
+    type T = Vec<(usize, S::Stored)>;
+}
+
+impl<S> ProcessPl for History<S>
+    where S: ValuedSignal + Send + Sync,
+          S::RuntimeRef: GetHistory<S::Stored> + SignalRuntimeRefPl,
+          S::Stored: Send + Sync,
+{
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        next.call(runtime, self.0.runtime().get_history());
+    }
+}
+
+impl<S> ProcessMutPl for History<S>
+    where S: ValuedSignal + Send + Sync,
+          S::RuntimeRef: GetHistory<S::Stored> + SignalRuntimeRefPl,
+          S::Stored: Send + Sync,
+{
+    fn call_mut<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        let history = self.0.runtime().get_history();
+        next.call(runtime, (self, history));
+    }
+}
@@ -3,13 +3,28 @@
 mod emit;
 mod await;
 mod try_emit;
+mod pre_value;
+mod history;
+mod on_each;
+mod gather;
+pub mod strategies;
 pub use self::emit::{EmitValue, CanEmit};
 pub use self::await::{AwaitValue, GetValue};
 pub use self::try_emit::{TryEmitValue, CanTryEmit};
+pub use self::pre_value::{PreValue, GetPreValue};
+pub use self::history::{History, GetHistory};
+pub use self::on_each::{OnEach, CanHandleEach};
+pub use self::gather::{Gather, Monoid, FromMonoid};
+pub use self::strategies::Strategy;
+pub(crate) use self::gather::{PartialValues, DeferredEmissions};
+pub(crate) use self::on_each::{EachEmissionsSt, EachEmissionsPl, HandlerSt, HandlerPl};
 
+use std::sync::Arc;
 use std::marker::PhantomData;
 
-use signal::Signal;
+use process::{Process, PauseN, Then, Value, value_proc};
+use signal::{Signal, Sustain, Forever};
+use signal::derived_signal::{MapSignal, FilterSignal};
 
 /// A reactive signal with value.
 pub trait ValuedSignal: Signal {
@@ -25,6 +40,22 @@ pub trait ValuedSignal: Signal {
         EmitValue { signal: self.clone(), emitted }
     }
 
+    /// Returns a process that emits the signal with value `emitted` `n` instants
+    /// after it is called.
+    fn emit_after<A>(&self, n: usize, emitted: A) -> Then<PauseN<Value<()>>, EmitValue<Self, A>>
+        where Self: Sized, A: 'static
+    {
+        value_proc(()).pause_n(n).then(self.emit(emitted))
+    }
+
+    /// Returns a process that emits the signal with value `emitted` at every instant
+    /// from the instant where it is called on.
+    fn sustain<A>(&self, emitted: A) -> Sustain<EmitValue<Self, A>, Forever>
+        where Self: Sized, A: Clone
+    {
+        Sustain { process: self.emit(emitted), until: Forever }
+    }
+
     /// Waits the signal to be emitted and gets its content.
     ///
     /// For a single-producer signal the process terminates immediately and for a
@@ -34,6 +65,64 @@ pub trait ValuedSignal: Signal {
     fn await(&self) -> AwaitValue<Self, Self::SigType> where Self: Sized {
         AwaitValue{ signal: self.clone(), signal_type: PhantomData }
     }
+
+    /// Returns a process that gets immediately the value of the signal at its last
+    /// emission before the current instant.
+    ///
+    /// For a mpmc signal this is the default value before the first emission, and
+    /// for a spmc signal the value is wrapped in an `Option`. Reading the value
+    /// doesn't change it. This is not available for mpsc signals, whose value is
+    /// moved to its consumer and thus cannot be kept for the next instant.
+    fn pre_value(&self) -> PreValue<Self> where Self: Sized {
+        PreValue(self.clone())
+    }
+
+    /// Returns a process that gets immediately the values of the last emissions
+    /// of the signal, each tagged with the number of the instant of the emission.
+    ///
+    /// The history must be enabled when the signal is created (see for example
+    /// `MpmcSignalSt::with_history`) and a value only enters the history at the end
+    /// of the instant of its emission.
+    fn history(&self) -> History<Self> where Self: Sized {
+        History(self.clone())
+    }
+
+    /// Returns a process that calls `handler` on each value emitted to a multi-producer
+    /// signal during the current instant, and executes the returned processes in
+    /// parallel. The process terminates at the following instant, without waiting
+    /// for the processes spawned by the handler.
+    ///
+    /// The values are only recorded during the instants where `on_each` is used, so
+    /// when it is used after values were emitted in the same instant, it rather handles
+    /// the values emitted during the next instant and terminates one instant later.
+    /// Values emitted after the first `on_each` of the instant are also passed to the
+    /// later ones. With the parallel runtime, the processes of a `join` are not ordered,
+    /// so whether the emissions joined with the first `on_each` of their instant are
+    /// handled at this instant depends on the scheduling.
+    ///
+    /// The values are cloned before being gathered, so `handler` must accept the same
+    /// type of values as the `gather` function of the signal, or the emission panics.
+    fn on_each<A, F, P>(&self, handler: F) -> OnEach<Self, A, F, P>
+        where Self: ValuedSignal<SigType=MpSignal> + Sized, F: FnMut(A) -> P
+    {
+        OnEach { signal: self.clone(), handler, marker: PhantomData }
+    }
+
+    /// Returns a signal with the same status whose value is the value of `self`
+    /// transformed by `f`. For a multi-producer signal `f` is applied on the gathered value.
+    fn map<F, V>(&self, f: F) -> MapSignal<Self, F, V>
+        where Self: Sized, F: Fn(Self::Stored) -> V
+    {
+        MapSignal { signal: self.clone(), f: Arc::new(f), marker: PhantomData }
+    }
+
+    /// Returns a signal which is present only when `self` is present with a value
+    /// satisfying `pred`. Only single-producer signals can be filtered.
+    fn filter<F>(&self, pred: F) -> FilterSignal<Self, F>
+        where Self: ValuedSignal<SigType=SpSignal> + Sized, F: Fn(&Self::Stored) -> bool
+    {
+        FilterSignal { signal: self.clone(), pred: Arc::new(pred) }
+    }
 }
 
 /// Define some subtypes that a signal with value can have.
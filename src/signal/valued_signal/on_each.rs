@@ -0,0 +1,339 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::cell::RefCell;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use runtime::{SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
+use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
+use signal::SignalInfo;
+use signal::valued_signal::{ValuedSignal, MpSignal};
+
+/// Process calling a handler for each value emitted to a multi-producer signal
+/// during the current instant.
+pub struct OnEach<S, A, F, P> {
+    pub(crate) signal: S,
+    pub(crate) handler: F,
+    pub(crate) marker: PhantomData<fn(A) -> P>,
+}
+
+impl<S, A, F, P> Process for OnEach<S, A, F, P>
+    where S: ValuedSignal<SigType=MpSignal>, A: 'static, F: FnMut(A) -> P + 'static, P: Process
+{
+    type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("on_each({})", self.signal.label()))
+    }
+}
+
+impl<S, A, F, P> ProcessMut for OnEach<S, A, F, P>
+    where S: ValuedSignal<SigType=MpSignal>,
+          A: 'static,
+          F: FnMut(A) -> P + Clone + 'static,
+          P: Process
+{}
+
+const HANDLED: usize = 1;
+const UNHANDLED: usize = 2;
+
+/// Tells whether some handlers are registered for the current instant, so that the
+/// emissions don't record anything otherwise. The upper bits hold the instant plus one,
+/// the two lower bits the flags `HANDLED` and `UNHANDLED` for this instant. The instant
+/// is the next one when a handler was registered after values were emitted.
+struct EachStatus(AtomicUsize);
+
+impl EachStatus {
+    fn new() -> Self {
+        EachStatus(AtomicUsize::new(0))
+    }
+
+    /// Returns the flags set for `instant` in `status`.
+    fn flags(status: usize, instant: usize) -> usize {
+        if status >> 2 == instant + 1 { status & 3 } else { 0 }
+    }
+
+    /// Called on each emission: returns true if handlers are registered for `instant`,
+    /// and otherwise remembers that a value was emitted without being recorded.
+    fn on_emit(&self, instant: usize) -> bool {
+        let mut status = self.0.load(Ordering::SeqCst);
+        loop {
+            if status >> 2 > instant + 1 {
+                // The handlers are registered for the next instant.
+                return false;
+            }
+            let flags = EachStatus::flags(status, instant);
+            if flags & HANDLED != 0 {
+                return true;
+            }
+            if flags & UNHANDLED != 0 {
+                return false;
+            }
+            let new_status = (instant + 1) << 2 | UNHANDLED;
+            match self.0.compare_exchange_weak(status, new_status, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return false,
+                Err(s) => status = s,
+            }
+        }
+    }
+
+    /// Called on each registration at `instant`: returns the instant from which the
+    /// handler is called, and whether it is the first handler of this instant. This is
+    /// the next instant if values were emitted before without being recorded.
+    fn register(&self, instant: usize) -> (usize, bool) {
+        let mut status = self.0.load(Ordering::SeqCst);
+        loop {
+            let (start, flags) = if status >> 2 > instant + 1 {
+                (instant + 1, status & 3)
+            } else {
+                match EachStatus::flags(status, instant) {
+                    flags if flags & UNHANDLED != 0 => (instant + 1, 0),
+                    flags => (instant, flags),
+                }
+            };
+            let new_status = (start + 1) << 2 | flags | HANDLED;
+            match self.0.compare_exchange_weak(status, new_status, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return (start, flags & HANDLED == 0),
+                Err(s) => status = s,
+            }
+        }
+    }
+}
+
+/// The values of type `A` emitted to a signal during an instant where handlers are
+/// registered, and these handlers.
+struct EachEmission<A, H: ?Sized> {
+    clone: fn(&A) -> A,
+    log: Vec<A>,
+    handlers: Vec<Box<H>>,
+}
+
+impl<A, H: ?Sized> EachEmission<A, H> where A: Clone {
+    fn new() -> Self {
+        EachEmission { clone: A::clone, log: Vec::new(), handlers: Vec::new() }
+    }
+}
+
+pub trait CanHandleEach<R, A, H: ?Sized> {
+    /// Registers `handler` to be called on each value emitted during the current instant,
+    /// including the ones that are already emitted, and returns the current instant.
+    /// If values were already emitted without being recorded, the handler is rather
+    /// called on each value emitted during the next instant, which is returned.
+    fn on_each(&mut self, runtime: &mut R, handler: Box<H>) -> usize;
+}
+
+/// Panics because the values emitted to the signal described by `info` are not of
+/// the type accepted by its handlers.
+fn type_mismatch(info: &SignalInfo) -> ! {
+    panic!("on_each was used with {} with handlers of another type than the emitted values.",
+           info.label())
+}
+
+// Implements the traits for the single thread version of the library.
+
+pub(crate) type HandlerSt<A> = FnMut(&mut SingleThreadRuntime, A);
+
+/// Records the values emitted to a signal during the instants where `on_each` is used.
+/// Everything is forgotten at the following instant.
+pub(crate) struct EachEmissionsSt {
+    status: EachStatus,
+    emissions: RefCell<Option<Box<Any>>>,
+}
+
+impl EachEmissionsSt {
+    pub(crate) fn new() -> Self {
+        EachEmissionsSt { status: EachStatus::new(), emissions: RefCell::new(None) }
+    }
+
+    /// Records `emitted` and calls the handlers on it, if there are any.
+    pub(crate) fn record<A>(&self, runtime: &mut SingleThreadRuntime, info: &SignalInfo, emitted: &A)
+        where A: 'static
+    {
+        if !self.status.on_emit(runtime.current_instant()) {
+            return;
+        }
+        if let Some(each) = self.emissions.borrow_mut().as_mut() {
+            match each.downcast_mut::<EachEmission<A, HandlerSt<A>>>() {
+                Some(each) => {
+                    for handler in each.handlers.iter_mut() {
+                        handler(runtime, (each.clone)(emitted));
+                    }
+                    each.log.push((each.clone)(emitted));
+                },
+                None => type_mismatch(info),
+            }
+        }
+    }
+
+    /// Registers a handler for the current instant, or for the next one if values
+    /// were already emitted, and returns this instant.
+    pub(crate) fn add_handler<A>(
+        &self,
+        runtime: &mut SingleThreadRuntime,
+        info: &SignalInfo,
+        mut handler: Box<HandlerSt<A>>) -> usize
+        where A: Clone + 'static
+    {
+        let mut emissions = self.emissions.borrow_mut();
+        let (start, first) = self.status.register(runtime.current_instant());
+        if first {
+            *emissions = Some(Box::new(EachEmission::<A, HandlerSt<A>>::new()));
+        }
+        match emissions.as_mut().and_then(|each| each.downcast_mut::<EachEmission<A, HandlerSt<A>>>()) {
+            Some(each) => {
+                for v in each.log.iter() {
+                    handler(runtime, v.clone());
+                }
+                each.handlers.push(handler);
+            },
+            None => type_mismatch(info),
+        }
+        start
+    }
+}
+
+impl<S, A, F, P> ProcessSt for OnEach<S, A, F, P>
+    where S: ValuedSignal<SigType=MpSignal>,
+          S::RuntimeRef: CanHandleEach<SingleThreadRuntime, A, HandlerSt<A>>,
+          A: Clone + 'static,
+          F: FnMut(A) -> P + 'static,
+          P: ProcessSt<Value=()>
+{
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        let mut handler = self.handler;
+        let each_handler = move |r: &mut SingleThreadRuntime, v: A| {
+            let p = handler(v);
+            r.on_current_instant(Box::new(move |r: &mut SingleThreadRuntime, ()| {
+                p.call(r, |_: &mut SingleThreadRuntime, ()| ());
+            }));
+        };
+        let start = self.signal.runtime().on_each(runtime, Box::new(each_handler));
+        runtime.on_instant(start + 1, Box::new(|r: &mut SingleThreadRuntime, ()| next.call(r, ())));
+    }
+}
+
+impl<S, A, F, P> ProcessMutSt for OnEach<S, A, F, P>
+    where S: ValuedSignal<SigType=MpSignal>,
+          S::RuntimeRef: CanHandleEach<SingleThreadRuntime, A, HandlerSt<A>>,
+          A: Clone + 'static,
+          F: FnMut(A) -> P + Clone + 'static,
+          P: ProcessSt<Value=()>
+{
+    fn call_mut<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        let signal = self.signal.clone();
+        let handler = self.handler.clone();
+        self.call(runtime, next.map(|()| (OnEach { signal, handler, marker: PhantomData }, ())));
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+pub(crate) type HandlerPl<A> = FnMut(&mut ParallelRuntime, A) + Send;
+
+/// Records the values emitted to a signal during the instants where `on_each` is used.
+/// Everything is forgotten at the following instant. The lock is only taken when
+/// handlers are registered for the instant.
+pub(crate) struct EachEmissionsPl {
+    status: EachStatus,
+    emissions: Mutex<Option<Box<Any + Send>>>,
+}
+
+impl EachEmissionsPl {
+    pub(crate) fn new() -> Self {
+        EachEmissionsPl { status: EachStatus::new(), emissions: Mutex::new(None) }
+    }
+
+    /// Records `emitted` and calls the handlers on it, if there are any.
+    pub(crate) fn record<A>(&self, runtime: &mut ParallelRuntime, info: &SignalInfo, emitted: &A)
+        where A: 'static
+    {
+        if !self.status.on_emit(runtime.current_instant()) {
+            return;
+        }
+        if let Some(each) = self.emissions.lock().unwrap().as_mut() {
+            match each.downcast_mut::<EachEmission<A, HandlerPl<A>>>() {
+                Some(each) => {
+                    for handler in each.handlers.iter_mut() {
+                        handler(runtime, (each.clone)(emitted));
+                    }
+                    each.log.push((each.clone)(emitted));
+                },
+                None => type_mismatch(info),
+            }
+        }
+    }
+
+    /// Registers a handler for the current instant, or for the next one if values
+    /// were already emitted, and returns this instant.
+    pub(crate) fn add_handler<A>(
+        &self,
+        runtime: &mut ParallelRuntime,
+        info: &SignalInfo,
+        mut handler: Box<HandlerPl<A>>) -> usize
+        where A: Clone + Send + 'static
+    {
+        let mut emissions = self.emissions.lock().unwrap();
+        let (start, first) = self.status.register(runtime.current_instant());
+        if first {
+            *emissions = Some(Box::new(EachEmission::<A, HandlerPl<A>>::new()));
+        }
+        match emissions.as_mut().and_then(|each| each.downcast_mut::<EachEmission<A, HandlerPl<A>>>()) {
+            Some(each) => {
+                for v in each.log.iter() {
+                    handler(runtime, v.clone());
+                }
+                each.handlers.push(handler);
+            },
+            None => type_mismatch(info),
+        }
+        start
+    }
+}
+
+impl<S, A, F, P> ConstraintOnValue for OnEach<S, A, F, P> {
+    type T = ();
+}
+
+impl<S, A, F, P> ProcessPl for OnEach<S, A, F, P>
+    where S: ValuedSignal<SigType=MpSignal> + Send + Sync,
+          S::RuntimeRef: CanHandleEach<ParallelRuntime, A, HandlerPl<A>>,
+          A: Clone + Send + 'static,
+          F: FnMut(A) -> P + Send + Sync + 'static,
+          P: ProcessPl<T=()>
+{
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        let mut handler = self.handler;
+        let each_handler = move |r: &mut ParallelRuntime, v: A| {
+            let p = handler(v);
+            r.on_current_instant(Box::new(move |r: &mut ParallelRuntime, ()| {
+                p.call(r, |_: &mut ParallelRuntime, ()| ());
+            }));
+        };
+        let start = self.signal.runtime().on_each(runtime, Box::new(each_handler));
+        runtime.on_instant(start + 1, Box::new(|r: &mut ParallelRuntime, ()| next.call(r, ())));
+    }
+}
+
+impl<S, A, F, P> ProcessMutPl for OnEach<S, A, F, P>
+    where S: ValuedSignal<SigType=MpSignal> + Send + Sync,
+          S::RuntimeRef: CanHandleEach<ParallelRuntime, A, HandlerPl<A>>,
+          A: Clone + Send + 'static,
+          F: FnMut(A) -> P + Clone + Send + Sync + 'static,
+          P: ProcessPl<T=()>
+{
+    fn call_mut<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        let signal = self.signal.clone();
+        let handler = self.handler.clone();
+        self.call(runtime, next.map(|()| (OnEach { signal, handler, marker: PhantomData }, ())));
+    }
+}
@@ -0,0 +1,87 @@
+use runtime::{SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
+use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
+use signal::valued_signal::ValuedSignal;
+use signal::signal_runtime::{SignalRuntimeRefSt, SignalRuntimeRefPl};
+
+/// Process that gets the value of a signal at its last emission before the
+/// current instant.
+pub struct PreValue<S>(pub(crate) S);
+
+pub trait GetPreValue {
+    /// The type of the value returned by `get_pre_value`.
+    type PreValue;
+
+    /// Returns the value of the signal at its last emission before the current instant.
+    /// This must not change the signal, so the value can be read any number of times.
+    fn get_pre_value(&self) -> Self::PreValue;
+}
+
+impl<S> Process for PreValue<S> where S: ValuedSignal, S::RuntimeRef: GetPreValue {
+    type Value = <S::RuntimeRef as GetPreValue>::PreValue;
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("pre_value({})", self.0.label()))
+    }
+}
+
+impl<S> ProcessMut for PreValue<S> where S: ValuedSignal, S::RuntimeRef: GetPreValue {}
+
+// Implements the traits for the single thread version of the library.
+
+impl<S> ProcessSt for PreValue<S>
+    where S: ValuedSignal, S::RuntimeRef: GetPreValue + SignalRuntimeRefSt
+{
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where C: ContinuationSt<Self::Value>
+    {
+        next.call(runtime, self.0.runtime().get_pre_value());
+    }
+}
+
+impl<S> ProcessMutSt for PreValue<S>
+    where S: ValuedSignal, S::RuntimeRef: GetPreValue + SignalRuntimeRefSt
+{
+    fn call_mut<C>(self, runtime: &mut SingleThreadRuntime, next: C)
+        where Self: Sized, C: ContinuationSt<(Self, Self::Value)>
+    {
+        let v = self.0.runtime().get_pre_value();
+        next.call(runtime, (self, v));
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<S> ConstraintOnValue for PreValue<S>
+    where S: ValuedSignal,
+          S::RuntimeRef: GetPreValue,
+          <S::RuntimeRef as GetPreValue>::PreValue: Send + Sync,
+{
+    type T = <S::RuntimeRef as GetPreValue>::PreValue;
+}
+
+impl<S> ProcessPl for PreValue<S>
+    where S: ValuedSignal + Send + Sync,
+          S::RuntimeRef: GetPreValue + SignalRuntimeRefPl,
+          <S::RuntimeRef as GetPreValue>::PreValue: Send + Sync,
+{
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where C: ContinuationPl<Self::Value>
+    {
+        next.call(runtime, self.0.runtime().get_pre_value());
+    }
+}
+
+impl<S> ProcessMutPl for PreValue<S>
+    where S: ValuedSignal + Send + Sync,
+          S::RuntimeRef: GetPreValue + SignalRuntimeRefPl,
+          <S::RuntimeRef as GetPreValue>::PreValue: Send + Sync,
+{
+    fn call_mut<C>(self, runtime: &mut ParallelRuntime, next: C)
+        where Self: Sized, C: ContinuationPl<(Self, Self::Value)>
+    {
+        let v = self.0.runtime().get_pre_value();
+        next.call(runtime, (self, v));
+    }
+}
@@ -0,0 +1,450 @@
+//! Ready-made gathers for multi-producer signals.
+//!
+//! A signal using one of them is created with `from_strategy`, for example
+//! `MpmcSignalSt::from_strategy(Sum::new())`. The strategies declare whether they
+//! are associative and commutative: the values emitted to an associative gather
+//! are combined separately by each parallel runtime before being merged, and a
+//! commutative gather gives the same value whatever the order of the emissions, so
+//! the deterministic parallel engine doesn't have to keep its emissions in order.
+
+use std::marker::PhantomData;
+use std::collections::BTreeSet;
+use std::ops::AddAssign;
+
+use signal::valued_signal::Gather;
+
+/// A gather that also provides the value of the signal before any emission.
+pub trait Strategy<A, B>: Gather<A, B> {
+    /// Returns the value of the signal at the beginning of each instant.
+    fn initial() -> B;
+}
+
+/// Collects the emitted values in a vector.
+pub struct Collect<A>(PhantomData<fn(A)>);
+
+impl<A> Collect<A> {
+    pub fn new() -> Self {
+        Collect(PhantomData)
+    }
+}
+
+impl<A> Default for Collect<A> {
+    fn default() -> Self {
+        Collect::new()
+    }
+}
+
+impl<A> Gather<A, Vec<A>> for Collect<A> {
+    fn gather(&mut self, emitted: A, value: &mut Vec<A>) {
+        value.push(emitted);
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<Vec<A>>, emitted: A) -> Option<A> {
+        partial.get_or_insert_with(Vec::new).push(emitted);
+        None
+    }
+
+    fn merge_partial(&mut self, partial: Vec<A>, value: &mut Vec<A>) {
+        value.extend(partial);
+    }
+}
+
+impl<A> Strategy<A, Vec<A>> for Collect<A> {
+    fn initial() -> Vec<A> {
+        Vec::new()
+    }
+}
+
+/// The integer types, whose addition is associative and commutative. This is not
+/// the case of the floating point numbers, which are rounded after each addition.
+pub trait Integer: AddAssign + Default {}
+
+impl Integer for i8 {}
+impl Integer for i16 {}
+impl Integer for i32 {}
+impl Integer for i64 {}
+impl Integer for isize {}
+impl Integer for u8 {}
+impl Integer for u16 {}
+impl Integer for u32 {}
+impl Integer for u64 {}
+impl Integer for usize {}
+
+/// Sums the emitted integers, starting from 0. The floating point numbers can be
+/// summed with a closure, which is neither associative nor commutative.
+pub struct Sum<A>(PhantomData<fn(A)>);
+
+impl<A> Sum<A> {
+    pub fn new() -> Self {
+        Sum(PhantomData)
+    }
+}
+
+impl<A> Default for Sum<A> {
+    fn default() -> Self {
+        Sum::new()
+    }
+}
+
+impl<A> Gather<A, A> for Sum<A> where A: Integer {
+    fn gather(&mut self, emitted: A, value: &mut A) {
+        *value += emitted;
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn is_commutative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<A>, emitted: A) -> Option<A> {
+        match *partial {
+            Some(ref mut v) => *v += emitted,
+            None => *partial = Some(emitted),
+        }
+        None
+    }
+
+    fn merge_partial(&mut self, partial: A, value: &mut A) {
+        *value += partial;
+    }
+}
+
+impl<A> Strategy<A, A> for Sum<A> where A: Integer {
+    fn initial() -> A {
+        A::default()
+    }
+}
+
+/// Keeps the smallest emitted value.
+pub struct Min<A>(PhantomData<fn(A)>);
+
+impl<A> Min<A> {
+    pub fn new() -> Self {
+        Min(PhantomData)
+    }
+}
+
+impl<A> Default for Min<A> {
+    fn default() -> Self {
+        Min::new()
+    }
+}
+
+impl<A> Gather<A, Option<A>> for Min<A> where A: Ord {
+    fn gather(&mut self, emitted: A, value: &mut Option<A>) {
+        if value.as_ref().is_none_or(|v| emitted < *v) {
+            *value = Some(emitted);
+        }
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn is_commutative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<Option<A>>, emitted: A) -> Option<A> {
+        Min::new().gather(emitted, partial.get_or_insert(None));
+        None
+    }
+
+    fn merge_partial(&mut self, partial: Option<A>, value: &mut Option<A>) {
+        if let Some(v) = partial {
+            self.gather(v, value);
+        }
+    }
+}
+
+impl<A> Strategy<A, Option<A>> for Min<A> where A: Ord {
+    fn initial() -> Option<A> {
+        None
+    }
+}
+
+/// Keeps the largest emitted value.
+pub struct Max<A>(PhantomData<fn(A)>);
+
+impl<A> Max<A> {
+    pub fn new() -> Self {
+        Max(PhantomData)
+    }
+}
+
+impl<A> Default for Max<A> {
+    fn default() -> Self {
+        Max::new()
+    }
+}
+
+impl<A> Gather<A, Option<A>> for Max<A> where A: Ord {
+    fn gather(&mut self, emitted: A, value: &mut Option<A>) {
+        if value.as_ref().is_none_or(|v| emitted > *v) {
+            *value = Some(emitted);
+        }
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn is_commutative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<Option<A>>, emitted: A) -> Option<A> {
+        Max::new().gather(emitted, partial.get_or_insert(None));
+        None
+    }
+
+    fn merge_partial(&mut self, partial: Option<A>, value: &mut Option<A>) {
+        if let Some(v) = partial {
+            self.gather(v, value);
+        }
+    }
+}
+
+impl<A> Strategy<A, Option<A>> for Max<A> where A: Ord {
+    fn initial() -> Option<A> {
+        None
+    }
+}
+
+/// Keeps the last emitted value.
+///
+/// With the parallel engine each runtime keeps its last value and the runtimes are
+/// merged in the order of their ids, so which value is kept depends on the way the
+/// processes are spread between the runtimes, except with the deterministic engine.
+pub struct Last<A>(PhantomData<fn(A)>);
+
+impl<A> Last<A> {
+    pub fn new() -> Self {
+        Last(PhantomData)
+    }
+}
+
+impl<A> Default for Last<A> {
+    fn default() -> Self {
+        Last::new()
+    }
+}
+
+impl<A> Gather<A, Option<A>> for Last<A> {
+    fn gather(&mut self, emitted: A, value: &mut Option<A>) {
+        *value = Some(emitted);
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<Option<A>>, emitted: A) -> Option<A> {
+        *partial = Some(Some(emitted));
+        None
+    }
+
+    fn merge_partial(&mut self, partial: Option<A>, value: &mut Option<A>) {
+        if partial.is_some() {
+            *value = partial;
+        }
+    }
+}
+
+impl<A> Strategy<A, Option<A>> for Last<A> {
+    fn initial() -> Option<A> {
+        None
+    }
+}
+
+/// Keeps the first emitted value. As for `Last`, the value kept by the parallel
+/// engine is arbitrary, except with the deterministic engine.
+pub struct First<A>(PhantomData<fn(A)>);
+
+impl<A> First<A> {
+    pub fn new() -> Self {
+        First(PhantomData)
+    }
+}
+
+impl<A> Default for First<A> {
+    fn default() -> Self {
+        First::new()
+    }
+}
+
+impl<A> Gather<A, Option<A>> for First<A> {
+    fn gather(&mut self, emitted: A, value: &mut Option<A>) {
+        if value.is_none() {
+            *value = Some(emitted);
+        }
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<Option<A>>, emitted: A) -> Option<A> {
+        First::new().gather(emitted, partial.get_or_insert(None));
+        None
+    }
+
+    fn merge_partial(&mut self, partial: Option<A>, value: &mut Option<A>) {
+        if value.is_none() {
+            *value = partial;
+        }
+    }
+}
+
+impl<A> Strategy<A, Option<A>> for First<A> {
+    fn initial() -> Option<A> {
+        None
+    }
+}
+
+/// Counts the emissions.
+pub struct Count<A>(PhantomData<fn(A)>);
+
+impl<A> Count<A> {
+    pub fn new() -> Self {
+        Count(PhantomData)
+    }
+}
+
+impl<A> Default for Count<A> {
+    fn default() -> Self {
+        Count::new()
+    }
+}
+
+impl<A> Gather<A, usize> for Count<A> {
+    fn gather(&mut self, _: A, value: &mut usize) {
+        *value += 1;
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn is_commutative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<usize>, _: A) -> Option<A> {
+        *partial.get_or_insert(0) += 1;
+        None
+    }
+
+    fn merge_partial(&mut self, partial: usize, value: &mut usize) {
+        *value += partial;
+    }
+}
+
+impl<A> Strategy<A, usize> for Count<A> {
+    fn initial() -> usize {
+        0
+    }
+}
+
+/// Computes the union of the emitted sets.
+pub struct Union<A>(PhantomData<fn(A)>);
+
+impl<A> Union<A> {
+    pub fn new() -> Self {
+        Union(PhantomData)
+    }
+}
+
+impl<A> Default for Union<A> {
+    fn default() -> Self {
+        Union::new()
+    }
+}
+
+impl<A> Gather<BTreeSet<A>, BTreeSet<A>> for Union<A> where A: Ord {
+    fn gather(&mut self, emitted: BTreeSet<A>, value: &mut BTreeSet<A>) {
+        value.extend(emitted);
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn is_commutative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<BTreeSet<A>>, emitted: BTreeSet<A>) -> Option<BTreeSet<A>> {
+        partial.get_or_insert_with(BTreeSet::new).extend(emitted);
+        None
+    }
+
+    fn merge_partial(&mut self, partial: BTreeSet<A>, value: &mut BTreeSet<A>) {
+        value.extend(partial);
+    }
+}
+
+impl<A> Strategy<BTreeSet<A>, BTreeSet<A>> for Union<A> where A: Ord {
+    fn initial() -> BTreeSet<A> {
+        BTreeSet::new()
+    }
+}
+
+/// Keeps the `k` largest emitted values, the largest first.
+pub struct TopK<A> {
+    k: usize,
+    marker: PhantomData<fn(A)>,
+}
+
+impl<A> TopK<A> {
+    pub fn new(k: usize) -> Self {
+        TopK { k, marker: PhantomData }
+    }
+
+    /// Inserts `emitted` in `values` if it is among the `k` largest ones.
+    fn insert(&self, emitted: A, values: &mut Vec<A>) where A: Ord {
+        let index = values.iter().position(|v| emitted > *v).unwrap_or(values.len());
+        if index < self.k {
+            values.insert(index, emitted);
+            values.truncate(self.k);
+        }
+    }
+}
+
+impl<A> Gather<A, Vec<A>> for TopK<A> where A: Ord {
+    fn gather(&mut self, emitted: A, value: &mut Vec<A>) {
+        self.insert(emitted, value);
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+
+    fn is_commutative() -> bool {
+        true
+    }
+
+    fn gather_partial(&self, partial: &mut Option<Vec<A>>, emitted: A) -> Option<A> {
+        self.insert(emitted, partial.get_or_insert_with(Vec::new));
+        None
+    }
+
+    fn merge_partial(&mut self, partial: Vec<A>, value: &mut Vec<A>) {
+        for v in partial {
+            self.gather(v, value);
+        }
+    }
+}
+
+impl<A> Strategy<A, Vec<A>> for TopK<A> where A: Ord {
+    fn initial() -> Vec<A> {
+        Vec::new()
+    }
+}
@@ -1,6 +1,6 @@
 use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
 use continuation::{ContinuationSt, ContinuationPl};
-use process::{Process, ProcessMut, ProcessSt, ProcessMutSt};
+use process::{Process, ProcessMut, ProcessSt, ProcessMutSt, Description};
 use process::{ProcessPl, ProcessMutPl, ConstraintOnValue};
 use signal::signal_runtime::SignalRuntimeRefBase;
 use signal::valued_signal::ValuedSignal;
@@ -13,6 +13,10 @@ pub struct TryEmitValue<S, A> {
 
 impl<S, A> Process for TryEmitValue<S, A> where S: ValuedSignal, A: 'static {
     type Value = bool;
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("try_emit({})", self.signal.label()))
+    }
 }
 
 impl<S, A> ProcessMut for TryEmitValue<S, A> where S: ValuedSignal, A: 'static {}
@@ -0,0 +1,390 @@
+//! Recording of signals as waveforms in a Value Change Dump (VCD) file, to be
+//! inspected in a viewer such as GTKWave.
+//!
+//! Each instant is a time step. A pure signal is a 1-bit wire which is high at
+//! the instants where the signal is present. A valued signal has such a wire
+//! and another variable holding the value of its last emission.
+//!
+//! The signals are sampled at the end of each instant, once they are reset, so
+//! the values come from `get_pre_value`, which doesn't change the signal. The
+//! value of a `MpscSignal` is moved to its consumer, so only its presence can be
+//! recorded, with `watch_pure`.
+
+use std::io::{self, Write};
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use std::collections::BTreeMap;
+
+use runtime::{Runtime, SingleThreadRuntime, ParallelRuntime};
+use continuation::{ContinuationSt, ContinuationPl};
+use process::{Process, ProcessSt, ProcessPl, ConstraintOnValue, Description};
+use signal::Signal;
+use signal::signal_runtime::{SignalRuntimeRefBase, SignalRuntimeRefSt, SignalRuntimeRefPl};
+use signal::valued_signal::GetPreValue;
+
+/// The type of a VCD variable holding the values of a signal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VcdVarKind {
+    /// A bit vector of the given width.
+    Vector(usize),
+    Real,
+    /// A string, which is an extension of the format understood by GTKWave.
+    Str,
+}
+
+/// Values that can be dumped in a VCD file.
+pub trait VcdValue {
+    fn var_kind() -> VcdVarKind;
+
+    /// The value as written in the VCD file, without the prefix of its kind.
+    fn vcd_value(&self) -> String;
+}
+
+impl VcdValue for bool {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Vector(1)
+    }
+
+    fn vcd_value(&self) -> String {
+        if *self { "1".to_owned() } else { "0".to_owned() }
+    }
+}
+
+impl VcdValue for u8 {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Vector(8)
+    }
+
+    fn vcd_value(&self) -> String {
+        format!("{:b}", self)
+    }
+}
+
+impl VcdValue for u32 {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Vector(32)
+    }
+
+    fn vcd_value(&self) -> String {
+        format!("{:b}", self)
+    }
+}
+
+impl VcdValue for u64 {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Vector(64)
+    }
+
+    fn vcd_value(&self) -> String {
+        format!("{:b}", self)
+    }
+}
+
+impl VcdValue for usize {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Vector(64)
+    }
+
+    fn vcd_value(&self) -> String {
+        format!("{:b}", self)
+    }
+}
+
+impl VcdValue for i32 {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Vector(32)
+    }
+
+    fn vcd_value(&self) -> String {
+        format!("{:b}", self)
+    }
+}
+
+impl VcdValue for i64 {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Vector(64)
+    }
+
+    fn vcd_value(&self) -> String {
+        format!("{:b}", self)
+    }
+}
+
+impl VcdValue for isize {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Vector(64)
+    }
+
+    fn vcd_value(&self) -> String {
+        format!("{:b}", self)
+    }
+}
+
+impl VcdValue for f32 {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Real
+    }
+
+    fn vcd_value(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl VcdValue for f64 {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Real
+    }
+
+    fn vcd_value(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl VcdValue for String {
+    fn var_kind() -> VcdVarKind {
+        VcdVarKind::Str
+    }
+
+    fn vcd_value(&self) -> String {
+        // Spaces would end the value.
+        self.replace(char::is_whitespace, "_")
+    }
+}
+
+/// The pre value of `SpmcSignal`, which is `None` only if the signal has never
+/// been emitted.
+impl<T> VcdValue for Option<T> where T: VcdValue {
+    fn var_kind() -> VcdVarKind {
+        T::var_kind()
+    }
+
+    fn vcd_value(&self) -> String {
+        match *self {
+            Some(ref v) => v.vcd_value(),
+            None => "x".to_owned(),
+        }
+    }
+}
+
+/// Keeps the waveforms of the signals it watches. It can be cloned and shared
+/// between the runtimes of a parallel engine.
+#[derive(Clone)]
+pub struct VcdRecorder(Arc<Mutex<VcdContent>>);
+
+struct VcdContent {
+    vars: Vec<VcdVar>,
+    last_instant: Option<usize>,
+}
+
+struct VcdVar {
+    name: String,
+    /// `None` for the presence wires.
+    kind: Option<VcdVarKind>,
+    changes: BTreeMap<usize, String>,
+}
+
+/// Dumps the value of a signal in the VCD format.
+type Dump<R> = fn(&R) -> String;
+
+/// Process installing the sampling of a signal in the runtime. The signal is then
+/// sampled at the end of each instant until the end of the execution.
+pub struct Watch<S> where S: Signal {
+    signal: S,
+    recorder: VcdRecorder,
+    presence: usize,
+    value: Option<(usize, Dump<S::RuntimeRef>)>,
+}
+
+impl VcdRecorder {
+    /// Creates a recorder with no signal.
+    pub fn new() -> Self {
+        VcdRecorder(Arc::new(Mutex::new(VcdContent { vars: Vec::new(), last_instant: None })))
+    }
+
+    fn add_var(&self, name: String, kind: Option<VcdVarKind>) -> usize {
+        let mut content = self.0.lock().unwrap();
+        content.vars.push(VcdVar { name, kind, changes: BTreeMap::new() });
+        content.vars.len() - 1
+    }
+
+    /// Returns a process that starts recording the presence of a signal, which
+    /// can be pure or valued.
+    pub fn watch_pure<S>(&self, name: &str, signal: &S) -> Watch<S> where S: Signal {
+        Watch {
+            signal: signal.clone(),
+            recorder: self.clone(),
+            presence: self.add_var(name.to_owned(), None),
+            value: None,
+        }
+    }
+
+    /// Returns a process that starts recording the presence and the value of a
+    /// valued signal, the value being dumped as a number, a boolean or a string.
+    /// The signal must have a `pre_value`, which rules out the mpsc signals.
+    pub fn watch_value<S>(&self, name: &str, signal: &S) -> Watch<S>
+        where S: Signal, S::RuntimeRef: GetPreValue,
+              <S::RuntimeRef as GetPreValue>::PreValue: VcdValue
+    {
+        fn dump<R>(r: &R) -> String where R: GetPreValue, R::PreValue: VcdValue {
+            r.get_pre_value().vcd_value()
+        }
+        let kind = <<S::RuntimeRef as GetPreValue>::PreValue as VcdValue>::var_kind();
+        let mut watch = self.watch_pure(name, signal);
+        watch.value = Some((self.add_var(format!("{}_value", name), Some(kind)), dump));
+        watch
+    }
+
+    /// Same as `watch_value`, the value being dumped as a string using `Display`.
+    pub fn watch_display<S>(&self, name: &str, signal: &S) -> Watch<S>
+        where S: Signal, S::RuntimeRef: GetPreValue,
+              <S::RuntimeRef as GetPreValue>::PreValue: Display
+    {
+        fn dump<R>(r: &R) -> String where R: GetPreValue, R::PreValue: Display {
+            r.get_pre_value().to_string().vcd_value()
+        }
+        let mut watch = self.watch_pure(name, signal);
+        watch.value = Some((
+            self.add_var(format!("{}_value", name), Some(VcdVarKind::Str)), dump));
+        watch
+    }
+
+    /// Records the state of a signal at the end of an instant.
+    fn sample(&self, instant: usize, presence: usize, present: bool,
+              value: Option<(usize, String)>)
+    {
+        let mut content = self.0.lock().unwrap();
+        if present {
+            let changes = &mut content.vars[presence].changes;
+            changes.insert(instant, "1".to_owned());
+            changes.insert(instant + 1, "0".to_owned());
+        }
+        if let Some((var, value)) = value {
+            content.vars[var].changes.insert(instant, value);
+        }
+        content.last_instant = content.last_instant.max(Some(instant));
+    }
+
+    /// Writes the recorded waveforms as a VCD file.
+    pub fn write_vcd<W>(&self, mut out: W) -> io::Result<()> where W: Write {
+        let content = self.0.lock().unwrap();
+        let ids: Vec<_> = (0..content.vars.len()).map(identifier).collect();
+        writeln!(out, "$timescale 1 ns $end")?;
+        writeln!(out, "$scope module reactive $end")?;
+        for (var, id) in content.vars.iter().zip(&ids) {
+            let (kind, width) = match var.kind {
+                None => ("wire", 1),
+                Some(VcdVarKind::Vector(width)) => ("wire", width),
+                Some(VcdVarKind::Real) => ("real", 64),
+                Some(VcdVarKind::Str) => ("string", 1),
+            };
+            writeln!(out, "$var {} {} {} {} $end", kind, width, id, var.name)?;
+        }
+        writeln!(out, "$upscope $end")?;
+        writeln!(out, "$enddefinitions $end")?;
+        writeln!(out, "$dumpvars")?;
+        for (var, id) in content.vars.iter().zip(&ids) {
+            match var.kind {
+                None => writeln!(out, "0{}", id)?,
+                Some(VcdVarKind::Vector(_)) => writeln!(out, "bx {}", id)?,
+                _ => (),
+            }
+        }
+        writeln!(out, "$end")?;
+        let end = match content.last_instant {
+            Some(instant) => instant + 1,
+            None => return Ok(()),
+        };
+        let mut changes: BTreeMap<usize, Vec<(usize, &str)>> = BTreeMap::new();
+        for (i, var) in content.vars.iter().enumerate() {
+            let mut current = None;
+            for (&instant, value) in var.changes.range(..end) {
+                if current != Some(value) {
+                    changes.entry(instant).or_default().push((i, value));
+                    current = Some(value);
+                }
+            }
+        }
+        for (instant, values) in changes {
+            writeln!(out, "#{}", instant)?;
+            for (i, value) in values {
+                match content.vars[i].kind {
+                    None => writeln!(out, "{}{}", value, ids[i])?,
+                    Some(VcdVarKind::Vector(_)) => writeln!(out, "b{} {}", value, ids[i])?,
+                    Some(VcdVarKind::Real) => writeln!(out, "r{} {}", value, ids[i])?,
+                    Some(VcdVarKind::Str) => writeln!(out, "s{} {}", value, ids[i])?,
+                }
+            }
+        }
+        writeln!(out, "#{}", end)
+    }
+}
+
+impl Default for VcdRecorder {
+    fn default() -> Self {
+        VcdRecorder::new()
+    }
+}
+
+/// The identifier of the `n`th variable, using the printable ASCII characters.
+fn identifier(mut n: usize) -> String {
+    let mut id = String::new();
+    loop {
+        id.push((b'!' + (n % 94) as u8) as char);
+        n /= 94;
+        if n == 0 {
+            return id;
+        }
+        n -= 1;
+    }
+}
+
+impl<S> Watch<S> where S: Signal {
+    /// Returns the function sampling the signal at the end of each instant.
+    fn sampler<R>(self) -> impl FnMut(usize)
+        where R: Runtime, S::RuntimeRef: SignalRuntimeRefBase<R>
+    {
+        let Watch { signal, recorder, presence, value } = self;
+        let runtime_ref = signal.runtime();
+        move |instant| {
+            // Emissions at the next instant may already be recorded in parallel.
+            let present = runtime_ref.emissions().present_before(instant + 1);
+            let value = match value {
+                Some((var, dump)) if present => Some((var, dump(&runtime_ref))),
+                _ => None,
+            };
+            recorder.sample(instant, presence, present, value);
+        }
+    }
+}
+
+impl<S> Process for Watch<S> where S: Signal {
+    type Value = ();
+
+    fn describe(&self) -> Description {
+        Description::leaf(format!("watch({})", self.signal.label()))
+    }
+}
+
+// Implements the traits for the single thread version of the library.
+
+impl<S> ProcessSt for Watch<S> where S: Signal, S::RuntimeRef: SignalRuntimeRefSt {
+    fn call<C>(self, runtime: &mut SingleThreadRuntime, next: C) where C: ContinuationSt<()> {
+        runtime.watch(Box::new(self.sampler::<SingleThreadRuntime>()));
+        next.call(runtime, ());
+    }
+}
+
+// Implements the traits for the parallel version of the library.
+
+impl<S> ConstraintOnValue for Watch<S> where S: Signal {
+    type T = ();
+}
+
+impl<S> ProcessPl for Watch<S>
+    where S: Signal + Send + Sync, S::RuntimeRef: SignalRuntimeRefPl
+{
+    fn call<C>(self, runtime: &mut ParallelRuntime, next: C) where C: ContinuationPl<()> {
+        runtime.watch(Box::new(self.sampler::<ParallelRuntime>()));
+        next.call(runtime, ());
+    }
+}
@@ -0,0 +1,73 @@
+//! Helpers to test reactive programs.
+//!
+//! In a reactive program the processes of an instant are executed in some order
+//! chosen by the runtime, and a program can give different results when this
+//! order changes, typically when the order of the values gathered by a signal
+//! matters. The single thread runtime always uses the same order, so such a
+//! program may seem fine until it is executed by the parallel engine. Here the
+//! program is executed with shuffled orders (see `execute_process_shuffled`) to
+//! find the seeds for which the result is different.
+//!
+//! Most of the processes and signals are also implemented twice, once for each
+//! engine, so `assert_same_execution` checks that a program gives the same result
+//! and emits the same values with both engines.
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+use process::{ProcessSt, ProcessPl, execute_process, execute_process_shuffled};
+use process::execute_process_parallel;
+use signal::record::Recorder;
+
+/// Executes the process built by `factory` with the usual order, then with the
+/// orders given by each of the `seeds`, and returns the seeds leading to a result
+/// different from the first one.
+pub fn divergent_seeds<F, P>(mut factory: F, seeds: Range<u64>) -> Vec<u64>
+    where F: FnMut() -> P, P: ProcessSt, P::Value: PartialEq
+{
+    let expected = execute_process(factory());
+    seeds.filter(|&seed| execute_process_shuffled(factory(), seed) != expected).collect()
+}
+
+/// Panics if the result of the process built by `factory` depends on the order of
+/// execution for any of the `seeds`, showing the first divergent seed.
+pub fn assert_order_independent<F, P>(mut factory: F, seeds: Range<u64>)
+    where F: FnMut() -> P, P: ProcessSt, P::Value: PartialEq + Debug
+{
+    let expected = execute_process(factory());
+    for seed in seeds {
+        let result = execute_process_shuffled(factory(), seed);
+        if result != expected {
+            panic!("With the seed {} the result is {:?} instead of {:?}.",
+                   seed, result, expected);
+        }
+    }
+}
+
+/// Executes the process built by `st` with the single thread runtime, then the one
+/// built by `pl` with the parallel engine for each of the numbers of runtimes, and
+/// panics if the results or the values emitted on the signals recorded with the
+/// `Recorder` are different, showing the first instant at which they differ.
+///
+/// The two factories should build the same program with the signals of each
+/// engine, and record the same signals under the same names.
+pub fn assert_same_execution<FS, PS, FP, PP>(st: FS, mut pl: FP, num_runtimes: &[usize])
+    where FS: FnOnce(&Recorder) -> PS, PS: ProcessSt, PS::Value: PartialEq<PP::Value> + Debug,
+          FP: FnMut(&Recorder) -> PP, PP: ProcessPl, PP::Value: Debug
+{
+    let recorder = Recorder::new();
+    let expected = execute_process(st(&recorder));
+    let expected_log = recorder.log();
+    for &n in num_runtimes {
+        let recorder = Recorder::new();
+        let result = execute_process_parallel(pl(&recorder), n);
+        let log = recorder.log();
+        if let Some(instant) = expected_log.first_divergence(&log) {
+            panic!("With {} runtimes the signals first differ at instant {}: {:?} instead of {:?}.",
+                   n, instant, log.emitted_at(instant), expected_log.emitted_at(instant));
+        }
+        if expected != result {
+            panic!("With {} runtimes the result is {:?} instead of {:?}.", n, result, expected);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+extern crate reactive;
+
+use reactive::process::{Process, ProcessMut, value_proc};
+use reactive::process::{execute_process, execute_process_parallel};
+use reactive::cell::{InstantCell, InstantCellSt, InstantCellPl};
+
+#[test]
+fn instant_cell_s () {
+    let cell = InstantCellSt::new(vec![], |x: i32, v: &mut Vec<i32>| v.push(x));
+    let p1 = cell.write(1).then(cell.read());
+    let p2 = value_proc(()).pause().then(cell.write(2).join(cell.write(3)));
+    let p3 = cell.read().pause().then(cell.read()).pause().then(cell.read());
+    assert_eq!(execute_process(p1.join(p2).join(p3)), ((vec![], ((), ())), vec![1, 2, 3]));
+    assert_eq!(execute_process(cell.read()), vec![1, 2, 3]);
+}
+
+#[test]
+fn instant_cell_p () {
+    let cell = InstantCellPl::new(0, |x: i32, sum: &mut i32| *sum += x);
+    let writer = cell.write(1).pause().repeat(10);
+    let reader = value_proc(()).pause_n(5).then(cell.read());
+    let p = writer.join(cell.write(100)).join(reader.join(cell.read()));
+    assert_eq!(execute_process_parallel(p, 4), ((vec![(); 10], ()), (105, 0)));
+    assert_eq!(cell.get(), 110);
+}
@@ -1,8 +1,18 @@
 extern crate reactive;
 
-use reactive::process::{Process, ProcessMut, value_proc, join_all};
-use reactive::process::{execute_process, execute_process_parallel};
+use std::sync::{Arc, Mutex};
+use std::rc::Rc;
+use std::cell::Cell;
+
+use reactive::process::{Process, ProcessMut, value_proc, join_all, join_all_each, join_all_emit};
+use reactive::process::{ProcessSt, execute_process, execute_process_parallel};
+use reactive::process::{execute_process_parallel_seeded, execute_process_parallel_deterministic};
+use reactive::runtime::{Runtime, SingleThreadRuntime};
 use reactive::process::LoopStatus::{Continue, Exit};
+use reactive::process::Description;
+use reactive::signal::{Signal, PureSignal, ValuedSignal};
+use reactive::signal::single_thread::{PureSignalSt, MpmcSignalSt};
+use reactive::signal::parallel::MpmcSignalPl;
 
 #[test]
 fn process42_s() {
@@ -106,3 +116,154 @@ fn while_repeat_join () {
     let p2 = value_proc(()).map(say_hello).pause().repeat(5);
     assert_eq!(execute_process_parallel(p1.join(p2), 2), (10, (1..6).collect()));
 }
+
+#[test]
+fn join_all_each_s () {
+    let completed = Arc::new(Mutex::new(Vec::new()));
+    let completed_clone = completed.clone();
+    let ps = (0..3).map(|i| value_proc(()).pause().repeat(3-i).map(move |_| i));
+    let p = join_all_each(ps, move |i| {
+        completed_clone.lock().unwrap().push(i);
+        value_proc(i*2)
+    });
+    assert_eq!(execute_process(p), vec![0, 2, 4]);
+    assert_eq!(*completed.lock().unwrap(), vec![2, 1, 0]);
+}
+
+#[test]
+fn join_all_each_p () {
+    let completed = Arc::new(Mutex::new(Vec::new()));
+    let completed_clone = completed.clone();
+    let ps = (0..3).map(|i| value_proc(()).pause().repeat(3-i).map(move |_| i));
+    let p = join_all_each(ps, move |i| {
+        completed_clone.lock().unwrap().push(i);
+        value_proc(i*2).pause()
+    });
+    assert_eq!(execute_process_parallel(p, 2), vec![0, 2, 4]);
+    assert_eq!(*completed.lock().unwrap(), vec![2, 1, 0]);
+}
+
+#[test]
+fn join_all_emit_s () {
+    let s = MpmcSignalSt::default();
+    let s2 = s.clone();
+    let ps = vec![value_proc(1).pause_n(2), value_proc(2).pause_n(0)];
+    let received = s.await().and_then(move |v| s2.await().map(move |w| (v, w)));
+    let p = join_all_emit(ps, &s).join(received);
+    assert_eq!(execute_process(p), (vec![(), ()], (vec![2], vec![1])));
+}
+
+#[test]
+fn join_all_emit_p () {
+    let s = MpmcSignalPl::default();
+    let s2 = s.clone();
+    let ps = vec![value_proc(1).pause_n(2), value_proc(2).pause_n(0)];
+    let received = s.await().and_then(move |v| s2.await().map(move |w| (v, w)));
+    let p = join_all_emit(ps, &s).join(received);
+    assert_eq!(execute_process_parallel(p, 2), (vec![(), ()], (vec![2], vec![1])));
+}
+
+#[test]
+fn pause_n () {
+    let p = value_proc(1).pause_n(3).join(value_proc(2).pause().pause().pause());
+    assert_eq!(execute_process(p.pause_n(0)), (1, 2));
+    let p = value_proc(()).pause_n(2).repeat(3).map(|v| v.len());
+    assert_eq!(execute_process_parallel(p, 2), 3);
+}
+
+#[test]
+fn every_s () {
+    let counter = Rc::new(Cell::new(0));
+    let counter_clone = counter.clone();
+    let incr = move |()| counter_clone.set(counter_clone.get() + 1);
+    let p = value_proc(()).map(incr).every(3);
+    let mut runtime = SingleThreadRuntime::new();
+    p.call(&mut runtime, |_: &mut SingleThreadRuntime, ()| ());
+    let mut executions = Vec::new();
+    for _ in 0..7 {
+        runtime.instant();
+        executions.push(counter.get());
+    }
+    assert_eq!(executions, vec![1, 1, 1, 2, 2, 2, 3]);
+}
+
+#[test]
+fn long_wait_s () {
+    let p = value_proc(1).pause_until(1_000_000).join(value_proc(2).pause_n(2_000_000));
+    assert_eq!(execute_process(p.pause()), (1, 2));
+}
+
+#[test]
+fn long_wait_stepped_s () {
+    let done = Rc::new(Cell::new(false));
+    let done_clone = done.clone();
+    let p = value_proc(()).pause_n(5).map(move |()| done_clone.set(true));
+    let mut runtime = SingleThreadRuntime::new();
+    p.call(&mut runtime, |_: &mut SingleThreadRuntime, ()| ());
+    for _ in 0..5 {
+        assert!(runtime.instant());
+        assert!(!done.get());
+    }
+    assert!(!runtime.instant());
+    assert!(done.get());
+}
+
+#[test]
+fn long_wait_p () {
+    let p = value_proc(1).pause_until(1_000_000).join(value_proc(2).pause_n(2_000_000));
+    let p = p.join(value_proc(3).pause_n(1_000_001));
+    assert_eq!(execute_process_parallel(p, 3), ((1, 2), 3));
+}
+
+#[test]
+fn describe_process() {
+    let s = PureSignalSt::new().named("go");
+    let p = value_proc(1).pause().map(|v| v + 1)
+        .join(s.present_else(value_proc(()), s.emit()).loop_proc());
+    let expected = Description::node("join", vec![
+        Description::node("map", vec![
+            Description::node("pause", vec![Description::leaf("value")])]),
+        Description::node("loop", vec![
+            Description::node("present_else(go)", vec![
+                Description::leaf("value"), Description::leaf("emit(go)")])])]);
+    assert_eq!(p.describe(), expected);
+    assert_eq!(expected.to_dot(), "digraph process {\n\
+                                   \x20   n0 [label=\"join\"];\n\
+                                   \x20   n1 [label=\"map\"];\n\
+                                   \x20   n2 [label=\"pause\"];\n\
+                                   \x20   n3 [label=\"value\"];\n\
+                                   \x20   n2 -> n3;\n\
+                                   \x20   n1 -> n2;\n\
+                                   \x20   n0 -> n1;\n\
+                                   \x20   n4 [label=\"loop\"];\n\
+                                   \x20   n5 [label=\"present_else(go)\"];\n\
+                                   \x20   n6 [label=\"value\"];\n\
+                                   \x20   n5 -> n6;\n\
+                                   \x20   n7 [label=\"emit(go)\"];\n\
+                                   \x20   n5 -> n7;\n\
+                                   \x20   n4 -> n5;\n\
+                                   \x20   n0 -> n4;\n\
+                                   }\n");
+}
+
+#[test]
+fn deterministic_p () {
+    let expected = {
+        let s = MpmcSignalSt::default();
+        let emissions = join_all((0..20).map(|i| s.emit(i).pause().then(s.emit(i * 10))));
+        execute_process(emissions.then(s.await()))
+    };
+    let program = || {
+        let s = MpmcSignalPl::default();
+        let emissions = join_all((0..20).map(|i| s.emit(i).pause().then(s.emit(i * 10))));
+        emissions.then(s.await())
+    };
+    let mut seeded = execute_process_parallel_seeded(program(), 4, 42);
+    seeded.sort();
+    assert_eq!(seeded, (0..20).map(|i| i * 10).collect::<Vec<_>>());
+    for &n in &[1, 3, 4] {
+        for _ in 0..10 {
+            assert_eq!(execute_process_parallel_deterministic(program(), n), expected);
+        }
+    }
+}
@@ -1,11 +1,22 @@
 extern crate reactive;
 
-use reactive::process::{Process, ProcessMut, value_proc};
-use reactive::process::{execute_process, execute_process_parallel};
+use std::rc::Rc;
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+
+use reactive::runtime::{Runtime, SingleThreadRuntime};
+use reactive::process::{Process, ProcessMut, ProcessSt, value_proc, join_all};
+use reactive::process::{execute_process, execute_process_parallel, execute_process_parallel_deterministic};
 use reactive::process::LoopStatus::{Continue, Exit};
-use reactive::signal::{Signal, PureSignal, ValuedSignal};
+use reactive::signal::{Signal, PureSignal, ValuedSignal, PresenceExpr};
+use reactive::signal::valued_signal::{Gather, Strategy};
+use reactive::signal::valued_signal::strategies::{Sum, Max, First, Last, Count, TopK};
 use reactive::signal::single_thread::{PureSignalSt, MpmcSignalSt, MpscSignalSt, SpmcSignalSt};
+use reactive::signal::single_thread::{RegisterSignalSt, SignalArraySt};
 use reactive::signal::parallel::{PureSignalPl, MpmcSignalPl, MpscSignalPl, SpmcSignalPl};
+use reactive::signal::parallel::{RegisterSignalPl, SignalArrayPl};
+use reactive::signal::vcd::VcdRecorder;
+use reactive::signal::record::{Recorder, Log};
 
 #[test]
 fn pure_signal_s () {
@@ -145,6 +156,461 @@ fn mpsc_signal_p () {
     assert_eq!(vals, expected_vals);
 }
 
+#[test]
+fn emit_after_s () {
+    let s = SpmcSignalSt::new();
+    let p1 = s.emit_after(3, 5);
+    let p2 = value_proc(()).pause_n(2).then(s.present_else(value_proc(0), value_proc(1)));
+    let p3 = value_proc(()).pause_n(3).then(s.await());
+    assert_eq!(execute_process(p1.join(p2).join(p3)), (((), 1), 5));
+}
+
+#[test]
+fn emit_after_p () {
+    let s = PureSignalPl::new();
+    let p1 = s.emit_after(2);
+    let p2 = value_proc(()).pause().pause().then(s.present_else(value_proc(0), value_proc(1)));
+    assert_eq!(execute_process_parallel(p1.join(p2), 2), ((), 0));
+}
+
+#[test]
+fn pre_s () {
+    let s = MpmcSignalSt::default();
+    let p1 = s.emit(3).pause().then(s.emit(4));
+    let p2 = value_proc(()).pause().then(s.pre_value().join(s.pre_present()));
+    let p3 = s.pre_value().join(s.pre_present());
+    assert_eq!(execute_process(p1.join(p2).join(p3)), (((), (vec![3], true)), (vec![], false)));
+}
+
+#[test]
+fn pre_p () {
+    let s = SpmcSignalPl::new();
+    let p1 = s.emit(1).pause().pause();
+    let p2 = value_proc(()).pause_n(2).then(s.pre_value().join(s.pre_present()));
+    assert_eq!(execute_process_parallel(p1.join(p2), 2), ((), (Some(1), false)));
+}
+
+#[test]
+fn pre_twice_s () {
+    let s = SpmcSignalSt::new();
+    let p1 = s.emit(1).join(s.await());
+    let p2 = value_proc(()).pause().then(s.pre_value().join(s.pre_value()));
+    assert_eq!(execute_process(p1.join(p2)), (((), 1), (Some(1), Some(1))));
+}
+
+#[test]
+fn history_s () {
+    let s = MpmcSignalSt::with_history(0, |x: i32, acc: &mut i32| *acc += x, 2);
+    let p = s.emit(1).pause().then(s.emit(2)).pause().then(s.emit(3)).pause().then(s.history());
+    assert_eq!(execute_process(p.join(s.emit(4))), (vec![(1, 2), (2, 3)], ()));
+}
+
+#[test]
+fn history_p () {
+    let s = SpmcSignalPl::with_history(3);
+    let p = s.emit(1).pause_n(2).then(s.emit(5)).pause().then(s.history());
+    assert_eq!(execute_process_parallel(p, 2), vec![(0, 1), (2, 5)]);
+}
+
+#[test]
+fn deterministic_gather_p () {
+    let expected = {
+        let s = MpscSignalSt::default();
+        let ps = (0..100).map(|i| value_proc(()).pause().then(s.emit(i)));
+        execute_process(join_all(ps).then(s.await()))
+    };
+    for _ in 0..10 {
+        let s = MpscSignalPl::default();
+        let ps = (0..100).map(|i| value_proc(()).pause().then(s.emit(i)));
+        let p = join_all(ps).then(s.await());
+        assert_eq!(execute_process_parallel_deterministic(p, 4), expected);
+    }
+}
+
+#[test]
+fn deterministic_await_gather_p () {
+    let expected = {
+        let (s, t) = (MpmcSignalSt::default(), MpscSignalSt::default());
+        let ps = (0..20).map(|i| {
+            let t = t.clone();
+            value_proc(()).pause().then(s.emit(i)).then(s.await())
+                .and_then(move |v: Vec<usize>| t.emit(i * 100 + v[i]))
+        });
+        execute_process(join_all(ps).then(t.await()))
+    };
+    for n in 1..5 {
+        let (s, t) = (MpmcSignalPl::default(), MpscSignalPl::default());
+        let ps = (0..20).map(|i| {
+            let t = t.clone();
+            value_proc(()).pause().then(s.emit(i)).then(s.await())
+                .and_then(move |v: Vec<usize>| t.emit(i * 100 + v[i]))
+        });
+        assert_eq!(execute_process_parallel_deterministic(join_all(ps).then(t.await()), n), expected);
+    }
+}
+
+#[test]
+fn deterministic_wakeup_p () {
+    let expected = {
+        let (s, t) = (PureSignalSt::new(), MpmcSignalSt::default());
+        let p1 = value_proc(()).pause().then(s.await_immediate()).then(t.emit(100));
+        let p2 = value_proc(()).pause().then(s.emit()).then(t.emit(200)).then(t.emit(300));
+        execute_process(p1.join(p2).then(t.emit(400)).then(t.await()))
+    };
+    for _ in 0..100 {
+        let (s, t) = (PureSignalPl::new(), MpmcSignalPl::default());
+        let p1 = value_proc(()).pause().then(s.await_immediate()).then(t.emit(100));
+        let p2 = value_proc(()).pause().then(s.emit()).then(t.emit(200)).then(t.emit(300));
+        let p = p1.join(p2).then(t.emit(400)).then(t.await());
+        assert_eq!(execute_process_parallel_deterministic(p, 2), expected);
+    }
+}
+
+#[test]
+fn monoid_gather_s () {
+    let s = MpmcSignalSt::from_monoid();
+    let p = s.emit("a".to_string()).join(s.emit("b".to_string())).then(s.await());
+    assert_eq!(execute_process(p), "ab".to_string());
+}
+
+#[test]
+fn monoid_gather_p () {
+    let s = MpmcSignalPl::from_monoid();
+    let mut ps = Vec::new();
+    for i in 0..1000 {
+        ps.push(s.emit(vec![i]));
+    }
+    let p = join_all(ps).then(s.await()).map(|mut v: Vec<i32>| { v.sort(); v });
+    assert_eq!(execute_process_parallel(p, 4), (0..1000).collect::<Vec<_>>());
+}
+
+#[test]
+fn numeric_monoid_p () {
+    let s = MpmcSignalPl::from_monoid();
+    let ps: Vec<_> = (0..1000u64).map(|i| s.emit(i)).collect();
+    let p = join_all(ps).then(s.await());
+    assert_eq!(execute_process_parallel(p, 4), 999 * 1000 / 2);
+}
+
+#[test]
+fn strategies_s () {
+    let max = MpmcSignalSt::from_strategy(Max::new());
+    let first = MpscSignalSt::from_strategy(First::new());
+    let p1 = max.emit(2).join(max.emit(5)).join(max.emit(3)).then(max.await());
+    let p2 = first.emit("a").join(first.emit("b")).then(first.await());
+    assert_eq!(execute_process(p1.join(p2)), (Some(5), Some("a")));
+}
+
+/// An associative gather which doesn't gather partially.
+struct Product;
+
+impl Gather<u64, u64> for Product {
+    fn gather(&mut self, emitted: u64, value: &mut u64) {
+        *value *= emitted;
+    }
+
+    fn is_associative() -> bool {
+        true
+    }
+}
+
+impl Strategy<u64, u64> for Product {
+    fn initial() -> u64 {
+        1
+    }
+}
+
+#[test]
+fn associative_without_partial_p () {
+    let s = MpmcSignalPl::from_strategy(Product);
+    let ps: Vec<_> = (1..11u64).map(|i| value_proc(()).pause().then(s.emit(i))).collect();
+    let p = join_all(ps).then(s.await());
+    assert_eq!(execute_process_parallel(p, 4), 3628800);
+}
+
+#[test]
+fn strategies_p () {
+    let sum = MpmcSignalPl::from_strategy(Sum::new());
+    let count = MpmcSignalPl::from_strategy(Count::new());
+    let top = MpmcSignalPl::from_strategy(TopK::new(3));
+    let mut ps = Vec::new();
+    for i in 0..1000 {
+        ps.push(sum.emit(i).join(count.emit(())).join(top.emit(i)));
+    }
+    let p = join_all(ps).then(sum.await().join(count.await()).join(top.await()));
+    assert_eq!(execute_process_parallel(p, 4), ((499500, 1000), vec![999, 998, 997]));
+}
+
+#[test]
+fn strategies_deterministic_p () {
+    let expected = {
+        let last = MpmcSignalSt::from_strategy(Last::default());
+        let ps = (0..100).map(|i| value_proc(()).pause().then(last.emit(i)));
+        execute_process(join_all(ps).then(last.await()))
+    };
+    let last = MpmcSignalPl::from_strategy(Last::default());
+    let ps = (0..100).map(|i| value_proc(()).pause().then(last.emit(i)));
+    let p = join_all(ps).then(last.await());
+    assert_eq!(execute_process_parallel_deterministic(p, 4), expected);
+}
+
+#[test]
+fn register_signal_s () {
+    let s = RegisterSignalSt::new(0);
+    let p1 = s.emit(1).pause_n(2).then(s.emit(2));
+    let p2 = s.read().join(s.await()).pause().then(s.read().join(s.pre_present()));
+    let p3 = value_proc(()).pause_n(2).then(s.read().join(s.await()));
+    assert_eq!(execute_process(p1.join(p2).join(p3)), (((), (1, true)), (1, 2)));
+    assert_eq!(s.value(), 2);
+}
+
+#[test]
+fn register_signal_p () {
+    let s = RegisterSignalPl::new(vec![0]);
+    let p1 = s.emit(vec![1]).pause();
+    let p2 = value_proc(()).pause_n(3).then(s.read().join(s.present_else(value_proc(1), value_proc(0))));
+    assert_eq!(execute_process_parallel(p1.join(p2), 2), ((), (vec![1], 0)));
+}
+
+#[test]
+fn signal_array_s () {
+    let a = SignalArraySt::new(4);
+    let p1 = value_proc(()).pause().then(a.emit_at(3, 'c').join(a.emit_at(1, 'a')));
+    let p2 = a.await_any().join(a.present_else(value_proc(true), value_proc(false)));
+    assert_eq!(execute_process(p1.join(p2)), (((), ()), (vec![(1, 'a'), (3, 'c')], false)));
+}
+
+#[test]
+fn signal_array_immediate_s () {
+    let a = SignalArraySt::new(4);
+    let p1 = value_proc(()).pause().then(a.emit_at(3, 'c')).then(a.emit_at(1, 'a'))
+        .pause().then(a.emit_at(0, 'z'));
+    // The immediate await is woken up by the first emission and misses the second one.
+    let p2 = a.await_any_immediate().join(a.await_any());
+    let p3 = a.await_any_immediate().pause().then(a.await_any_immediate());
+    assert_eq!(execute_process(p1.join(p2).join(p3)),
+               (((), (vec![(3, 'c')], vec![(1, 'a'), (3, 'c')])), vec![(0, 'z')]));
+}
+
+#[test]
+fn signal_array_p () {
+    let a = SignalArrayPl::new(100);
+    assert_eq!(a.len(), 100);
+    assert!(!a.is_empty() && SignalArrayPl::<i32>::new(0).is_empty());
+    let mut ps = Vec::new();
+    for i in 0..50 {
+        ps.push(a.emit_at(2 * i, i));
+    }
+    let p = value_proc(()).pause().then(join_all(ps)).join(a.await_any());
+    let (_, present) = execute_process_parallel(p, 4);
+    assert_eq!(present, (0..50).map(|i| (2 * i, i)).collect::<Vec<_>>());
+}
+
+#[test]
+fn signal_array_immediate_p () {
+    let a = SignalArrayPl::new(4);
+    let p1 = value_proc(()).pause().then(a.emit_at(2, 20)).pause().then(a.emit_at(0, 0));
+    let p2 = a.await_any_immediate().join(a.await_any());
+    let p3 = a.await_any_immediate().pause().then(a.await_any_immediate());
+    assert_eq!(execute_process_parallel(p1.join(p2).join(p3), 2),
+               (((), (vec![(2, 20)], vec![(2, 20)])), vec![(0, 0)]));
+}
+
+#[test]
+fn on_each_s () {
+    let s = MpmcSignalSt::new(0, |x: i32, acc: &mut i32| *acc += x);
+    let sum = Rc::new(Cell::new(0));
+    let sum2 = sum.clone();
+    let handler = move |x: i32| {
+        let sum = sum2.clone();
+        value_proc(x).map(move |x| sum.set(sum.get() + x))
+    };
+    let p1 = value_proc(()).pause().then(s.emit(1).join(s.emit(2))).pause().then(s.emit(4));
+    execute_process(p1.join(s.on_each(handler).repeat(2)));
+    assert_eq!(sum.get(), 3);
+}
+
+#[test]
+fn on_each_p () {
+    let s = MpscSignalPl::default();
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let received2 = received.clone();
+    let handler = move |x: i32| {
+        let received = received2.clone();
+        value_proc(x).map(move |x| received.lock().unwrap().push(x))
+    };
+    let emit = s.emit(1).join(s.emit(2));
+    let each = emit.join(s.on_each(handler.clone())).join(s.on_each(handler));
+    let (_, mut v) = execute_process_parallel(each.join(s.await()), 1);
+    let mut received = received.lock().unwrap().clone();
+    v.sort();
+    received.sort();
+    assert_eq!((v, received), (vec![1, 2], vec![1, 1, 2, 2]));
+}
+
+#[test]
+fn on_each_replay_s () {
+    let s = MpmcSignalSt::new(0, |x: i32, acc: &mut i32| *acc += x);
+    let sum = Rc::new(Cell::new(0));
+    let sum2 = sum.clone();
+    let handler = move |x: i32| {
+        let sum = sum2.clone();
+        value_proc(x).map(move |x| sum.set(sum.get() + x))
+    };
+    let p = s.on_each(handler.clone()).join(s.emit(1)).join(s.on_each(handler));
+    execute_process(p.then(s.emit(2)));
+    assert_eq!(sum.get(), 2);
+}
+
+#[test]
+fn on_each_after_emit_s () {
+    let s = MpmcSignalSt::new(0, |x: i32, acc: &mut i32| *acc += x);
+    let sum = Rc::new(Cell::new(0));
+    let (sum2, sum3) = (sum.clone(), sum.clone());
+    let handler = move |x: i32| {
+        let sum = sum2.clone();
+        value_proc(x).map(move |x| sum.set(sum.get() + x))
+    };
+    let each = s.emit(1).then(s.on_each(handler)).then(value_proc(()).map(move |()| sum3.get()));
+    let emit = value_proc(()).pause().then(s.emit(2)).pause().then(s.emit(4));
+    assert_eq!(execute_process(each.join(emit)), (2, ()));
+}
+
+/// A gather accepting two types of values.
+struct Concat;
+
+impl Gather<i32, String> for Concat {
+    fn gather(&mut self, emitted: i32, value: &mut String) {
+        value.push_str(&emitted.to_string());
+    }
+}
+
+impl Gather<&'static str, String> for Concat {
+    fn gather(&mut self, emitted: &'static str, value: &mut String) {
+        value.push_str(emitted);
+    }
+}
+
+impl Strategy<i32, String> for Concat {
+    fn initial() -> String {
+        String::new()
+    }
+}
+
+#[test]
+#[should_panic(expected = "handlers of another type")]
+fn on_each_type_mismatch_s () {
+    let s = MpmcSignalSt::from_strategy::<i32, _, _>(Concat);
+    let each = s.on_each(|_: &'static str| value_proc(()));
+    execute_process(each.join(s.emit(1)));
+}
+
+#[test]
+fn derived_signal_s () {
+    let s = MpmcSignalSt::default();
+    let len = s.map(|v: Vec<i32>| v.len());
+    let p1 = s.emit(1).join(s.emit(2)).then(len.await());
+    let f = SpmcSignalSt::new();
+    let big = f.filter(|x: &i32| *x > 2);
+    let p2 = f.emit(1).pause().then(f.emit(3));
+    let p3 = big.present_else(value_proc(true), value_proc(false)).join(big.await());
+    assert_eq!(execute_process(p1.join(p2).join(p3)), ((2, ()), (false, 3)));
+}
+
+#[test]
+fn derived_signal_p () {
+    let s1 = PureSignalPl::new();
+    let s2 = SpmcSignalPl::new();
+    let p1 = value_proc(()).pause_n(2).then(s2.emit(5));
+    let p2 = s1.merge(s2.map(|x: i32| x * 2)).await_immediate()
+        .then(s2.map(|x: i32| x * 2).await());
+    assert_eq!(execute_process_parallel(p1.join(p2), 2), ((), 10));
+}
+
+#[test]
+fn merge_await_s () {
+    let (s1, s2) = (PureSignalSt::new(), PureSignalSt::new());
+    let done = Rc::new(Cell::new(false));
+    let done_clone = done.clone();
+    let p1 = value_proc(()).pause_n(3).then(s2.emit());
+    let p2 = s1.merge(s2.clone()).await_immediate().map(move |()| done_clone.set(true));
+    let mut runtime = SingleThreadRuntime::new();
+    p1.join(p2).call(&mut runtime, |_: &mut SingleThreadRuntime, _| ());
+    for _ in 0..3 {
+        assert!(runtime.instant());
+        assert!(!done.get());
+    }
+    assert!(!runtime.instant());
+    assert!(done.get());
+}
+
+#[test]
+fn merge_await_loop_s () {
+    let (s1, s2) = (PureSignalSt::new(), PureSignalSt::new());
+    let p1 = value_proc(()).pause().then(s1.emit()).pause().repeat(100);
+    let p2 = s1.merge(s2.clone()).await_immediate().pause().repeat(100);
+    execute_process(p1.join(p2));
+    // The awaits on `s2` are dropped instead of piling up.
+    assert!(s2.info().awaiting() <= 1);
+}
+
+#[test]
+fn merge_await_loop_p () {
+    let (s1, s2) = (PureSignalPl::new(), PureSignalPl::new());
+    let p1 = value_proc(()).pause().then(s1.emit()).pause().repeat(100);
+    let p2 = s1.merge(s2.clone()).await_immediate().pause().repeat(100);
+    execute_process_parallel(p1.join(p2), 2);
+    assert!(s2.info().awaiting() <= 1);
+}
+
+#[test]
+fn presence_expr_s () {
+    let (s1, s2, s3) = (PureSignalSt::new(), PureSignalSt::new(), PureSignalSt::new());
+    let p1 = (s1.presence() | s2.presence()).present_else(value_proc(1), value_proc(2));
+    let p2 = (s1.presence() & !s3.presence()).present_else(value_proc(1), value_proc(2));
+    let p3 = (s3.presence() | !s1.presence()).present_else(value_proc(1), value_proc(2));
+    let p = s2.emit().pause().then(s1.emit()).join(p1).join(p2).join(p3);
+    assert_eq!(execute_process(s1.emit().join(p)), ((), ((((), 1), 1), 2)));
+}
+
+#[test]
+fn presence_expr_p () {
+    let (s1, s2) = (PureSignalPl::new(), PureSignalPl::new());
+    let p1 = value_proc(()).pause().then(s2.emit());
+    let expr = !s1.presence() & s2.presence();
+    let p2 = expr.present_else(value_proc(1), value_proc(2))
+        .join(value_proc(()).pause().then(expr.present_else(value_proc(1), value_proc(2))));
+    assert_eq!(execute_process_parallel(p1.join(p2), 2), ((), (2, 1)));
+}
+
+#[test]
+fn presence_expr_filter_p () {
+    let s = SpmcSignalPl::new();
+    let big = s.filter(|x: &i32| *x > 2);
+    let p = (!big.presence()).present_else(value_proc(1), value_proc(2));
+    assert_eq!(execute_process_parallel(s.emit(1).join(p), 2), ((), 1));
+}
+
+#[test]
+fn sustain_s () {
+    let s = PureSignalSt::new();
+    let stop = PureSignalSt::new();
+    let p1 = s.sustain().until(stop.clone());
+    let p2 = value_proc(()).pause_n(2).then(stop.emit());
+    let p3 = value_proc(()).pause_n(3).then(s.pre_present());
+    let p4 = value_proc(()).pause_n(4).then(s.pre_present());
+    assert_eq!(execute_process(p1.join(p2).join(p3).join(p4)), ((((), ()), true), false));
+}
+
+#[test]
+fn sustain_p () {
+    let s = SpmcSignalPl::new();
+    let p1 = s.sustain(7).for_instants(2).then(s.pre_value());
+    let p2 = value_proc(()).pause().then(s.await());
+    let p3 = value_proc(()).pause_n(2).then(s.present_else(value_proc(1), value_proc(0)));
+    assert_eq!(execute_process_parallel(p1.join(p2).join(p3), 2), ((Some(7), 7), 0));
+}
+
 #[test]
 #[should_panic(expected = "Multiple emissions")]
 fn spmc_multiple_emission_s () {
@@ -258,3 +724,149 @@ fn mpsc_multiple_reception_p () {
     let s = MpscSignalPl::default();
     execute_process_parallel(s.emit(true).join(s.await()).join(s.await()), 3);
 }
+
+#[test]
+fn vcd_s () {
+    let recorder = VcdRecorder::new();
+    let s = PureSignalSt::new();
+    let r = RegisterSignalSt::new(0);
+    let watch = recorder.watch_pure("s", &s).join(recorder.watch_value("r", &r));
+    let p1 = value_proc(()).pause().then(s.emit());
+    let p2 = r.emit(5).pause_n(2).then(r.emit(6));
+    execute_process(watch.then(p1.join(p2)));
+    let mut vcd = Vec::new();
+    recorder.write_vcd(&mut vcd).unwrap();
+    let expected = "$timescale 1 ns $end\n$scope module reactive $end\n\
+                    $var wire 1 ! s $end\n$var wire 1 \" r $end\n$var wire 32 # r_value $end\n\
+                    $upscope $end\n$enddefinitions $end\n$dumpvars\n0!\n0\"\nbx #\n$end\n\
+                    #0\n1\"\nb101 #\n#1\n1!\n0\"\n#2\n0!\n1\"\nb110 #\n#3\n";
+    assert_eq!(String::from_utf8(vcd).unwrap(), expected);
+}
+
+#[test]
+fn vcd_p () {
+    let recorder = VcdRecorder::new();
+    let gather = |x: i32, acc: &mut i32| *acc += x;
+    let s = MpmcSignalPl::new(0, gather);
+    let watch = recorder.watch_value("s", &s).join(recorder.watch_display("t", &s));
+    let p = s.emit(1).join(s.emit(2)).pause().pause().then(s.emit(4));
+    execute_process_parallel(watch.then(p), 3);
+    let mut vcd = Vec::new();
+    recorder.write_vcd(&mut vcd).unwrap();
+    let vcd = String::from_utf8(vcd).unwrap();
+    let changes = vcd.split("$end\n").last().unwrap();
+    assert_eq!(changes, "#0\n1!\nb11 \"\n1#\ns3 $\n#1\n0!\n0#\n#2\n1!\nb100 \"\n1#\ns4 $\n#3\n");
+}
+
+#[test]
+fn named_signal_s () {
+    let s = PureSignalSt::new().named("go");
+    let t = SpmcSignalSt::<i32>::new();
+    assert_eq!(s.info().name(), Some("go".to_owned()));
+    assert!(t.info().name().is_none());
+    assert!(s.info().id() != t.info().id());
+    assert_eq!(format!("{}", s.info()), format!("signal `go` (#{})", s.info().id()));
+    let s2 = s.clone().named("stop");
+    assert_eq!(s.info().label(), "stop");
+    let s = s.named("go");
+    let p = s.await_immediate().join(
+        value_proc(()).map(move |()| format!("{:?}", s2)).pause().then(s.emit()));
+    execute_process(p);
+    let debug = format!("{:?}", s);
+    assert!(debug.starts_with("PureSignalSt { id: "));
+    assert!(debug.ends_with("name: Some(\"go\"), present: false, awaiting: 0 }"));
+}
+
+#[test]
+fn named_signal_p () {
+    let s = MpmcSignalPl::default().named("votes");
+    let s2 = s.clone();
+    let debug = move |()| format!("{:?}", s2);
+    let p1 = value_proc(()).pause().map(debug);
+    let p2 = value_proc(()).pause().pause().then(s.emit(1));
+    let p = s.await().join(p1.join(p2));
+    let (values, (debug, ())) = execute_process_parallel(p, 2);
+    assert_eq!(values, vec![1]);
+    assert!(debug.contains("name: Some(\"votes\"), present: false, awaiting: 1 }"));
+}
+
+#[test]
+#[should_panic(expected = "Multiple emissions of the single-producer signal `x`")]
+fn named_multiple_emission_s () {
+    let s = SpmcSignalSt::new().named("x");
+    execute_process(s.emit(1).join(s.emit(2)));
+}
+
+#[test]
+fn record_replay_s () {
+    let program = |recorder: &Recorder, factor: i32, shift: i32| {
+        let gather = |x: i32, acc: &mut i32| *acc += x;
+        let clicks = MpmcSignalSt::new(0, gather);
+        let score = MpmcSignalSt::new(0, gather);
+        let source = move |instant: usize| vec![instant as i32 + shift, 10];
+        let inject = recorder.inject("clicks", &clicks, source).pause().repeat(3);
+        let score2 = score.clone();
+        let react = clicks.await().and_then(move |v| score2.emit(v * factor));
+        let record = recorder.record("clicks", &clicks).join(recorder.record("score", &score));
+        record.then(inject.join(react))
+    };
+    let recorder = Recorder::new();
+    execute_process(program(&recorder, 1, 1));
+    let mut lines = Vec::new();
+    recorder.log().write_json_lines(&mut lines).unwrap();
+    let lines = String::from_utf8(lines).unwrap();
+    assert_eq!(lines.lines().next().unwrap(),
+               "{\"instant\":0,\"kind\":\"input\",\"signal\":\"clicks\",\"value\":\"1\"}");
+    assert!(lines.contains("{\"instant\":1,\"kind\":\"emitted\",\"signal\":\"score\",\"value\":\"11\"}"));
+    let log = Log::read_json_lines(lines.as_bytes()).unwrap();
+    assert_eq!(log, recorder.log());
+    // The inputs come from the log, whatever the host gives.
+    let replayer = Recorder::replay(log.clone());
+    execute_process(program(&replayer, 1, 100));
+    assert_eq!(replayer.first_divergence(), None);
+    let replayer = Recorder::replay(log);
+    execute_process(program(&replayer, 2, 1));
+    assert_eq!(replayer.first_divergence(), Some(1));
+}
+
+#[test]
+fn read_json_lines () {
+    let lines = "{ \"instant\": 3, \"kind\": \"input\", \
+                 \"signal\" : \"a\\\"b\", \"value\": \"\\ud83d\\ude00\\n\" }\n\n";
+    let log = Log::read_json_lines(lines.as_bytes()).unwrap();
+    assert_eq!(log.entries.len(), 1);
+    assert_eq!((log.entries[0].instant, log.entries[0].signal.as_str()), (3, "a\"b"));
+    assert_eq!(log.entries[0].value, "\u{1f600}\n");
+    let bad = "{\"instant\":0,\"kind\":\"input\",\"signal\":\"\\x\",\"value\":\"\"}";
+    let err = Log::read_json_lines(bad.as_bytes()).unwrap_err();
+    assert_eq!(err.to_string(), "invalid log entry at line 1: invalid escape");
+    let bad = "{\"instant\":0,\"kind\":\"input\",\"signal\":\"\\ud83d\",\"value\":\"\"}";
+    assert!(Log::read_json_lines(bad.as_bytes()).is_err());
+}
+
+#[test]
+fn record_replay_p () {
+    let program = |recorder: &Recorder, factor: i32| {
+        let gather = |x: i32, acc: &mut i32| *acc += x;
+        let clicks = MpmcSignalPl::new(0, gather);
+        let score = MpmcSignalPl::new(0, gather);
+        let source = |instant: usize| if instant == 1 { vec![5, 6] } else { vec![] };
+        let inject = recorder.inject("clicks", &clicks, source).pause().repeat(3);
+        let score2 = score.clone();
+        let react = clicks.await().and_then(move |v| score2.emit(v * factor));
+        let record = recorder.record("clicks", &clicks).join(recorder.record("score", &score));
+        record.then(inject.join(react))
+    };
+    let recorder = Recorder::new();
+    execute_process_parallel(program(&recorder, 1), 3);
+    let log = recorder.log();
+    let score: Vec<_> = log.entries.iter().filter(|e| e.signal == "score").collect();
+    assert_eq!(score.len(), 1);
+    assert_eq!((score[0].instant, score[0].value.as_str()), (2, "11"));
+    let replayer = Recorder::replay(log.clone());
+    execute_process_parallel(program(&replayer, 1), 2);
+    assert_eq!(replayer.first_divergence(), None);
+    let replayer = Recorder::replay(log);
+    execute_process_parallel(program(&replayer, 3), 2);
+    assert_eq!(replayer.first_divergence(), Some(2));
+}
@@ -0,0 +1,72 @@
+extern crate reactive;
+
+use reactive::process::{Process, ProcessMut, value_proc, join_all};
+use reactive::signal::{PureSignal, ValuedSignal};
+use reactive::signal::single_thread::{PureSignalSt, MpmcSignalSt};
+use reactive::signal::parallel::{PureSignalPl, MpmcSignalPl};
+use reactive::signal::record::Recorder;
+use reactive::testing::{divergent_seeds, assert_order_independent, assert_same_execution};
+
+#[test]
+fn shuffled_order () {
+    let program = || {
+        let s = MpmcSignalSt::default();
+        join_all((0..5).map(|i| value_proc(()).pause().then(s.emit(i)))).then(s.await())
+    };
+    let seeds = divergent_seeds(program, 0..20);
+    assert!(!seeds.is_empty());
+    let sorted = move || program().map(|mut v: Vec<i32>| { v.sort(); v });
+    assert!(divergent_seeds(sorted, 0..20).is_empty());
+    assert_order_independent(sorted, 0..20);
+}
+
+#[test]
+#[should_panic(expected = "With the seed")]
+fn shuffled_order_panic () {
+    let program = || {
+        let s = MpmcSignalSt::default();
+        join_all((0..5).map(|i| value_proc(()).pause().then(s.emit(i)))).then(s.await())
+    };
+    assert_order_independent(program, 0..20);
+}
+
+#[test]
+fn same_execution () {
+    let st = |recorder: &Recorder| {
+        let gather = |x: i32, acc: &mut i32| *acc += x;
+        let total = MpmcSignalSt::new(0, gather);
+        let tick = PureSignalSt::new();
+        let record = recorder.record("total", &total)
+            .join(recorder.record_presence("tick", &tick));
+        let emissions = join_all((0..4).map(|i| total.emit(i).pause().repeat(i as usize + 1)));
+        record.then(emissions.join(tick.emit().pause().pause().then(total.await())))
+    };
+    let pl = |recorder: &Recorder| {
+        let gather = |x: i32, acc: &mut i32| *acc += x;
+        let total = MpmcSignalPl::new(0, gather);
+        let tick = PureSignalPl::new();
+        let record = recorder.record("total", &total)
+            .join(recorder.record_presence("tick", &tick));
+        let emissions = join_all((0..4).map(|i| total.emit(i).pause().repeat(i as usize + 1)));
+        record.then(emissions.join(tick.emit().pause().pause().then(total.await())))
+    };
+    assert_same_execution(st, pl, &[1, 2, 4]);
+}
+
+#[test]
+#[should_panic(expected = "the signals first differ at instant 1")]
+fn same_execution_divergent () {
+    let st = |recorder: &Recorder| {
+        let gather = |x: i32, acc: &mut i32| *acc += x;
+        let total = MpmcSignalSt::new(0, gather);
+        let emissions = total.emit(1).pause().then(total.emit(2));
+        recorder.record("total", &total).then(emissions)
+    };
+    let pl = |recorder: &Recorder| {
+        let gather = |x: i32, acc: &mut i32| *acc += x;
+        let total = MpmcSignalPl::new(0, gather);
+        let emissions = total.emit(1).pause().then(total.emit(3));
+        recorder.record("total", &total).then(emissions)
+    };
+    assert_same_execution(st, pl, &[2]);
+}
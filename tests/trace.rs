@@ -0,0 +1,138 @@
+extern crate reactive;
+
+use std::sync::{Arc, Mutex};
+
+use reactive::process::{Process, value_proc, join_all};
+use reactive::process::{execute_process_traced, execute_process_parallel_traced};
+use reactive::process::{execute_process_with_stats, execute_process_parallel_with_stats};
+use reactive::runtime::{Subscriber, TraceEvent, BarrierKind, write_csv};
+use reactive::runtime::{ChromeTracer, StatsCollector};
+use reactive::signal::{Signal, PureSignal};
+use reactive::signal::single_thread::PureSignalSt;
+
+struct Recorder(Mutex<Vec<(usize, usize, TraceEvent)>>);
+
+impl Subscriber for Recorder {
+    fn event(&self, thread: usize, instant: usize, event: TraceEvent) {
+        self.0.lock().unwrap().push((thread, instant, event));
+    }
+}
+
+impl Recorder {
+    fn count<F>(&self, f: F) -> usize where F: Fn(&(usize, usize, TraceEvent)) -> bool {
+        self.0.lock().unwrap().iter().filter(|e| f(e)).count()
+    }
+}
+
+#[test]
+fn trace_s() {
+    let recorder = Arc::new(Recorder(Mutex::new(Vec::new())));
+    let s = PureSignalSt::new();
+    let p = s.await_immediate().join(value_proc(()).pause().then(s.emit()));
+    execute_process_traced(p, recorder.clone());
+    let events = recorder.0.lock().unwrap().clone();
+    assert!(events.iter().all(|&(thread, _, _)| thread == 0));
+    assert_eq!(events.first(), Some(&(0, 0, TraceEvent::InstantStart)));
+    assert_eq!(events.last(), Some(&(0, 1, TraceEvent::InstantEnd)));
+    assert_eq!(recorder.count(|e| e.2 == TraceEvent::InstantStart), 2);
+    assert_eq!(recorder.count(|e| e.2 == TraceEvent::AwaitRegistered && e.1 == 0), 1);
+    assert_eq!(recorder.count(|e| e.2 == TraceEvent::AwaitWoken && e.1 == 1), 1);
+    assert_eq!(recorder.count(|e| e.2 == TraceEvent::SignalEmitted && e.1 == 1), 1);
+    assert_eq!(recorder.count(|e| e.2 == TraceEvent::SignalReset && e.1 == 1), 1);
+    assert_eq!(recorder.count(|e| e.2 == TraceEvent::ContinuationStart),
+               recorder.count(|e| e.2 == TraceEvent::ContinuationExecuted));
+}
+
+#[test]
+fn trace_p() {
+    let recorder = Arc::new(Recorder(Mutex::new(Vec::new())));
+    let p = value_proc(2).pause().map(|v| v * 21);
+    assert_eq!(execute_process_parallel_traced(p, 3, recorder.clone()), 42);
+    for thread in 0..3 {
+        assert_eq!(recorder.count(|e| e.0 == thread && e.2 == TraceEvent::InstantStart), 2);
+        for &kind in &[BarrierKind::Works, BarrierKind::EndOfInstantWorks,
+                       BarrierKind::PresenceTests, BarrierKind::SignalsReset] {
+            assert_eq!(recorder.count(
+                |e| e.0 == thread && e.2 == TraceEvent::BarrierWait(kind)), 2);
+            assert_eq!(recorder.count(
+                |e| e.0 == thread && e.2 == TraceEvent::BarrierPassed(kind)), 2);
+        }
+    }
+    assert!(recorder.0.lock().unwrap().iter().all(|&(thread, _, e)| match e {
+        TraceEvent::StealSuccess { from } => from != thread,
+        _ => true,
+    }));
+    assert_eq!(recorder.count(|e| e.2 == TraceEvent::ContinuationStart),
+               recorder.count(|e| e.2 == TraceEvent::ContinuationExecuted));
+}
+
+#[test]
+fn stats_s() {
+    let s = PureSignalSt::new();
+    let p = s.await_immediate().join(value_proc(()).pause().then(s.emit()));
+    let (_, stats) = execute_process_with_stats(p);
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats.iter().map(|s| s.instant).collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(stats.iter().map(|s| s.awaits_pending).collect::<Vec<_>>(), vec![1, 0]);
+    assert_eq!(stats.iter().map(|s| s.signals_emitted).collect::<Vec<_>>(), vec![0, 1]);
+    assert!(stats.iter().all(|s| s.continuations.len() == 1 && s.continuations[0] > 0));
+    let mut csv = Vec::new();
+    write_csv(&stats, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("instant,continuations_0,steal_attempts"));
+    assert!(lines[2].starts_with("1,"));
+    assert!(lines[2].ends_with(",1,0"));
+}
+
+#[test]
+fn stats_p() {
+    let p = value_proc(2).pause().pause().map(|v| v * 21);
+    let (v, stats) = execute_process_parallel_with_stats(p, 4);
+    assert_eq!(v, 42);
+    assert_eq!(stats.len(), 3);
+    for s in &stats {
+        assert_eq!(s.continuations.len(), 4);
+        assert!(s.steal_successes <= s.steal_attempts);
+        assert_eq!(s.awaits_pending, 0);
+    }
+    let mut csv = Vec::new();
+    write_csv(&stats, &mut csv).unwrap();
+    let header = String::from_utf8(csv).unwrap().lines().next().unwrap().to_owned();
+    assert_eq!(header.split(',').count(), 1 + 4 + 7);
+}
+
+#[test]
+#[should_panic(expected = "created for 2 runtimes but the runtime 3")]
+fn stats_too_few_runtimes() {
+    StatsCollector::new(2).event(3, 0, TraceEvent::InstantStart);
+}
+
+#[test]
+fn chrome_trace_p() {
+    let tracer = Arc::new(ChromeTracer::new());
+    let mut ps = Vec::new();
+    for i in 0..20 {
+        ps.push(value_proc(i).pause().map(|v| v + 1));
+    }
+    let p = join_all(ps);
+    assert_eq!(execute_process_parallel_traced(p, 4, tracer.clone()),
+               (1..21).collect::<Vec<_>>());
+    let mut json = Vec::new();
+    tracer.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.starts_with("{\"traceEvents\":["));
+    assert!(json.ends_with("]}\n"));
+    assert_eq!(json.matches("\"name\":\"instant\"").count(), 2);
+    assert_eq!(json.matches("\"name\":\"reset barrier\"").count(), 2 * 4);
+    assert!(json.matches("\"name\":\"continuation\"").count() >= 40);
+    assert!(!json.contains(",\n]"));
+}
+
+#[test]
+fn chrome_trace_empty() {
+    let mut json = Vec::new();
+    ChromeTracer::default().write_json(&mut json).unwrap();
+    assert_eq!(String::from_utf8(json).unwrap(), "{\"traceEvents\":[\n]}\n");
+}